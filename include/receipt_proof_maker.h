@@ -0,0 +1,66 @@
+#ifndef RECEIPT_PROOF_MAKER_H
+#define RECEIPT_PROOF_MAKER_H
+
+/* Generated by cbindgen from src/ffi.rs. Do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+#define RECEIPT_PROOF_OK 0
+
+#define RECEIPT_PROOF_NULL_POINTER 1
+
+#define RECEIPT_PROOF_INVALID_PROOF 2
+
+#define RECEIPT_PROOF_INVALID_INPUT 3
+
+#define RECEIPT_PROOF_BUFFER_TOO_SMALL 4
+
+#define RECEIPT_PROOF_PANIC 5
+
+/*
+ Verifies an RLP-encoded receipt proof against a 32 byte receipts root & on
+ success copies the proven receipt into `receipt_out`. `receipt_len_out` is
+ always set to the receipt's length when the proof is valid, so a caller
+ getting `RECEIPT_PROOF_BUFFER_TOO_SMALL` can retry with a big enough buffer.
+
+ # Safety
+
+ `proof` must point to `proof_len` readable bytes, `receipts_root` to 32
+ readable bytes, `receipt_out` to `receipt_out_capacity` writable bytes &
+ `receipt_len_out` to a writable `size_t`.
+ */
+int32_t receipt_proof_verify(const uint8_t *proof,
+                             size_t proof_len,
+                             const uint8_t *receipts_root,
+                             size_t tx_index,
+                             uint8_t *receipt_out,
+                             size_t receipt_out_capacity,
+                             size_t *receipt_len_out);
+
+/*
+ Builds the receipts trie of a block from its RLP-encoded receipts, given in
+ tx order, writing the receipts root into `receipts_root_out` & the RLP-encoded
+ proof of the receipt at `tx_index` into `proof_out`. `proof_len_out` is
+ always set to the proof's length when one could be built, so a caller getting
+ `RECEIPT_PROOF_BUFFER_TOO_SMALL` can retry with a big enough buffer.
+
+ # Safety
+
+ `receipts` & `receipt_lens` must point to `num_receipts` receipt pointers &
+ lengths, each receipt pointer to its length's worth of readable bytes,
+ `proof_out` to `proof_out_capacity` writable bytes, `proof_len_out` to a
+ writable `size_t` & `receipts_root_out` to 32 writable bytes.
+ */
+int32_t receipt_proof_generate(const uint8_t *const *receipts,
+                               const size_t *receipt_lens,
+                               size_t num_receipts,
+                               size_t tx_index,
+                               uint8_t *proof_out,
+                               size_t proof_out_capacity,
+                               size_t *proof_len_out,
+                               uint8_t *receipts_root_out);
+
+#endif /* RECEIPT_PROOF_MAKER_H */
@@ -0,0 +1,219 @@
+use std::sync::Mutex;
+use ethereum_types::H256;
+use crate::errors::AppError;
+use crate::patricia_trie::Trie;
+use crate::rpc_cache::RpcCache;
+use crate::get_block::get_block_by_blockhash;
+use crate::utils::convert_bytes_to_hex;
+use crate::get_branch_from_trie::find_branch_in_trie;
+use crate::check_block_finality::check_block_finality;
+use crate::check_receipts_root::warn_on_receipts_root_mismatch;
+use crate::get_tx_index::get_tx_index_from_transactions;
+use crate::get_hex_proof_from_branch::get_hex_proof_from_branch;
+use crate::get_receipts::get_receipts_from_tx_hashes;
+use crate::get_receipts_trie::get_receipts_trie_from_receipts;
+use crate::types::{
+    Block,
+    Result,
+    Receipt,
+    NodeStack,
+    ReceiptProof,
+    FinalityRequirement,
+};
+
+#[derive(Debug)]
+pub struct BlockProofContext {
+    pub block: Block,
+    pub receipts: Vec<Receipt>,
+    pub receipts_root: H256,
+    trie: Mutex<Option<Trie>>,
+}
+
+impl BlockProofContext {
+    pub fn new(block: Block, receipts: Vec<Receipt>) -> Result<BlockProofContext> {
+        if receipts.len() != block.transactions.len() {
+            return Err(AppError::Custom(
+                format!(
+                    "✘ Block {} has {} transactions but {} receipts were given!",
+                    block.number,
+                    block.transactions.len(),
+                    receipts.len(),
+                )
+            ))
+        }
+        info!("✔ Building merkle-patricia trie from {} receipts...", receipts.len());
+        let trie = get_receipts_trie_from_receipts(&receipts)?;
+        // NOTE: Only warns until the typed receipts encoding is checked against a real post-London block.
+        warn_on_receipts_root_mismatch(&block, trie.root);
+        Ok(
+            BlockProofContext {
+                receipts_root: trie.root,
+                trie: Mutex::new(Some(trie)),
+                block,
+                receipts,
+            }
+        )
+    }
+
+    pub fn from_block_hash(
+        endpoint: &str,
+        block_hash: H256,
+        finality_requirement: &FinalityRequirement,
+        maybe_cache: Option<&RpcCache>,
+    ) -> Result<BlockProofContext> {
+        info!("✔ Getting block {:?} & all its receipts...", block_hash);
+        let block = get_block_by_blockhash(endpoint, block_hash, maybe_cache)?;
+        check_block_finality(endpoint, &block, finality_requirement)
+            .and_then(|_| get_receipts_from_tx_hashes(endpoint, &block.transactions, maybe_cache))
+            .and_then(|receipts| BlockProofContext::new(block, receipts))
+    }
+
+    pub fn get_tx_index(&self, tx_hash: &H256) -> Result<usize> {
+        get_tx_index_from_transactions(tx_hash, &self.block.transactions)
+    }
+
+    pub fn get_receipt(&self, index: usize) -> Result<&Receipt> {
+        self.receipts
+            .get(index)
+            .ok_or_else(|| AppError::Custom(
+                format!("✘ No receipt at index {} in block {}!", index, self.block.number)
+            ))
+    }
+
+    pub fn get_branch(&self, index: usize) -> Result<NodeStack> {
+        // NOTE: Finding a branch consumes the trie, so it's taken out of the context & put back.
+        // An in-range index is always in the trie, so it's only lost if the trie is corrupt.
+        self.get_receipt(index)?;
+        let mut maybe_trie = self.trie
+            .lock()
+            .map_err(|_| AppError::Custom("✘ Receipts trie lock was poisoned!".to_string()))?;
+        match maybe_trie.take() {
+            Some(trie) => find_branch_in_trie(trie, index)
+                .map(|(trie, branch)| {
+                    *maybe_trie = Some(trie);
+                    branch
+                }),
+            None => Err(AppError::Custom(
+                format!("✘ Receipts trie of block {} is unavailable!", self.block.number)
+            )),
+        }
+    }
+
+    pub fn get_branch_for_tx_hash(&self, tx_hash: &H256) -> Result<NodeStack> {
+        self.get_tx_index(tx_hash)
+            .and_then(|index| self.get_branch(index))
+    }
+
+    pub fn get_receipt_proof_for_index(&self, tx_index: usize) -> Result<ReceiptProof> {
+        let receipt = self.get_receipt(tx_index)?;
+        self.get_branch(tx_index)
+            .and_then(|branch| get_hex_proof_from_branch(&branch))
+            .map(|proof|
+                ReceiptProof {
+                    proof,
+                    tx_index,
+                    tx_hash: self.block.transactions[tx_index],
+                    block_hash: self.block.hash,
+                    block_number: self.block.number.as_u64(),
+                    receipts_root: self.block.receipts_root,
+                    receipt_data: convert_bytes_to_hex(rlp::encode(receipt)),
+                }
+            )
+    }
+
+    pub fn get_receipt_proof(&self, tx_hash: &H256) -> Result<ReceiptProof> {
+        self.get_tx_index(tx_hash)
+            .and_then(|tx_index| self.get_receipt_proof_for_index(tx_index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::get_branch_from_trie::get_branch_from_trie;
+    use crate::test_utils::{
+        get_sample_receipts,
+        get_sample_mock_node,
+        get_expected_block_1,
+        get_sample_tx_hashes_1,
+        SAMPLE_RECEIPT_JSONS_1_PATH,
+    };
+
+    fn get_sample_receipts_1() -> Vec<Receipt> {
+        get_sample_receipts(
+            SAMPLE_RECEIPT_JSONS_1_PATH.to_string(),
+            get_sample_tx_hashes_1(),
+        )
+    }
+
+    fn get_sample_block_proof_context() -> BlockProofContext {
+        BlockProofContext::new(get_expected_block_1(), get_sample_receipts_1())
+            .unwrap()
+    }
+
+    #[test]
+    fn should_hand_out_same_branches_as_fresh_tries() {
+        let context = get_sample_block_proof_context();
+        let trie = get_receipts_trie_from_receipts(&context.receipts)
+            .unwrap();
+        assert!(context.receipts_root == context.block.receipts_root);
+        [0, 14, context.receipts.len() - 1, 14]
+            .iter()
+            .for_each(|index|
+                assert!(context.get_branch(*index).unwrap() == get_branch_from_trie(trie.clone(), *index).unwrap())
+            );
+    }
+
+    #[test]
+    fn should_not_err_when_receipts_root_does_not_match_header() {
+        let mut block = get_expected_block_1();
+        block.receipts_root = H256::zero();
+        let result = BlockProofContext::new(block, get_sample_receipts_1())
+            .unwrap();
+        assert!(result.receipts_root != result.block.receipts_root);
+    }
+
+    #[test]
+    fn should_get_receipt_proofs_by_tx_hash_and_index() {
+        let context = get_sample_block_proof_context();
+        let tx_hash = context.block.transactions[14];
+        let result = context.get_receipt_proof(&tx_hash)
+            .unwrap();
+        assert!(result.tx_index == 14);
+        assert!(result.tx_hash == tx_hash);
+        assert!(result == context.get_receipt_proof_for_index(14).unwrap());
+        assert!(context.get_branch_for_tx_hash(&tx_hash).unwrap() == context.get_branch(14).unwrap());
+    }
+
+    #[test]
+    fn should_err_on_index_out_of_range() {
+        let context = get_sample_block_proof_context();
+        match context.get_receipt_proof_for_index(context.receipts.len()) {
+            Err(AppError::Custom(e)) => assert!(e.contains("No receipt at index")),
+            _ => panic!("Out of range index should not be accepted!"),
+        }
+    }
+
+    #[test]
+    fn should_err_when_receipts_do_not_match_transactions() {
+        let mut receipts = get_sample_receipts_1();
+        receipts.pop();
+        match BlockProofContext::new(get_expected_block_1(), receipts) {
+            Err(AppError::Custom(e)) => assert!(e.contains("receipts were given")),
+            _ => panic!("Missing receipts should not be accepted!"),
+        }
+    }
+
+    #[test]
+    fn should_get_block_proof_context_from_block_hash() {
+        let mock_node = get_sample_mock_node();
+        let block = get_expected_block_1();
+        let result = BlockProofContext::from_block_hash(
+            &mock_node.get_endpoint(),
+            block.hash,
+            &FinalityRequirement::Confirmations(0),
+            None,
+        ).unwrap();
+        assert!(result.receipts.len() == block.transactions.len());
+    }
+}
@@ -0,0 +1,163 @@
+use ethereum_types::U256;
+use crate::errors::AppError;
+use crate::get_block::get_block_by_number;
+use crate::types::{
+    Block,
+    Result,
+    FinalityRequirement,
+};
+
+fn check_block_is_canonical(endpoint: &str, block: &Block) -> Result<()> {
+    get_block_by_number(endpoint, &block.number.to_string())
+        .and_then(|canonical_block|
+            match canonical_block.hash == block.hash {
+                true => Ok(()),
+                false => Err(AppError::NotCanonical(
+                    format!(
+                        "✘ Block {:?} at height {} was replaced by {:?}!",
+                        block.hash,
+                        block.number,
+                        canonical_block.hash,
+                    )
+                ))
+            }
+        )
+}
+
+fn check_block_has_confirmations(
+    endpoint: &str,
+    block: &Block,
+    min_confirmations: u64,
+) -> Result<()> {
+    get_block_by_number(endpoint, "latest")
+        .and_then(|latest_block| {
+            let confirmations = latest_block.number.saturating_sub(block.number);
+            match confirmations >= U256::from(min_confirmations) {
+                true => Ok(()),
+                false => Err(AppError::NotYetFinal(
+                    format!(
+                        "✘ Block {} has {} of the {} required confirmations!",
+                        block.number,
+                        confirmations,
+                        min_confirmations,
+                    )
+                ))
+            }
+        })
+}
+
+fn check_block_is_not_ahead_of_tag(
+    endpoint: &str,
+    block: &Block,
+    tag: &str,
+) -> Result<()> {
+    get_block_by_number(endpoint, tag)
+        .and_then(|tagged_block|
+            match block.number <= tagged_block.number {
+                true => Ok(()),
+                false => Err(AppError::NotYetFinal(
+                    format!(
+                        "✘ Block {} is ahead of the `{}` block {}!",
+                        block.number,
+                        tag,
+                        tagged_block.number,
+                    )
+                ))
+            }
+        )
+}
+
+pub fn check_block_finality(
+    endpoint: &str,
+    block: &Block,
+    finality_requirement: &FinalityRequirement,
+) -> Result<()> {
+    match finality_requirement {
+        FinalityRequirement::Confirmations(min_confirmations) =>
+            check_block_has_confirmations(endpoint, block, *min_confirmations),
+        FinalityRequirement::BlockTag(tag) =>
+            check_block_is_not_ahead_of_tag(endpoint, block, tag),
+    }
+        .and_then(|_| check_block_is_canonical(endpoint, block))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethereum_types::H256;
+    use crate::mock_node::MockNode;
+    use crate::test_utils::{
+        get_expected_block,
+        get_expected_block_1,
+        get_sample_mock_node,
+        get_sample_mock_node_fixtures,
+    };
+
+    fn get_mock_node_with_finalized_tag() -> MockNode {
+        get_sample_mock_node_fixtures()
+            .map(|fixtures| fixtures.add_block_tag("finalized", 0x7da175))
+            .and_then(MockNode::start)
+            .unwrap()
+    }
+
+    #[test]
+    fn should_pass_confirmations_check_for_deep_block() {
+        let mock_node = get_sample_mock_node();
+        let requirement = FinalityRequirement::Confirmations(64);
+        check_block_finality(
+            &mock_node.get_endpoint(),
+            &get_expected_block(),
+            &requirement,
+        ).unwrap();
+    }
+
+    #[test]
+    fn should_fail_confirmations_check_for_block_at_tip() {
+        let mock_node = get_sample_mock_node();
+        let requirement = FinalityRequirement::Confirmations(64);
+        match check_block_finality(
+            &mock_node.get_endpoint(),
+            &get_expected_block_1(),
+            &requirement,
+        ) {
+            Err(AppError::NotYetFinal(e)) =>
+                assert!(e.contains("0 of the 64 required")),
+            _ => panic!("Block at tip should not be final!"),
+        }
+    }
+
+    #[test]
+    fn should_pass_finalized_tag_check_for_finalized_block() {
+        let mock_node = get_mock_node_with_finalized_tag();
+        let requirement = FinalityRequirement::BlockTag("finalized".to_string());
+        check_block_finality(
+            &mock_node.get_endpoint(),
+            &get_expected_block(),
+            &requirement,
+        ).unwrap();
+    }
+
+    #[test]
+    fn should_fail_finalized_tag_check_for_block_ahead_of_it() {
+        let mock_node = get_mock_node_with_finalized_tag();
+        let requirement = FinalityRequirement::BlockTag("finalized".to_string());
+        match check_block_finality(
+            &mock_node.get_endpoint(),
+            &get_expected_block_1(),
+            &requirement,
+        ) {
+            Err(AppError::NotYetFinal(_)) => assert!(true),
+            _ => panic!("Block ahead of finalized block should not be final!"),
+        }
+    }
+
+    #[test]
+    fn should_fail_canonical_check_for_reorged_block() {
+        let mock_node = get_sample_mock_node();
+        let mut reorged_block = get_expected_block();
+        reorged_block.hash = H256::zero();
+        match check_block_is_canonical(&mock_node.get_endpoint(), &reorged_block) {
+            Err(AppError::NotCanonical(_)) => assert!(true),
+            _ => panic!("Reorged block should not be canonical!"),
+        }
+    }}
@@ -0,0 +1,124 @@
+use ethereum_types::H256;
+use crate::errors::AppError;
+use crate::get_log::check_logs_bloom_of_block;
+use crate::get_receipts::get_receipts_from_tx_hashes;
+use crate::get_receipts_trie::get_receipts_trie_from_receipts;
+use crate::types::{
+    Block,
+    Result,
+};
+
+pub fn check_receipts_root_of_block(block: &Block, receipts_root: H256) -> Result<H256> {
+    match receipts_root == block.receipts_root {
+        true => {
+            info!("✔ Receipts root matches header: {:?}", receipts_root);
+            Ok(receipts_root)
+        },
+        false => Err(AppError::Custom(
+            format!(
+                "✘ Recomputed receipts root {:?} of block {} does not match header's {:?}!",
+                receipts_root,
+                block.number,
+                block.receipts_root,
+            )
+        ))
+    }
+}
+
+pub fn warn_on_receipts_root_mismatch(block: &Block, receipts_root: H256) {
+    if let Err(e) = check_receipts_root_of_block(block, receipts_root) {
+        warn!("{} Proofs from this block will not verify against its header!", e);
+    }
+}
+
+pub fn check_receipts_root(endpoint: &str, block: &Block) -> Result<H256> {
+    info!("✔ Recomputing receipts root of block {}...", block.number);
+    let receipts = get_receipts_from_tx_hashes(endpoint, &block.transactions, None)?;
+    get_receipts_trie_from_receipts(&receipts)
+        .and_then(|trie| check_receipts_root_of_block(block, trie.root))
+        .and_then(|receipts_root| {
+            check_logs_bloom_of_block(block, &receipts)?;
+            info!("✔ Logs bloom matches header!");
+            Ok(receipts_root)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethereum_types::Bloom;
+    use crate::utils::convert_hex_to_h256;
+    use crate::get_log::get_logs_bloom_from_receipts;
+    use crate::test_utils::{
+        RECEIPTS_ROOT_1,
+        RECEIPTS_ROOT_2,
+        get_sample_receipts,
+        get_sample_mock_node,
+        get_expected_block_1,
+        get_sample_tx_hashes_1,
+        SAMPLE_RECEIPT_JSONS_1_PATH,
+    };
+
+    #[test]
+    fn should_match_real_receipts_root_of_block() {
+        let receipts = get_sample_receipts(
+            SAMPLE_RECEIPT_JSONS_1_PATH.to_string(),
+            get_sample_tx_hashes_1(),
+        );
+        let block = get_expected_block_1();
+        let receipts_root = get_receipts_trie_from_receipts(&receipts)
+            .unwrap()
+            .root;
+        let result = check_receipts_root_of_block(&block, receipts_root)
+            .unwrap();
+        assert!(result == convert_hex_to_h256(RECEIPTS_ROOT_1.to_string()).unwrap());
+    }
+
+    #[test]
+    fn should_err_when_receipts_root_does_not_match_header() {
+        let block = get_expected_block_1();
+        match check_receipts_root_of_block(&block, H256::zero()) {
+            Err(AppError::Custom(e)) => assert!(e.contains("does not match header's")),
+            _ => panic!("Receipts root not in the header should not be accepted!"),
+        }
+    }
+
+    #[test]
+    fn should_check_receipts_root_of_block() {
+        let mock_node = get_sample_mock_node();
+        let block = get_expected_block_1();
+        let result = check_receipts_root(&mock_node.get_endpoint(), &block)
+            .unwrap();
+        assert!(result == block.receipts_root);
+    }
+
+    #[test]
+    fn should_err_on_receipts_root_mismatch() {
+        let mock_node = get_sample_mock_node();
+        let mut block = get_expected_block_1();
+        block.receipts_root = convert_hex_to_h256(RECEIPTS_ROOT_2.to_string()).unwrap();
+        match check_receipts_root(&mock_node.get_endpoint(), &block) {
+            Err(AppError::Custom(e)) => assert!(e.contains("does not match")),
+            _ => panic!("Mismatched receipts root should not be accepted!"),
+        }
+    }
+
+    #[test]
+    fn should_err_on_logs_bloom_mismatch() {
+        let mock_node = get_sample_mock_node();
+        let receipts = get_sample_receipts(
+            SAMPLE_RECEIPT_JSONS_1_PATH.to_string(),
+            get_sample_tx_hashes_1(),
+        );
+        let mut block = get_expected_block_1();
+        block.receipts_root = get_receipts_trie_from_receipts(&receipts)
+            .unwrap()
+            .root;
+        block.logs_bloom = Bloom::default();
+        assert!(get_logs_bloom_from_receipts(&receipts) != block.logs_bloom);
+        match check_receipts_root(&mock_node.get_endpoint(), &block) {
+            Err(AppError::Custom(e)) => assert!(e.contains("does not match its receipts")),
+            _ => panic!("Mismatched logs bloom should not be accepted!"),
+        }
+    }
+}
@@ -19,16 +19,17 @@ pub fn connect_to_node(state: State) -> Result<State> {
 mod tests {
     use super::*;
     use crate::test_utils::{
-        WORKING_ENDPOINT,
+        get_sample_mock_node,
         get_valid_tx_hash_h256,
         get_valid_initial_state,
     };
 
     #[test]
     fn should_connect_to_node_and_return_state_when_endpoint_works() {
+        let mock_node = get_sample_mock_node();
         let state = State::set_endpoint_in_state(
             get_valid_initial_state().unwrap(),
-            WORKING_ENDPOINT.to_string()
+            mock_node.get_endpoint()
         ).unwrap();
         let expected_tx_hash = get_valid_tx_hash_h256()
             .unwrap();
@@ -1,23 +1,29 @@
-use crate::types::Byte;
 use ethereum_types::H256;
-use crate::nibble_utils::Nibbles;
+pub use receipt_proof_verifier::constants::{
+    ZERO_BYTE,
+    EMPTY_NIBBLES,
+    HIGH_NIBBLE_MASK,
+    LEAF_NODE_STRING,
+    BRANCH_NODE_STRING,
+    NUM_BITS_IN_NIBBLE,
+    NUM_NIBBLES_IN_BYTE,
+    EXTENSION_NODE_STRING,
+};
 
-pub const ZERO_BYTE: u8 = 0u8;
 pub const HASH_LENGTH: usize  = 32;
 pub const HASH_HEX_CHARS: usize  = 64;
+pub const MAX_TX_TYPE: u8 = 0x7f;
 pub const HEX_PREFIX_LENGTH: usize = 2;
-pub const NUM_BITS_IN_NIBBLE: usize = 4;
 pub const REQWEST_TIMEOUT_TIME: u64 = 5;
-pub const NUM_NIBBLES_IN_BYTE: usize = 2;
-pub const HIGH_NIBBLE_MASK: Byte = 15u8; // NOTE: 15u8 == [0,0,0,0,1,1,1,1]
+pub const DEFAULT_CACHE_SIZE_IN_MB: u64 = 256;
+pub const CACHE_FINALITY_CONFIRMATIONS: u64 = 64;
+pub const DEFAULT_MIN_CONFIRMATIONS: u64 = 64;
 pub static DOT_ENV_PATH: &'static str = "./.env";
+pub static CONFIG_FILE_PATH: &'static str = "./config.json";
 pub static LOG_FILE_PATH: &'static str = "logs/";
-pub static LEAF_NODE_STRING: &'static str = "leaf";
-pub static BRANCH_NODE_STRING: &'static str = "branch";
-pub static EXTENSION_NODE_STRING: &'static str = "extension";
 pub const HASHED_NULL_NODE: H256 = H256(HASHED_NULL_NODE_BYTES);
+pub const BLOCK_TAGS: [&str; 3] = ["latest", "safe", "finalized"];
 pub static DEFAULT_ENDPOINT: &'static str = "https://mainnet.infura.io/v3/9c7178cede9f4a8a84a151d058bd609c";
-pub const EMPTY_NIBBLES: Nibbles = Nibbles { data: Vec::new(), offset: 0 };
 pub static LOCK_EVENT_STRING: &'static str = "413055b58d692937cc2a7d80ca019c17e8d01175e58d11f157ae9124078b01d6";
 
 const HASHED_NULL_NODE_BYTES: [u8; 32] = [ // NOTE: keccak hash of the RLP of null
@@ -0,0 +1,201 @@
+#[cfg(feature = "native")]
+use std::fs;
+use rlp::Rlp;
+use ethereum_types::H256;
+use serde_json::Value as Json;
+use crate::errors::AppError;
+use crate::trie_nodes::rlp_decode_node;
+use crate::get_keccak_hash::keccak_hash_bytes;
+use crate::types::{
+    Bytes,
+    Result,
+};
+use crate::utils::{
+    convert_hex_to_h256,
+    convert_bytes_to_hex,
+    convert_hex_to_bytes,
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProofFile {
+    pub proof: Bytes,
+    pub tx_index: Option<usize>,
+    pub block_hash: Option<H256>,
+}
+
+fn is_hex_string(contents: &str) -> bool {
+    let hex = contents.trim_start_matches("0x");
+    !hex.is_empty() && hex.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+fn get_proof_file_from_json(json: Json) -> Result<ProofFile> {
+    match json["proof"].as_str() {
+        None => Err(AppError::Custom(
+            "✘ Proof JSON has no `proof` field!".to_string()
+        )),
+        Some(hex_proof) => Ok(
+            ProofFile {
+                proof: convert_hex_to_bytes(hex_proof.to_string())?,
+                tx_index: json["tx_index"].as_u64().map(|index| index as usize),
+                block_hash: match json["block_hash"].as_str() {
+                    Some(hash) => Some(convert_hex_to_h256(hash.to_string())?),
+                    None => None,
+                },
+            }
+        )
+    }
+}
+
+pub fn get_proof_file_from_bytes(bytes: Bytes) -> Result<ProofFile> {
+    match String::from_utf8(bytes.clone()) {
+        Ok(ref contents) if contents.trim_start().starts_with('{') =>
+            serde_json::from_str(contents)
+                .map_err(AppError::from)
+                .and_then(get_proof_file_from_json),
+        Ok(ref contents) if is_hex_string(contents.trim()) => Ok(
+            ProofFile {
+                proof: convert_hex_to_bytes(contents.trim().to_string())?,
+                tx_index: None,
+                block_hash: None,
+            }
+        ),
+        _ => Ok(ProofFile { proof: bytes, tx_index: None, block_hash: None }),
+    }
+}
+
+#[cfg(feature = "native")]
+pub fn read_proof_file(path: &str) -> Result<ProofFile> {
+    info!("✔ Reading proof file at {}...", path);
+    fs::read(path)
+        .map_err(|e| AppError::Custom(
+            format!("✘ Cannot read proof file at {}!\n✘ {}", path, e)
+        ))
+        .and_then(get_proof_file_from_bytes)
+}
+
+pub fn get_proof_nodes(proof: &Bytes) -> Result<Vec<Bytes>> {
+    let rlp = Rlp::new(proof);
+    match rlp.is_list() {
+        false => Err(AppError::Custom(
+            "✘ Proof is not an RLP list of trie nodes!".to_string()
+        )),
+        true => rlp
+            .item_count()
+            .and_then(|num_nodes|
+                (0..num_nodes)
+                    .map(|i| rlp.at(i).map(|node| node.as_raw().to_vec()))
+                    .collect::<std::result::Result<Vec<Bytes>, _>>()
+            )
+            .map_err(|e| AppError::Custom(
+                format!("✘ Cannot decode proof nodes: {}", e)
+            ))
+    }
+}
+
+fn get_decoded_node_string(index: usize, encoded_node: Bytes) -> Result<String> {
+    let hash = keccak_hash_bytes(&encoded_node)?;
+    let node = rlp_decode_node(encoded_node)?;
+    let mut lines = vec![format!("Node {}: {} {:?}", index, node.get_type(), hash)];
+    match &node.branch {
+        Some(branch) => branch.branches
+            .iter()
+            .enumerate()
+            .filter_map(|(i, child)| child.as_ref().map(|child| (i, child)))
+            .for_each(|(i, child)|
+                lines.push(
+                    format!("    [{:x}]   0x{}", i, convert_bytes_to_hex(child.clone()))
+                )
+            ),
+        None => lines.push(format!("    path:  {:?}", node.get_key())),
+    };
+    if let Some(value) = node.get_value() {
+        lines.push(format!("    value: 0x{}", convert_bytes_to_hex(value)));
+    }
+    Ok(lines.join("\n"))
+}
+
+pub fn get_decoded_proof_string(proof: &Bytes) -> Result<String> {
+    info!("✔ Decoding nodes in proof...");
+    get_proof_nodes(proof)?
+        .into_iter()
+        .enumerate()
+        .map(|(i, encoded_node)| get_decoded_node_string(i, encoded_node))
+        .collect::<Result<Vec<String>>>()
+        .map(|node_strings| format!("{}\n", node_strings.join("\n\n")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{
+        get_temp_cache_dir,
+        get_sample_proof_1,
+        get_valid_block_hash_h256,
+    };
+
+    fn get_sample_proof_1_bytes() -> Bytes {
+        convert_hex_to_bytes(get_sample_proof_1()).unwrap()
+    }
+
+    #[test]
+    fn should_get_proof_nodes_from_proof() {
+        let result = get_proof_nodes(&get_sample_proof_1_bytes())
+            .unwrap();
+        assert!(result.len() == 3);
+        result
+            .into_iter()
+            .for_each(|encoded_node| assert!(rlp_decode_node(encoded_node).is_ok()));
+    }
+
+    #[test]
+    fn should_err_on_proof_that_is_not_an_rlp_list() {
+        match get_proof_nodes(&vec![0x83, 0x01, 0x02, 0x03]) {
+            Err(AppError::Custom(e)) => assert!(e.contains("not an RLP list")),
+            _ => panic!("Non-list proof should not be accepted!"),
+        }
+    }
+
+    #[test]
+    fn should_get_decoded_proof_string() {
+        let result = get_decoded_proof_string(&get_sample_proof_1_bytes())
+            .unwrap();
+        assert!(result.starts_with("Node 0: branch 0x"));
+        assert!(result.contains("Node 1: branch"));
+        assert!(result.contains("Node 2: leaf"));
+        assert!(result.contains("    [e]   0x"));
+    }
+
+    #[test]
+    fn should_get_proof_file_from_hex_json_and_raw_bytes() {
+        let proof = get_sample_proof_1_bytes();
+        let block_hash = get_valid_block_hash_h256().unwrap();
+        let json = json!({
+            "proof": get_sample_proof_1(),
+            "tx_index": 14,
+            "block_hash": format!("{:?}", block_hash),
+        });
+        let result_1 = get_proof_file_from_bytes(
+            format!("0x{}\n", get_sample_proof_1()).into_bytes()
+        ).unwrap();
+        let result_2 = get_proof_file_from_bytes(json.to_string().into_bytes())
+            .unwrap();
+        let result_3 = get_proof_file_from_bytes(proof.clone())
+            .unwrap();
+        assert!(result_1 == ProofFile { proof: proof.clone(), tx_index: None, block_hash: None });
+        assert!(result_2.proof == proof);
+        assert!(result_2.tx_index == Some(14));
+        assert!(result_2.block_hash == Some(block_hash));
+        assert!(result_3 == result_1);
+    }
+
+    #[test]
+    fn should_read_proof_file() {
+        let dir = get_temp_cache_dir("proof-file");
+        fs::create_dir_all(&dir).unwrap();
+        let path = format!("{}/proof", dir);
+        fs::write(&path, get_sample_proof_1()).unwrap();
+        let result = read_proof_file(&path)
+            .unwrap();
+        assert!(result.proof == get_sample_proof_1_bytes());
+    }
+}
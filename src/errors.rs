@@ -1,5 +1,6 @@
 use hex;
 use log;
+#[cfg(feature = "native")]
 use reqwest;
 use std::fmt;
 use serde_json;
@@ -8,8 +9,11 @@ use std::error::Error;
 #[derive(Debug)]
 pub enum AppError {
     Custom(String),
+    NotYetFinal(String),
+    NotCanonical(String),
     IOError(std::io::Error),
     HexError(hex::FromHexError),
+    #[cfg(feature = "native")]
     ReqwestError(reqwest::Error),
     SerdeJsonError(serde_json::Error),
     NoneError(std::option::NoneError),
@@ -21,6 +25,10 @@ impl fmt::Display for AppError {
         let msg = match *self {
             AppError::Custom(ref msg) =>
                 format!("{}", msg),
+            AppError::NotYetFinal(ref msg) =>
+                format!("✘ Block not yet final!\n✘ {}", msg),
+            AppError::NotCanonical(ref msg) =>
+                format!("✘ Block not in canonical chain!\n✘ {}", msg),
             AppError::HexError(ref e) =>
                 format!("✘ Hex Error!\n✘ {}", e),
             AppError::IOError(ref e) =>
@@ -31,6 +39,7 @@ impl fmt::Display for AppError {
                 format!("✘ Nothing to unwrap!\n✘ {:?}", e),
             AppError::SetLoggerError(ref e) =>
                 format!("✘ Error setting up logger!\n✘ {}", e),
+            #[cfg(feature = "native")]
             AppError::ReqwestError(ref e) =>
                 format!(
                     "\n✘ HTTP Reqwest Error!\n✘ {}\n{}",
@@ -48,6 +57,12 @@ impl Error for AppError {
     }
 }
 
+impl From<receipt_proof_verifier::VerifierError> for AppError {
+    fn from(e: receipt_proof_verifier::VerifierError) -> AppError {
+        AppError::Custom(e.to_string())
+    }
+}
+
 impl From<hex::FromHexError> for AppError {
     fn from(e: hex::FromHexError) -> AppError {
         AppError::HexError(e)
@@ -66,6 +81,7 @@ impl From<std::option::NoneError> for AppError {
     }
 }
 
+#[cfg(feature = "native")]
 impl From<reqwest::Error> for AppError {
     fn from(e: reqwest::Error) -> AppError {
         AppError::ReqwestError(e)
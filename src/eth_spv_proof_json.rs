@@ -0,0 +1,283 @@
+use std::convert::TryFrom;
+use rlp::RlpStream;
+use ethereum_types::{
+    H160,
+    H256,
+};
+use serde::{
+    Serialize,
+    Deserialize,
+};
+use crate::errors::AppError;
+use crate::decode_proof::get_proof_nodes;
+use crate::types::{
+    Bytes,
+    Result,
+    EthSpvProof,
+};
+use crate::utils::{
+    get_bytes_from_field,
+    convert_bytes_to_hex,
+    convert_hex_to_bytes,
+    convert_bytes_to_prefixed_hex,
+};
+
+pub const ETH_SPV_PROOF_JSON_VERSION: u32 = 1;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EthSpvProofJson {
+    pub version: u32,
+    pub log_index: i32,
+    pub log_entry_data: String,
+    pub receipt_index: u64,
+    pub receipt_data: String,
+    pub header_data: String,
+    pub proof: Vec<String>,
+    pub token: String,
+    pub lock_amount: String,
+    pub bridge_fee: String,
+    pub ckb_recipient: String,
+    pub block_hash: String,
+    pub recipient_lockscript: String,
+    pub replay_resist_outpoint: String,
+    pub sudt_extra_data: String,
+}
+
+fn prefix_hex(hex: &str) -> String {
+    format!("0x{}", hex)
+}
+
+fn get_fixed_bytes_from_field(name: &str, value: &str, length: usize) -> Result<Bytes> {
+    get_bytes_from_field(name, value)
+        .and_then(|bytes| match bytes.len() == length {
+            true => Ok(bytes),
+            false => Err(AppError::Custom(
+                format!("✘ Field `{}` must be {} bytes, got {}!", name, length, bytes.len())
+            )),
+        })
+}
+
+fn get_u128_from_field(name: &str, value: &str) -> Result<u128> {
+    value
+        .parse::<u128>()
+        .map_err(|_| AppError::Custom(
+            format!("✘ Field `{}` must be a decimal string, got {}!", name, value)
+        ))
+}
+
+fn get_proof_node_hexes(hex_proof: &str) -> Result<Vec<String>> {
+    match hex_proof.is_empty() {
+        true => Ok(vec![]),
+        false => convert_hex_to_bytes(hex_proof.to_string())
+            .and_then(|proof| get_proof_nodes(&proof))
+            .map(|nodes| nodes.iter().map(|node| convert_bytes_to_prefixed_hex(node)).collect()),
+    }
+}
+
+fn get_hex_proof_from_node_hexes(node_hexes: &[String]) -> Result<String> {
+    match node_hexes.is_empty() {
+        true => Ok(String::new()),
+        false => {
+            let mut stream = RlpStream::new_list(node_hexes.len());
+            node_hexes
+                .iter()
+                .map(|node_hex| get_bytes_from_field("proof", node_hex))
+                .collect::<Result<Vec<Bytes>>>()?
+                .iter()
+                .for_each(|node| { stream.append_raw(node, 1); });
+            Ok(convert_bytes_to_hex(stream.out()))
+        }
+    }
+}
+
+fn get_unprefixed_hex_from_field(name: &str, value: &str) -> Result<String> {
+    get_bytes_from_field(name, value).map(convert_bytes_to_hex)
+}
+
+impl TryFrom<&EthSpvProof> for EthSpvProofJson {
+    type Error = AppError;
+
+    fn try_from(eth_spv_proof: &EthSpvProof) -> Result<Self> {
+        Ok(
+            EthSpvProofJson {
+                version: ETH_SPV_PROOF_JSON_VERSION,
+                log_index: eth_spv_proof.log_index,
+                log_entry_data: prefix_hex(&eth_spv_proof.log_entry_data),
+                receipt_index: eth_spv_proof.receipt_index,
+                receipt_data: prefix_hex(&eth_spv_proof.receipt_data),
+                header_data: prefix_hex(&eth_spv_proof.header_data),
+                proof: get_proof_node_hexes(&eth_spv_proof.proof)?,
+                token: format!("{:?}", eth_spv_proof.token),
+                lock_amount: eth_spv_proof.lock_amount.to_string(),
+                bridge_fee: eth_spv_proof.bridge_fee.to_string(),
+                ckb_recipient: eth_spv_proof.ckb_recipient.clone(),
+                block_hash: format!("{:?}", eth_spv_proof.block_hash),
+                recipient_lockscript: convert_bytes_to_prefixed_hex(&eth_spv_proof.recipient_lockscript),
+                replay_resist_outpoint: convert_bytes_to_prefixed_hex(&eth_spv_proof.replay_resist_outpoint),
+                sudt_extra_data: convert_bytes_to_prefixed_hex(&eth_spv_proof.sudt_extra_data),
+            }
+        )
+    }
+}
+
+impl TryFrom<EthSpvProofJson> for EthSpvProof {
+    type Error = AppError;
+
+    fn try_from(json: EthSpvProofJson) -> Result<Self> {
+        if json.version != ETH_SPV_PROOF_JSON_VERSION {
+            return Err(AppError::Custom(
+                format!(
+                    "✘ Unsupported proof JSON version {}, expected {}!",
+                    json.version,
+                    ETH_SPV_PROOF_JSON_VERSION,
+                )
+            ))
+        }
+        Ok(
+            EthSpvProof {
+                log_index: json.log_index,
+                log_entry_data: get_unprefixed_hex_from_field("log_entry_data", &json.log_entry_data)?,
+                receipt_index: json.receipt_index,
+                receipt_data: get_unprefixed_hex_from_field("receipt_data", &json.receipt_data)?,
+                header_data: get_unprefixed_hex_from_field("header_data", &json.header_data)?,
+                proof: get_hex_proof_from_node_hexes(&json.proof)?,
+                token: H160::from_slice(&get_fixed_bytes_from_field("token", &json.token, 20)?),
+                lock_amount: get_u128_from_field("lock_amount", &json.lock_amount)?,
+                bridge_fee: get_u128_from_field("bridge_fee", &json.bridge_fee)?,
+                ckb_recipient: json.ckb_recipient,
+                block_hash: H256::from_slice(
+                    &get_fixed_bytes_from_field("block_hash", &json.block_hash, 32)?
+                ),
+                recipient_lockscript: get_bytes_from_field(
+                    "recipient_lockscript",
+                    &json.recipient_lockscript,
+                )?,
+                replay_resist_outpoint: get_bytes_from_field(
+                    "replay_resist_outpoint",
+                    &json.replay_resist_outpoint,
+                )?,
+                sudt_extra_data: get_bytes_from_field("sudt_extra_data", &json.sudt_extra_data)?,
+            }
+        )
+    }
+}
+
+impl EthSpvProof {
+    pub fn to_json(&self) -> Result<String> {
+        EthSpvProofJson::try_from(self)
+            .and_then(|json| Ok(serde_json::to_string(&json)?))
+    }
+
+    pub fn from_json(json: &str) -> Result<EthSpvProof> {
+        serde_json::from_str::<EthSpvProofJson>(json)
+            .map_err(AppError::from)
+            .and_then(EthSpvProof::try_from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value as Json;
+    use crate::test_utils::{
+        get_sample_proof_1,
+        get_valid_block_hash_h256,
+    };
+
+    fn get_sample_eth_spv_proof() -> EthSpvProof {
+        EthSpvProof {
+            log_index: 2,
+            log_entry_data: "f85894".to_string(),
+            receipt_index: 14,
+            receipt_data: "f90164".to_string(),
+            header_data: String::new(),
+            proof: get_sample_proof_1(),
+            token: H160::from_slice(&[0xaa; 20]),
+            lock_amount: 340_282_366_920_938_463_463_374_607_431_768_211_455,
+            bridge_fee: 1_000,
+            ckb_recipient: "ckt1qyq".to_string(),
+            block_hash: get_valid_block_hash_h256().unwrap(),
+            recipient_lockscript: vec![0x49, 0x00],
+            replay_resist_outpoint: vec![0x01, 0x02, 0x03],
+            sudt_extra_data: vec![],
+        }
+    }
+
+    #[test]
+    fn should_round_trip_eth_spv_proof_through_json() {
+        let eth_spv_proof = get_sample_eth_spv_proof();
+        let json = eth_spv_proof.to_json().unwrap();
+        let result = EthSpvProof::from_json(&json).unwrap();
+        assert!(result.to_json().unwrap() == json);
+        assert!(result.proof == eth_spv_proof.proof);
+        assert!(result.lock_amount == eth_spv_proof.lock_amount);
+        assert!(result.recipient_lockscript == eth_spv_proof.recipient_lockscript);
+    }
+
+    #[test]
+    fn should_serialize_eth_spv_proof_to_versioned_schema() {
+        let json: Json = serde_json::from_str(&get_sample_eth_spv_proof().to_json().unwrap())
+            .unwrap();
+        assert!(json["version"] == ETH_SPV_PROOF_JSON_VERSION);
+        assert!(json["log_entry_data"] == "0xf85894");
+        assert!(json["header_data"] == "0x");
+        assert!(json["token"] == format!("0x{}", "aa".repeat(20)));
+        assert!(json["lock_amount"] == u128::max_value().to_string());
+        assert!(json["bridge_fee"] == "1000");
+        assert!(json["sudt_extra_data"] == "0x");
+        let expected_nodes = get_proof_nodes(&convert_hex_to_bytes(get_sample_proof_1()).unwrap())
+            .unwrap();
+        assert!(json["proof"].as_array().unwrap().len() == 3);
+        assert!(json["proof"][0] == convert_bytes_to_prefixed_hex(&expected_nodes[0]));
+    }
+
+    #[test]
+    fn should_still_deserialize_eth_spv_proof_from_its_field_format() {
+        let json = json!({
+            "log_index": 2,
+            "log_entry_data": "f85894",
+            "receipt_index": 14,
+            "receipt_data": "f90164",
+            "header_data": "",
+            "proof": get_sample_proof_1(),
+            "token": format!("0x{}", "aa".repeat(20)),
+            "lock_amount": 1_000_000,
+            "bridge_fee": 1_000,
+            "ckb_recipient": "ckt1qyq",
+            "block_hash": format!("{:?}", get_valid_block_hash_h256().unwrap()),
+            "recipient_lockscript": [0x49, 0x00],
+            "replay_resist_outpoint": [0x01, 0x02, 0x03],
+            "sudt_extra_data": [],
+        });
+        let result: EthSpvProof = serde_json::from_value(json)
+            .unwrap();
+        let expected_result = get_sample_eth_spv_proof();
+        assert!(result.proof == expected_result.proof);
+        assert!(result.token == expected_result.token);
+        assert!(result.lock_amount == 1_000_000);
+        assert!(result.recipient_lockscript == expected_result.recipient_lockscript);
+    }
+
+    #[test]
+    fn should_err_on_unsupported_version() {
+        let mut json: Json = serde_json::from_str(&get_sample_eth_spv_proof().to_json().unwrap())
+            .unwrap();
+        json["version"] = json!(2);
+        match EthSpvProof::from_json(&json.to_string()) {
+            Err(AppError::Custom(e)) => assert!(e.contains("Unsupported proof JSON version")),
+            _ => panic!("Unknown schema version should not be accepted!"),
+        }
+    }
+
+    #[test]
+    fn should_err_on_unprefixed_hex_field() {
+        let mut json: Json = serde_json::from_str(&get_sample_eth_spv_proof().to_json().unwrap())
+            .unwrap();
+        json["receipt_data"] = json!("f90164");
+        match EthSpvProof::from_json(&json.to_string()) {
+            Err(AppError::Custom(e)) => assert!(e.contains("0x-prefixed")),
+            _ => panic!("Unprefixed hex field should not be accepted!"),
+        }
+    }
+}
@@ -0,0 +1,580 @@
+use rlp::RlpStream;
+use crate::errors::AppError;
+use tiny_keccak::{
+    keccak256,
+    keccak512,
+};
+use ethereum_types::{
+    H256,
+    U256,
+    U512,
+};
+use crate::types::{
+    Block,
+    Bytes,
+    Result,
+};
+
+pub const EPOCH_LENGTH: u64 = 30000;
+const WORD_BYTES: usize = 4;
+const HASH_BYTES: usize = 64;
+const MIX_BYTES: usize = 128;
+const ACCESSES: u32 = 64;
+const CACHE_ROUNDS: usize = 3;
+const DATASET_PARENTS: u32 = 256;
+const FNV_PRIME: u32 = 0x01000193;
+const CACHE_BYTES_INIT: u64 = 1 << 24;
+const CACHE_BYTES_GROWTH: u64 = 1 << 17;
+const DATASET_BYTES_INIT: u64 = 1 << 30;
+const DATASET_BYTES_GROWTH: u64 = 1 << 23;
+const WORDS_PER_ITEM: usize = HASH_BYTES / WORD_BYTES;
+const WORDS_PER_MIX: usize = MIX_BYTES / WORD_BYTES;
+
+pub type Item = [u32; WORDS_PER_ITEM]; // NOTE: A 64 byte hash as LE words.
+pub type MerkleTree = Vec<Vec<H256>>;
+
+pub struct HashimotoOutput {
+    pub result: H256,
+    pub mix_digest: H256,
+    pub dag_node_indices: Vec<u32>,
+}
+
+// NOTE: This crate's own witness format, not byte-compatible with other ethash provers':
+// `dag_nodes` are the 64 accessed 128 byte DAG nodes in access order, each the LE words of
+// dataset items 2i & 2i + 1. `merkle_proofs` hold each node's sibling hashes from leaf to root,
+// in a keccak256 tree whose leaves are the hashed nodes, whose parents hash left ++ right &
+// whose odd-length levels are padded with a zero hash. `dataset_merkle_root` is its root.
+#[derive(Clone, Debug)]
+pub struct DagWitness {
+    pub dataset_merkle_root: H256,
+    pub dag_nodes: Vec<Bytes>,
+    pub merkle_proofs: Vec<Vec<H256>>,
+}
+
+pub struct EthashCache {
+    pub epoch: u64,
+    pub items: Vec<Item>,
+    pub dataset_size: u64,
+}
+
+impl EthashCache {
+    pub fn new(epoch: u64) -> EthashCache {
+        info!("✔ Generating ethash cache for epoch {}...", epoch);
+        EthashCache {
+            epoch,
+            dataset_size: get_dataset_size(epoch),
+            items: make_cache(get_cache_size(epoch), &get_seed_hash(epoch)),
+        }
+    }
+}
+
+pub struct EthashDataset {
+    pub epoch: u64,
+    pub items: Vec<Item>,
+    pub merkle_tree: MerkleTree,
+}
+
+impl EthashDataset {
+    pub fn new(cache: &EthashCache) -> EthashDataset {
+        info!("✔ Generating ethash dataset for epoch {}...", cache.epoch);
+        let items = make_dataset(&cache.items, cache.dataset_size);
+        let merkle_tree = get_dataset_merkle_tree(&items);
+        EthashDataset { epoch: cache.epoch, items, merkle_tree }
+    }
+
+    pub fn get_merkle_root(&self) -> H256 {
+        get_merkle_root(&self.merkle_tree)
+    }
+}
+
+fn fnv(v1: u32, v2: u32) -> u32 {
+    v1.wrapping_mul(FNV_PRIME) ^ v2
+}
+
+fn convert_bytes_to_item(bytes: &[u8]) -> Item {
+    let mut item = [0u32; WORDS_PER_ITEM];
+    item.iter_mut()
+        .zip(bytes.chunks(WORD_BYTES))
+        .for_each(|(word, chunk)|
+            *word = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]])
+        );
+    item
+}
+
+fn convert_words_to_bytes(words: &[u32]) -> Bytes {
+    words
+        .iter()
+        .flat_map(|word| word.to_le_bytes().to_vec())
+        .collect()
+}
+
+fn keccak512_item(item: &Item) -> Item {
+    convert_bytes_to_item(&keccak512(&convert_words_to_bytes(item)))
+}
+
+fn is_prime(number: u64) -> bool {
+    number > 1 && (2..)
+        .take_while(|divisor| divisor * divisor <= number)
+        .all(|divisor| number % divisor != 0)
+}
+
+pub fn get_epoch(block_number: u64) -> u64 {
+    block_number / EPOCH_LENGTH
+}
+
+pub fn get_cache_size(epoch: u64) -> usize {
+    let mut size = CACHE_BYTES_INIT + CACHE_BYTES_GROWTH * epoch - HASH_BYTES as u64;
+    while !is_prime(size / HASH_BYTES as u64) {
+        size -= 2 * HASH_BYTES as u64;
+    }
+    size as usize
+}
+
+pub fn get_dataset_size(epoch: u64) -> u64 {
+    let mut size = DATASET_BYTES_INIT + DATASET_BYTES_GROWTH * epoch - MIX_BYTES as u64;
+    while !is_prime(size / MIX_BYTES as u64) {
+        size -= 2 * MIX_BYTES as u64;
+    }
+    size
+}
+
+pub fn get_seed_hash(epoch: u64) -> [u8; 32] {
+    (0..epoch).fold([0u8; 32], |seed, _| keccak256(&seed))
+}
+
+pub fn make_cache(cache_size: usize, seed: &[u8; 32]) -> Vec<Item> {
+    let num_items = cache_size / HASH_BYTES;
+    let mut items = Vec::with_capacity(num_items);
+    items.push(convert_bytes_to_item(&keccak512(seed)));
+    for i in 1..num_items {
+        let item = keccak512_item(&items[i - 1]);
+        items.push(item);
+    }
+    for _ in 0..CACHE_ROUNDS {
+        for i in 0..num_items {
+            let v = items[i][0] as usize % num_items;
+            let u = (i + num_items - 1) % num_items;
+            let mut mixed = [0u32; WORDS_PER_ITEM];
+            for j in 0..WORDS_PER_ITEM {
+                mixed[j] = items[u][j] ^ items[v][j];
+            }
+            items[i] = keccak512_item(&mixed);
+        }
+    }
+    items
+}
+
+fn calc_dataset_item_from_lookup<F>(
+    num_items: usize,
+    index: u32,
+    lookup: F,
+) -> Item where F: Fn(usize) -> Item {
+    let mut mix = lookup(index as usize % num_items);
+    mix[0] ^= index;
+    mix = keccak512_item(&mix);
+    for j in 0..DATASET_PARENTS {
+        let parent = fnv(index ^ j, mix[j as usize % WORDS_PER_ITEM]) as usize;
+        let parent_item = lookup(parent % num_items);
+        for k in 0..WORDS_PER_ITEM {
+            mix[k] = fnv(mix[k], parent_item[k]);
+        }
+    }
+    keccak512_item(&mix)
+}
+
+pub fn calc_dataset_item(cache: &[Item], index: u32) -> Item {
+    calc_dataset_item_from_lookup(cache.len(), index, |i| cache[i])
+}
+
+pub fn make_dataset(cache: &[Item], dataset_size: u64) -> Vec<Item> {
+    (0..(dataset_size / HASH_BYTES as u64) as u32)
+        .map(|index| calc_dataset_item(cache, index))
+        .collect()
+}
+
+pub fn hashimoto<F>(
+    header_hash: &H256,
+    nonce: u64,
+    dataset_size: u64,
+    lookup: F,
+) -> HashimotoOutput where F: Fn(u32) -> Item {
+    let num_items = (dataset_size / HASH_BYTES as u64) as u32;
+    let mut seed_preimage = header_hash.as_bytes().to_vec();
+    seed_preimage.extend_from_slice(&nonce.to_le_bytes());
+    let seed = convert_bytes_to_item(&keccak512(&seed_preimage));
+    let mut mix = [0u32; WORDS_PER_MIX];
+    for i in 0..WORDS_PER_MIX {
+        mix[i] = seed[i % WORDS_PER_ITEM];
+    }
+    let mut dag_node_indices = Vec::with_capacity(ACCESSES as usize);
+    for i in 0..ACCESSES {
+        let p = fnv(i ^ seed[0], mix[i as usize % WORDS_PER_MIX]) % (num_items / 2) * 2;
+        dag_node_indices.push(p / 2);
+        let first_item = lookup(p);
+        let second_item = lookup(p + 1);
+        for j in 0..WORDS_PER_ITEM {
+            mix[j] = fnv(mix[j], first_item[j]);
+            mix[j + WORDS_PER_ITEM] = fnv(mix[j + WORDS_PER_ITEM], second_item[j]);
+        }
+    }
+    let compressed_mix: Vec<u32> = mix
+        .chunks(4)
+        .map(|words| fnv(fnv(fnv(words[0], words[1]), words[2]), words[3]))
+        .collect();
+    let mix_digest = convert_words_to_bytes(&compressed_mix);
+    let mut result_preimage = convert_words_to_bytes(&seed);
+    result_preimage.extend_from_slice(&mix_digest);
+    HashimotoOutput {
+        dag_node_indices,
+        mix_digest: H256::from_slice(&mix_digest),
+        result: H256::from(keccak256(&result_preimage)),
+    }
+}
+
+pub fn get_seal_hash(block: &Block) -> H256 {
+    let mut rlp_stream = RlpStream::new();
+    block.rlp_append_header(&mut rlp_stream, false);
+    H256::from(keccak256(&rlp_stream.out()))
+}
+
+fn check_epoch(block: &Block, epoch: u64) -> Result<()> {
+    match get_epoch(block.number.low_u64()) == epoch {
+        true => Ok(()),
+        false => Err(AppError::Custom(
+            format!("✘ Block {} is not in epoch {}!", block.number, epoch)
+        ))
+    }
+}
+
+fn check_result_meets_difficulty(result: &H256, difficulty: &U256) -> Result<()> {
+    // NOTE: Checks result <= 2^256 / difficulty without the division.
+    let product = U512::from(U256::from(result.as_bytes())) * U512::from(*difficulty);
+    match !difficulty.is_zero() && product <= U512::one() << 256 {
+        true => Ok(()),
+        false => Err(AppError::Custom(
+            format!("✘ PoW result does not meet difficulty {}!", difficulty)
+        ))
+    }
+}
+
+fn verify_seal<F>(
+    block: &Block,
+    dataset_size: u64,
+    lookup: F,
+) -> Result<HashimotoOutput> where F: Fn(u32) -> Item {
+    let output = hashimoto(
+        &get_seal_hash(block),
+        block.nonce.low_u64(),
+        dataset_size,
+        lookup,
+    );
+    match output.mix_digest == block.mix_hash {
+        false => Err(AppError::Custom(
+            format!(
+                "✘ Mix digest {:?} does not match mix hash of block {}!",
+                output.mix_digest,
+                block.number,
+            )
+        )),
+        true => check_result_meets_difficulty(&output.result, &block.difficulty)
+            .map(|_| output),
+    }
+}
+
+pub fn verify_block_pow(block: &Block, cache: &EthashCache) -> Result<()> {
+    info!("✔ Verifying PoW of block {}...", block.number);
+    check_epoch(block, cache.epoch)
+        .and_then(|_|
+            verify_seal(block, cache.dataset_size, |index|
+                calc_dataset_item(&cache.items, index)
+            )
+        )
+        .map(|_| info!("✔ PoW of block {} is valid!", block.number))
+}
+
+fn hash_pair(left: &H256, right: &H256) -> H256 {
+    let mut preimage = left.as_bytes().to_vec();
+    preimage.extend_from_slice(right.as_bytes());
+    H256::from(keccak256(&preimage))
+}
+
+fn get_dag_node(items: &[Item], node_index: u32) -> Bytes {
+    let index = node_index as usize * 2;
+    convert_words_to_bytes(&[items[index], items[index + 1]].concat())
+}
+
+pub fn get_dataset_merkle_tree(items: &[Item]) -> MerkleTree {
+    // NOTE: Leaves are keccak256 of each 128 byte DAG node (two dataset items)
+    // & odd-length levels are padded with a zero hash before pairing up.
+    let mut level: Vec<H256> = (0..(items.len() / 2) as u32)
+        .map(|node_index| H256::from(keccak256(&get_dag_node(items, node_index))))
+        .collect();
+    let mut merkle_tree = Vec::new();
+    while level.len() > 1 {
+        if level.len() % 2 == 1 {
+            level.push(H256::zero());
+        }
+        let next_level = level
+            .chunks(2)
+            .map(|pair| hash_pair(&pair[0], &pair[1]))
+            .collect();
+        merkle_tree.push(level);
+        level = next_level;
+    }
+    merkle_tree.push(level);
+    merkle_tree
+}
+
+pub fn get_merkle_root(merkle_tree: &MerkleTree) -> H256 {
+    merkle_tree
+        .last()
+        .and_then(|root_level| root_level.first())
+        .cloned()
+        .unwrap_or_else(H256::zero)
+}
+
+fn get_merkle_proof(merkle_tree: &MerkleTree, leaf_index: u32) -> Vec<H256> {
+    merkle_tree[..merkle_tree.len() - 1]
+        .iter()
+        .enumerate()
+        .map(|(depth, level)| level[(leaf_index as usize >> depth) ^ 1])
+        .collect()
+}
+
+pub fn verify_dag_node_merkle_proof(
+    root: &H256,
+    dag_node: &Bytes,
+    node_index: u32,
+    proof: &[H256],
+) -> bool {
+    let computed_root = proof
+        .iter()
+        .enumerate()
+        .fold(H256::from(keccak256(dag_node)), |hash, (depth, sibling)|
+            match (node_index >> depth) & 1 {
+                0 => hash_pair(&hash, sibling),
+                _ => hash_pair(sibling, &hash),
+            }
+        );
+    &computed_root == root
+}
+
+fn get_dag_witness(
+    block: &Block,
+    items: &[Item],
+    merkle_tree: &MerkleTree,
+) -> Result<DagWitness> {
+    let dataset_size = (items.len() * HASH_BYTES) as u64;
+    verify_seal(block, dataset_size, |index| items[index as usize])
+        .map(|output|
+            DagWitness {
+                dataset_merkle_root: get_merkle_root(merkle_tree),
+                dag_nodes: output.dag_node_indices
+                    .iter()
+                    .map(|node_index| get_dag_node(items, *node_index))
+                    .collect(),
+                merkle_proofs: output.dag_node_indices
+                    .iter()
+                    .map(|node_index| get_merkle_proof(merkle_tree, *node_index))
+                    .collect(),
+            }
+        )
+}
+
+pub fn generate_dag_witness(
+    block: &Block,
+    dataset: &EthashDataset,
+) -> Result<DagWitness> {
+    info!("✔ Generating DAG witness for block {}...", block.number);
+    check_epoch(block, dataset.epoch)
+        .and_then(|_| get_dag_witness(block, &dataset.items, &dataset.merkle_tree))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::collections::HashMap;
+    use crate::utils::convert_hex_to_h256;
+    use crate::test_utils::{
+        get_expected_block,
+        SAMPLE_ETHASH_CACHE_SLICE_PATH,
+        SAMPLE_ETHASH_DATASET_SLICE_PATH,
+    };
+
+    const TEST_CACHE_SIZE: usize = 1024;
+    const TEST_DATASET_SIZE: u64 = 32 * 1024;
+    const SAMPLE_DAG_NODE_INDEX: u32 = 11026501;
+
+    fn read_item_slice(path: &str) -> HashMap<u32, Item> {
+        // NOTE: One `<index> <hex item>` line per item, as precomputed from epoch 274's cache.
+        fs::read_to_string(path)
+            .unwrap()
+            .lines()
+            .map(|line| match line.split(' ').collect::<Vec<&str>>()[..] {
+                [index, item] => (
+                    index.parse().unwrap(),
+                    convert_bytes_to_item(&hex::decode(item).unwrap()),
+                ),
+                _ => panic!("Malformed item slice line: {}", line),
+            })
+            .collect()
+    }
+
+    fn get_test_cache() -> Vec<Item> {
+        make_cache(TEST_CACHE_SIZE, &[0u8; 32])
+    }
+
+    fn get_block_sealed_with_test_dataset(
+        items: &[Item],
+        difficulty: U256,
+    ) -> Block {
+        let mut block = get_expected_block();
+        block.difficulty = difficulty;
+        let output = hashimoto(
+            &get_seal_hash(&block),
+            block.nonce.low_u64(),
+            TEST_DATASET_SIZE,
+            |index| items[index as usize],
+        );
+        block.mix_hash = output.mix_digest;
+        block
+    }
+
+    #[test]
+    fn should_get_cache_and_dataset_sizes() {
+        assert!(get_cache_size(0) == 16776896);
+        assert!(get_cache_size(1) == 16907456);
+        assert!(get_dataset_size(0) == 1073739904);
+        assert!(get_dataset_size(1) == 1082130304);
+    }
+
+    #[test]
+    fn should_get_seed_hash() {
+        let expected_result = convert_hex_to_h256(
+            "0x290decd9548b62a8d60345a988386fc84ba6bc95484008f6362f93160ef3e563"
+                .to_string()
+        ).unwrap();
+        assert!(get_seed_hash(0) == [0u8; 32]);
+        assert!(H256::from(get_seed_hash(1)) == expected_result);
+    }
+
+    #[test]
+    fn should_get_epoch() {
+        assert!(get_epoch(29999) == 0);
+        assert!(get_epoch(30000) == 1);
+        assert!(get_epoch(0x7da175) == 274);
+    }
+
+    #[test]
+    fn light_and_full_hashimoto_should_agree() {
+        let cache = get_test_cache();
+        let items = make_dataset(&cache, TEST_DATASET_SIZE);
+        let header_hash = get_seal_hash(&get_expected_block());
+        let light = hashimoto(&header_hash, 1337, TEST_DATASET_SIZE, |index|
+            calc_dataset_item(&cache, index)
+        );
+        let full = hashimoto(&header_hash, 1337, TEST_DATASET_SIZE, |index|
+            items[index as usize]
+        );
+        assert!(light.result == full.result);
+        assert!(light.mix_digest == full.mix_digest);
+        assert!(light.dag_node_indices.len() == ACCESSES as usize);
+    }
+
+    #[test]
+    fn should_fail_to_verify_seal_with_wrong_mix_hash() {
+        let items = make_dataset(&get_test_cache(), TEST_DATASET_SIZE);
+        let mut block = get_block_sealed_with_test_dataset(&items, U256::one());
+        block.mix_hash = H256::zero();
+        match verify_seal(&block, TEST_DATASET_SIZE, |index| items[index as usize]) {
+            Err(AppError::Custom(e)) => assert!(e.contains("Mix digest")),
+            _ => panic!("Seal with wrong mix hash should not verify!"),
+        }
+    }
+
+    #[test]
+    fn should_fail_to_verify_seal_not_meeting_difficulty() {
+        let items = make_dataset(&get_test_cache(), TEST_DATASET_SIZE);
+        let block = get_block_sealed_with_test_dataset(&items, U256::MAX);
+        match verify_seal(&block, TEST_DATASET_SIZE, |index| items[index as usize]) {
+            Err(AppError::Custom(e)) => assert!(e.contains("difficulty")),
+            _ => panic!("Seal not meeting difficulty should not verify!"),
+        }
+    }
+
+    #[test]
+    fn should_generate_dag_witness_that_verifies_against_root() {
+        let items = make_dataset(&get_test_cache(), TEST_DATASET_SIZE);
+        let merkle_tree = get_dataset_merkle_tree(&items);
+        let block = get_block_sealed_with_test_dataset(&items, U256::one());
+        let output = hashimoto(
+            &get_seal_hash(&block),
+            block.nonce.low_u64(),
+            TEST_DATASET_SIZE,
+            |index| items[index as usize],
+        );
+        let witness = get_dag_witness(&block, &items, &merkle_tree).unwrap();
+        assert!(witness.dag_nodes.len() == ACCESSES as usize);
+        witness.dag_nodes
+            .iter()
+            .zip(witness.merkle_proofs.iter())
+            .zip(output.dag_node_indices.iter())
+            .for_each(|((dag_node, proof), node_index)| {
+                assert!(dag_node.len() == MIX_BYTES);
+                assert!(verify_dag_node_merkle_proof(
+                    &witness.dataset_merkle_root,
+                    dag_node,
+                    *node_index,
+                    proof,
+                ));
+            });
+    }
+
+    #[test]
+    fn should_not_verify_tampered_dag_node() {
+        let items = make_dataset(&get_test_cache(), TEST_DATASET_SIZE);
+        let merkle_tree = get_dataset_merkle_tree(&items);
+        let mut dag_node = get_dag_node(&items, 7);
+        dag_node[0] ^= 1;
+        assert!(!verify_dag_node_merkle_proof(
+            &get_merkle_root(&merkle_tree),
+            &dag_node,
+            7,
+            &get_merkle_proof(&merkle_tree, 7),
+        ));
+    }
+
+    #[test]
+    fn should_verify_pow_of_mainnet_block() {
+        let block = get_expected_block();
+        let dataset_slice = read_item_slice(SAMPLE_ETHASH_DATASET_SLICE_PATH);
+        let dataset_size = get_dataset_size(get_epoch(block.number.low_u64()));
+        let output = verify_seal(&block, dataset_size, |index| dataset_slice[&index])
+            .unwrap();
+        assert!(output.dag_node_indices[0] == SAMPLE_DAG_NODE_INDEX);
+    }
+
+    #[test]
+    fn should_calc_mainnet_dataset_items_from_cache() {
+        let cache_slice = read_item_slice(SAMPLE_ETHASH_CACHE_SLICE_PATH);
+        let dataset_slice = read_item_slice(SAMPLE_ETHASH_DATASET_SLICE_PATH);
+        let num_items = get_cache_size(get_epoch(get_expected_block().number.low_u64())) / HASH_BYTES;
+        [SAMPLE_DAG_NODE_INDEX * 2, SAMPLE_DAG_NODE_INDEX * 2 + 1]
+            .iter()
+            .for_each(|index| {
+                let result = calc_dataset_item_from_lookup(num_items, *index, |i|
+                    cache_slice[&(i as u32)]
+                );
+                assert!(result == dataset_slice[index]);
+            });
+    }
+
+    #[test]
+    #[ignore] // NOTE: Builds the ~50MB cache for epoch 274, slow in debug mode.
+    fn should_verify_pow_of_mainnet_block_with_full_cache() {
+        let block = get_expected_block();
+        let cache = EthashCache::new(get_epoch(block.number.low_u64()));
+        verify_block_pow(&block, &cache).unwrap();
+    }
+}
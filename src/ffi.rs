@@ -0,0 +1,327 @@
+use std::slice;
+use std::panic::{
+    self,
+    UnwindSafe,
+};
+use ethereum_types::H256;
+use crate::decode_proof::get_proof_nodes;
+use crate::verify_proof::verify_receipt_proof;
+use crate::get_branch_from_trie::get_branch_from_trie;
+use crate::get_hex_proof_from_branch::rlp_encode_node_stack;
+use crate::get_receipts_trie::get_receipts_trie_from_encoded_receipts;
+use crate::types::{
+    Bytes,
+    Result,
+};
+
+pub const RECEIPT_PROOF_OK: i32 = 0;
+pub const RECEIPT_PROOF_NULL_POINTER: i32 = 1;
+pub const RECEIPT_PROOF_INVALID_PROOF: i32 = 2;
+pub const RECEIPT_PROOF_INVALID_INPUT: i32 = 3;
+pub const RECEIPT_PROOF_BUFFER_TOO_SMALL: i32 = 4;
+pub const RECEIPT_PROOF_PANIC: i32 = 5;
+
+const HASH_LENGTH: usize = 32;
+
+unsafe fn get_slice<'a>(pointer: *const u8, length: usize) -> Option<&'a [u8]> {
+    match (pointer.is_null(), length) {
+        (_, 0) => Some(&[]),
+        (true, _) => None,
+        (false, _) => Some(slice::from_raw_parts(pointer, length)),
+    }
+}
+
+unsafe fn write_to_buffer(
+    bytes: &[u8],
+    buffer: *mut u8,
+    buffer_capacity: usize,
+    length_out: *mut usize,
+) -> i32 {
+    *length_out = bytes.len();
+    match bytes.len() > buffer_capacity {
+        true => RECEIPT_PROOF_BUFFER_TOO_SMALL,
+        false => {
+            if !bytes.is_empty() {
+                buffer.copy_from(bytes.as_ptr(), bytes.len());
+            }
+            RECEIPT_PROOF_OK
+        }
+    }
+}
+
+fn catch_panic<F: FnOnce() -> i32 + UnwindSafe>(f: F) -> i32 {
+    panic::catch_unwind(f).unwrap_or(RECEIPT_PROOF_PANIC)
+}
+
+fn get_verified_receipt(proof: &[u8], receipts_root: &[u8], tx_index: usize) -> Result<Bytes> {
+    get_proof_nodes(&proof.to_vec())
+        .and_then(|proof_nodes|
+            verify_receipt_proof(&proof_nodes, &H256::from_slice(receipts_root), tx_index)
+        )
+}
+
+fn get_proof_and_receipts_root(
+    encoded_receipts: &[Bytes],
+    tx_index: usize,
+) -> Result<(Bytes, H256)> {
+    let trie = get_receipts_trie_from_encoded_receipts(encoded_receipts)?;
+    let receipts_root = trie.root;
+    get_branch_from_trie(trie, tx_index)
+        .and_then(|branch| rlp_encode_node_stack(&branch))
+        .map(|proof| (proof, receipts_root))
+}
+
+/// Verifies an RLP-encoded receipt proof against a 32 byte receipts root & on
+/// success copies the proven receipt into `receipt_out`. `receipt_len_out` is
+/// always set to the receipt's length when the proof is valid, so a caller
+/// getting `RECEIPT_PROOF_BUFFER_TOO_SMALL` can retry with a big enough buffer.
+///
+/// # Safety
+///
+/// `proof` must point to `proof_len` readable bytes, `receipts_root` to 32
+/// readable bytes, `receipt_out` to `receipt_out_capacity` writable bytes &
+/// `receipt_len_out` to a writable `size_t`.
+#[no_mangle]
+pub unsafe extern "C" fn receipt_proof_verify(
+    proof: *const u8,
+    proof_len: usize,
+    receipts_root: *const u8,
+    tx_index: usize,
+    receipt_out: *mut u8,
+    receipt_out_capacity: usize,
+    receipt_len_out: *mut usize,
+) -> i32 {
+    catch_panic(|| {
+        let (proof, receipts_root) = match (
+            get_slice(proof, proof_len),
+            get_slice(receipts_root, HASH_LENGTH),
+        ) {
+            (Some(proof), Some(receipts_root)) => (proof, receipts_root),
+            _ => return RECEIPT_PROOF_NULL_POINTER,
+        };
+        if receipt_len_out.is_null() || (receipt_out.is_null() && receipt_out_capacity > 0) {
+            return RECEIPT_PROOF_NULL_POINTER;
+        }
+        match get_verified_receipt(proof, receipts_root, tx_index) {
+            Ok(receipt) =>
+                write_to_buffer(&receipt, receipt_out, receipt_out_capacity, receipt_len_out),
+            Err(e) => {
+                info!("✘ Proof failed verification: {}", e);
+                RECEIPT_PROOF_INVALID_PROOF
+            }
+        }
+    })
+}
+
+/// Builds the receipts trie of a block from its RLP-encoded receipts, given in
+/// tx order, writing the receipts root into `receipts_root_out` & the RLP-encoded
+/// proof of the receipt at `tx_index` into `proof_out`. `proof_len_out` is
+/// always set to the proof's length when one could be built, so a caller getting
+/// `RECEIPT_PROOF_BUFFER_TOO_SMALL` can retry with a big enough buffer.
+///
+/// # Safety
+///
+/// `receipts` & `receipt_lens` must point to `num_receipts` receipt pointers &
+/// lengths, each receipt pointer to its length's worth of readable bytes,
+/// `proof_out` to `proof_out_capacity` writable bytes, `proof_len_out` to a
+/// writable `size_t` & `receipts_root_out` to 32 writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn receipt_proof_generate(
+    receipts: *const *const u8,
+    receipt_lens: *const usize,
+    num_receipts: usize,
+    tx_index: usize,
+    proof_out: *mut u8,
+    proof_out_capacity: usize,
+    proof_len_out: *mut usize,
+    receipts_root_out: *mut u8,
+) -> i32 {
+    catch_panic(|| {
+        if receipts.is_null() || receipt_lens.is_null() || proof_len_out.is_null() ||
+            receipts_root_out.is_null() || (proof_out.is_null() && proof_out_capacity > 0)
+        {
+            return RECEIPT_PROOF_NULL_POINTER;
+        }
+        let receipt_pointers = slice::from_raw_parts(receipts, num_receipts);
+        let receipt_lens = slice::from_raw_parts(receipt_lens, num_receipts);
+        let encoded_receipts = match receipt_pointers
+            .iter()
+            .zip(receipt_lens.iter())
+            .map(|(pointer, length)| get_slice(*pointer, *length).map(|receipt| receipt.to_vec()))
+            .collect::<Option<Vec<Bytes>>>()
+        {
+            Some(encoded_receipts) => encoded_receipts,
+            None => return RECEIPT_PROOF_NULL_POINTER,
+        };
+        match get_proof_and_receipts_root(&encoded_receipts, tx_index) {
+            Ok((proof, receipts_root)) => {
+                receipts_root_out.copy_from(receipts_root.as_bytes().as_ptr(), HASH_LENGTH);
+                write_to_buffer(&proof, proof_out, proof_out_capacity, proof_len_out)
+            },
+            Err(e) => {
+                info!("✘ Cannot generate proof: {}", e);
+                RECEIPT_PROOF_INVALID_INPUT
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ptr;
+    use std::fs::read_to_string;
+    use crate::utils::convert_hex_to_bytes;
+    use crate::test_utils::{
+        PROOF_1_INDEX,
+        RECEIPTS_ROOT_1,
+        get_sample_proof_1,
+    };
+
+    const HEADER_PATH: &str = "./include/receipt_proof_maker.h";
+
+    fn get_sample_proof_1_bytes() -> Bytes {
+        convert_hex_to_bytes(get_sample_proof_1()).unwrap()
+    }
+
+    fn get_receipts_root_1_bytes() -> Bytes {
+        convert_hex_to_bytes(RECEIPTS_ROOT_1.to_string()).unwrap()
+    }
+
+    fn verify(proof: &[u8], receipts_root: &[u8], tx_index: usize, capacity: usize) -> (i32, Bytes) {
+        let mut receipt = vec![0u8; capacity];
+        let mut receipt_len = 0;
+        let status = unsafe {
+            receipt_proof_verify(
+                proof.as_ptr(),
+                proof.len(),
+                receipts_root.as_ptr(),
+                tx_index,
+                receipt.as_mut_ptr(),
+                receipt.len(),
+                &mut receipt_len,
+            )
+        };
+        receipt.truncate(receipt_len);
+        (status, receipt)
+    }
+
+    fn generate(receipts: &[Bytes], tx_index: usize) -> (i32, Bytes, Bytes) {
+        let receipt_pointers = receipts.iter().map(|receipt| receipt.as_ptr()).collect::<Vec<_>>();
+        let receipt_lens = receipts.iter().map(|receipt| receipt.len()).collect::<Vec<_>>();
+        let mut proof = vec![0u8; 10_000];
+        let mut proof_len = 0;
+        let mut receipts_root = vec![0u8; HASH_LENGTH];
+        let status = unsafe {
+            receipt_proof_generate(
+                receipt_pointers.as_ptr(),
+                receipt_lens.as_ptr(),
+                receipts.len(),
+                tx_index,
+                proof.as_mut_ptr(),
+                proof.len(),
+                &mut proof_len,
+                receipts_root.as_mut_ptr(),
+            )
+        };
+        proof.truncate(proof_len);
+        (status, proof, receipts_root)
+    }
+
+    #[test]
+    fn should_verify_proof_via_ffi() {
+        let (status, receipt) = verify(
+            &get_sample_proof_1_bytes(),
+            &get_receipts_root_1_bytes(),
+            PROOF_1_INDEX,
+            10_000,
+        );
+        assert!(status == RECEIPT_PROOF_OK);
+        assert!(!receipt.is_empty());
+    }
+
+    #[test]
+    fn should_return_receipt_length_if_buffer_too_small() {
+        let (_, receipt) = verify(
+            &get_sample_proof_1_bytes(),
+            &get_receipts_root_1_bytes(),
+            PROOF_1_INDEX,
+            10_000,
+        );
+        let mut receipt_len = 0;
+        let proof = get_sample_proof_1_bytes();
+        let status = unsafe {
+            receipt_proof_verify(
+                proof.as_ptr(),
+                proof.len(),
+                get_receipts_root_1_bytes().as_ptr(),
+                PROOF_1_INDEX,
+                ptr::null_mut(),
+                0,
+                &mut receipt_len,
+            )
+        };
+        assert!(status == RECEIPT_PROOF_BUFFER_TOO_SMALL);
+        assert!(receipt_len == receipt.len());
+    }
+
+    #[test]
+    fn should_fail_to_verify_proof_against_wrong_index() {
+        let (status, _) = verify(
+            &get_sample_proof_1_bytes(),
+            &get_receipts_root_1_bytes(),
+            PROOF_1_INDEX + 1,
+            10_000,
+        );
+        assert!(status == RECEIPT_PROOF_INVALID_PROOF);
+    }
+
+    #[test]
+    fn should_err_on_null_pointers() {
+        let mut receipt_len = 0;
+        let status = unsafe {
+            receipt_proof_verify(ptr::null(), 1, ptr::null(), 0, ptr::null_mut(), 0, &mut receipt_len)
+        };
+        assert!(status == RECEIPT_PROOF_NULL_POINTER);
+    }
+
+    #[test]
+    fn should_generate_proof_via_ffi_that_verifies() {
+        let (_, receipt) = verify(
+            &get_sample_proof_1_bytes(),
+            &get_receipts_root_1_bytes(),
+            PROOF_1_INDEX,
+            10_000,
+        );
+        let receipts = vec![receipt.clone(); 20];
+        let (status, proof, receipts_root) = generate(&receipts, 17);
+        assert!(status == RECEIPT_PROOF_OK);
+        let (status, result) = verify(&proof, &receipts_root, 17, 10_000);
+        assert!(status == RECEIPT_PROOF_OK);
+        assert!(result == receipt);
+    }
+
+    #[test]
+    fn should_err_when_generating_proof_for_missing_index() {
+        let (status, _, _) = generate(&[vec![0xc0]], 1);
+        assert!(status == RECEIPT_PROOF_INVALID_INPUT);
+    }
+
+    #[test]
+    fn should_declare_every_export_in_header() {
+        let header = read_to_string(HEADER_PATH)
+            .unwrap();
+        [
+            "int32_t receipt_proof_verify(",
+            "int32_t receipt_proof_generate(",
+            "#define RECEIPT_PROOF_OK 0",
+            "#define RECEIPT_PROOF_NULL_POINTER 1",
+            "#define RECEIPT_PROOF_INVALID_PROOF 2",
+            "#define RECEIPT_PROOF_INVALID_INPUT 3",
+            "#define RECEIPT_PROOF_BUFFER_TOO_SMALL 4",
+            "#define RECEIPT_PROOF_PANIC 5",
+        ]
+            .iter()
+            .for_each(|declaration| assert!(header.contains(declaration)));
+    }
+}
@@ -0,0 +1,232 @@
+use std::collections::{
+    HashMap,
+    BTreeMap,
+};
+use ethereum_types::H256;
+use crate::errors::AppError;
+use crate::rpc_cache::RpcCache;
+use crate::validate_tx_hash::validate_tx_hash;
+use crate::get_receipts::get_receipt_from_tx_hash;
+use crate::block_proof_context::BlockProofContext;
+use crate::types::{
+    Result,
+    ReceiptProof,
+    FinalityRequirement,
+};
+
+pub fn copy_block_err(err: &AppError) -> AppError {
+    match err {
+        AppError::NotYetFinal(msg) => AppError::NotYetFinal(msg.clone()),
+        AppError::NotCanonical(msg) => AppError::NotCanonical(msg.clone()),
+        _ => AppError::Custom(err.to_string()),
+    }
+}
+
+pub fn get_block_hash_of_tx(
+    endpoint: &str,
+    tx_hash: &str,
+    maybe_cache: Option<&RpcCache>,
+) -> Result<(H256, H256)> {
+    validate_tx_hash(tx_hash.to_string())
+        .and_then(|_| get_receipt_from_tx_hash(endpoint, tx_hash, maybe_cache))
+        .map(|receipt| (receipt.transaction_hash, receipt.block_hash))
+}
+
+fn group_tx_indices_by_block(located_txs: &[Result<(H256, H256)>]) -> BTreeMap<H256, Vec<(usize, H256)>> {
+    located_txs
+        .iter()
+        .enumerate()
+        .filter_map(|(i, located_tx)| located_tx.as_ref().ok().map(|(tx_hash, block_hash)| (i, *tx_hash, *block_hash)))
+        .fold(BTreeMap::new(), |mut blocks: BTreeMap<H256, Vec<(usize, H256)>>, (i, tx_hash, block_hash)| {
+            blocks.entry(block_hash).or_default().push((i, tx_hash));
+            blocks
+        })
+}
+
+pub fn generate_receipt_proofs(
+    endpoint: &str,
+    tx_hashes: &[String],
+    finality_requirement: &FinalityRequirement,
+    maybe_cache: Option<&RpcCache>,
+) -> Vec<(String, Result<ReceiptProof>)> {
+    info!("✔ Generating proofs for {} transactions...", tx_hashes.len());
+    let located_txs: Vec<Result<(H256, H256)>> = tx_hashes
+        .iter()
+        .map(|tx_hash| get_block_hash_of_tx(endpoint, tx_hash, maybe_cache))
+        .collect();
+    let mut proofs: HashMap<usize, Result<ReceiptProof>> = HashMap::new();
+    // NOTE: Only one block's receipts & trie are held at a time, each dropped once its txs are proven.
+    group_tx_indices_by_block(&located_txs)
+        .into_iter()
+        .for_each(|(block_hash, txs)| {
+            let maybe_block_proof_context = BlockProofContext::from_block_hash(
+                endpoint,
+                block_hash,
+                finality_requirement,
+                maybe_cache,
+            );
+            txs
+                .iter()
+                .for_each(|(i, tx_hash)| {
+                    let result = match &maybe_block_proof_context {
+                        Ok(block_proof_context) => block_proof_context.get_receipt_proof(tx_hash),
+                        Err(err) => Err(copy_block_err(err)),
+                    };
+                    proofs.insert(*i, result);
+                });
+        });
+    tx_hashes
+        .iter()
+        .zip(located_txs)
+        .enumerate()
+        .map(|(i, (tx_hash, located_tx))| {
+            let result = located_tx.and_then(|_|
+                proofs
+                    .remove(&i)
+                    .unwrap_or_else(|| Err(AppError::Custom(format!("✘ No proof generated for tx {}!", tx_hash))))
+            );
+            (tx_hash.clone(), result)
+        })
+        .collect()
+}
+
+pub fn get_tx_hashes_from_string(string: &str) -> Vec<String> {
+    string
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect()
+}
+
+pub fn get_batch_output_line(tx_hash: &str, result: Result<ReceiptProof>) -> Result<String> {
+    match result {
+        Ok(receipt_proof) => Ok(serde_json::to_string(&receipt_proof)?),
+        Err(err) => Ok(json!({ "tx_hash": tx_hash, "error": err.to_string() }).to_string()),
+    }
+}
+
+pub fn get_batch_output(results: Vec<(String, Result<ReceiptProof>)>) -> Result<String> {
+    results
+        .into_iter()
+        .map(|(tx_hash, result)| get_batch_output_line(&tx_hash, result))
+        .collect::<Result<Vec<String>>>()
+        .map(|lines| lines.iter().map(|line| format!("{}\n", line)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value as Json;
+    use crate::test_utils::{
+        PROOF_1_INDEX,
+        SAMPLE_TX_HASH,
+        get_sample_mock_node,
+        get_sample_tx_hashes_1,
+    };
+
+    fn get_results(tx_hashes: &[String]) -> Vec<(String, Result<ReceiptProof>)> {
+        let mock_node = get_sample_mock_node();
+        generate_receipt_proofs(
+            &mock_node.get_endpoint(),
+            tx_hashes,
+            &FinalityRequirement::Confirmations(0),
+            None,
+        )
+    }
+
+    #[test]
+    fn should_generate_receipt_proofs_for_tx_hashes_in_same_block() {
+        let sample_tx_hashes = get_sample_tx_hashes_1();
+        let tx_hashes = vec![
+            sample_tx_hashes[PROOF_1_INDEX].clone(),
+            sample_tx_hashes[0].clone(),
+        ];
+        let results = get_results(&tx_hashes);
+        assert!(results.len() == 2);
+        let proof_1 = results[0].1.as_ref().unwrap();
+        let proof_2 = results[1].1.as_ref().unwrap();
+        assert!(results[0].0 == tx_hashes[0]);
+        assert!(proof_1.tx_index == PROOF_1_INDEX);
+        assert!(proof_2.tx_index == 0);
+        assert!(proof_1.block_hash == proof_2.block_hash);
+        assert!(proof_1.proof != proof_2.proof);
+    }
+
+    #[test]
+    fn should_keep_per_item_errors_in_order() {
+        let tx_hashes = vec![
+            "0xc0ffee".to_string(),
+            get_sample_tx_hashes_1()[PROOF_1_INDEX].clone(),
+            format!("0x{}", "00".repeat(32)),
+        ];
+        let results = get_results(&tx_hashes);
+        assert!(results[0].1.is_err());
+        assert!(results[1].1.is_ok());
+        match &results[2].1 {
+            Err(AppError::Custom(e)) => assert!(e.contains("No receipt found")),
+            _ => panic!("Missing receipt should not yield a proof!"),
+        }
+    }
+
+    #[test]
+    fn should_copy_block_err_to_each_tx_in_block() {
+        let mock_node = get_sample_mock_node();
+        let sample_tx_hashes = get_sample_tx_hashes_1();
+        let results = generate_receipt_proofs(
+            &mock_node.get_endpoint(),
+            &sample_tx_hashes[..2],
+            &FinalityRequirement::Confirmations(1_000_000_000),
+            None,
+        );
+        results
+            .iter()
+            .for_each(|(_, result)| match result {
+                Err(AppError::NotYetFinal(_)) => assert!(true),
+                _ => panic!("Tx in non-final block should not yield a proof!"),
+            });
+    }
+
+    #[test]
+    fn should_group_tx_indices_by_block() {
+        let block_hash_1 = H256::from_low_u64_be(1);
+        let block_hash_2 = H256::from_low_u64_be(2);
+        let tx_hashes: Vec<H256> = (0..4).map(|i| H256::from_low_u64_be(i + 10)).collect();
+        let located_txs = vec![
+            Ok((tx_hashes[0], block_hash_1)),
+            Ok((tx_hashes[1], block_hash_2)),
+            Err(AppError::Custom("✘ No receipt found!".to_string())),
+            Ok((tx_hashes[3], block_hash_1)),
+        ];
+        let result = group_tx_indices_by_block(&located_txs);
+        assert!(result.len() == 2);
+        assert!(result[&block_hash_1] == vec![(0, tx_hashes[0]), (3, tx_hashes[3])]);
+        assert!(result[&block_hash_2] == vec![(1, tx_hashes[1])]);
+    }
+
+    #[test]
+    fn should_get_tx_hashes_from_string() {
+        let string = format!("# Lock txs\n{}\n\n  {}  \n", SAMPLE_TX_HASH, "0xc0ffee");
+        let result = get_tx_hashes_from_string(&string);
+        assert!(result == vec![SAMPLE_TX_HASH.to_string(), "0xc0ffee".to_string()]);
+    }
+
+    #[test]
+    fn should_get_batch_output_with_one_json_line_per_item() {
+        let tx_hashes = vec![
+            get_sample_tx_hashes_1()[PROOF_1_INDEX].clone(),
+            "0xc0ffee".to_string(),
+        ];
+        let output = get_batch_output(get_results(&tx_hashes))
+            .unwrap();
+        let lines: Vec<Json> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert!(lines.len() == 2);
+        assert!(lines[0]["tx_index"] == PROOF_1_INDEX);
+        assert!(lines[0]["tx_hash"] == tx_hashes[0]);
+        assert!(lines[1]["tx_hash"] == "0xc0ffee");
+        assert!(lines[1]["error"].is_string());
+    }
+}
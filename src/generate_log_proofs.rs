@@ -0,0 +1,328 @@
+use std::collections::BTreeMap;
+use ethereum_types::{
+    H256,
+    Address,
+};
+use serde_json::Value as Json;
+use crate::errors::AppError;
+use crate::rpc_cache::RpcCache;
+use crate::get_rpc_call_jsons::get_logs_json;
+use crate::make_rpc_call::{
+    make_rpc_call,
+    get_response_text,
+    deserialize_to_logs_rpc_response,
+};
+use crate::block_proof_context::BlockProofContext;
+use crate::generate_batch_proofs::copy_block_err;
+use crate::types::{
+    Result,
+    LogJson,
+    ReceiptProof,
+    FinalityRequirement,
+};
+use crate::utils::{
+    convert_hex_to_h256,
+    convert_hex_to_u256,
+};
+
+const LIMIT_EXCEEDED_ERR_CODE: i64 = -32005;
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ReceiptLogsProof {
+    #[serde(flatten)]
+    pub receipt_proof: ReceiptProof,
+    pub log_indices: Vec<usize>,
+}
+
+type TxLogs = BTreeMap<usize, (H256, Vec<String>)>;
+
+fn is_more_than_n_results_msg(msg: &str) -> bool {
+    let msg = msg.to_lowercase();
+    msg.contains("query returned more than") || msg
+        .match_indices("more than ")
+        .any(|(i, prefix)| {
+            let rest = &msg[i + prefix.len()..];
+            let num_digits = rest.chars().take_while(char::is_ascii_digit).count();
+            num_digits > 0 && rest[num_digits..].starts_with(" results")
+        })
+}
+
+fn is_result_cap_err(err: &AppError) -> bool {
+    // NOTE: Only the `limit exceeded` code or a "more than N results" message mark a capped
+    // query, since splitting on any other error would just repeat it across ever smaller ranges.
+    let msg = err.to_string();
+    match msg.find('{').and_then(|i| serde_json::from_str::<Json>(&msg[i..]).ok()) {
+        Some(response) =>
+            response["error"]["code"] == LIMIT_EXCEEDED_ERR_CODE ||
+            response["error"]["message"].as_str().map(is_more_than_n_results_msg).unwrap_or(false),
+        None => msg.contains("RPC call failed") && is_more_than_n_results_msg(&msg),
+    }
+}
+
+fn get_logs(
+    endpoint: &str,
+    address: &Address,
+    topics: &[H256],
+    from_block: u64,
+    to_block: u64,
+) -> Result<Vec<LogJson>> {
+    get_logs_json(
+        &format!("{:?}", address),
+        &topics.iter().map(|topic| format!("{:?}", topic)).collect::<Vec<String>>(),
+        &format!("0x{:x}", from_block),
+        &format!("0x{:x}", to_block),
+    )
+        .and_then(|json| make_rpc_call(endpoint, json))
+        .and_then(get_response_text)
+        .and_then(deserialize_to_logs_rpc_response)
+        .map(|res| res.result)
+}
+
+pub fn get_logs_in_range(
+    endpoint: &str,
+    address: &Address,
+    topics: &[H256],
+    from_block: u64,
+    to_block: u64,
+) -> Result<Vec<LogJson>> {
+    match get_logs(endpoint, address, topics, from_block, to_block) {
+        Err(ref err) if from_block < to_block && is_result_cap_err(err) => {
+            let middle_block = from_block + (to_block - from_block) / 2;
+            info!(
+                "✔ Node capped logs in blocks {}-{}, splitting at block {}...",
+                from_block,
+                to_block,
+                middle_block,
+            );
+            let mut logs = get_logs_in_range(endpoint, address, topics, from_block, middle_block)?;
+            logs.append(&mut get_logs_in_range(endpoint, address, topics, middle_block + 1, to_block)?);
+            Ok(logs)
+        },
+        result => result,
+    }
+}
+
+fn group_logs_by_block_and_tx(logs: &[LogJson]) -> Result<BTreeMap<(u64, H256), TxLogs>> {
+    logs
+        .iter()
+        .try_fold(BTreeMap::new(), |mut blocks: BTreeMap<(u64, H256), TxLogs>, log| {
+            let block_key = (
+                convert_hex_to_u256(log.blockNumber.clone())?.as_u64(),
+                convert_hex_to_h256(log.blockHash.clone())?,
+            );
+            let tx_index = convert_hex_to_u256(log.transactionIndex.clone())?.as_usize();
+            let tx_hash = convert_hex_to_h256(log.transactionHash.clone())?;
+            blocks
+                .entry(block_key)
+                .or_insert_with(BTreeMap::new)
+                .entry(tx_index)
+                .or_insert_with(|| (tx_hash, vec![]))
+                .1
+                .push(log.logIndex.clone());
+            Ok(blocks)
+        })
+}
+
+fn get_log_indices_in_receipt(
+    block_log_indices: &[String],
+    receipt_log_indices: &[String],
+    tx_hash: &H256,
+) -> Result<Vec<usize>> {
+    block_log_indices
+        .iter()
+        .map(|block_log_index|
+            match receipt_log_indices.iter().position(|index| index == block_log_index) {
+                Some(log_index) => Ok(log_index),
+                None => Err(AppError::Custom(
+                    format!("✘ Log {} not found in receipt of tx {:?}!", block_log_index, tx_hash)
+                ))
+            }
+        )
+        .collect()
+}
+
+fn get_receipt_logs_proof(
+    block_proof_context: &BlockProofContext,
+    tx_hash: &H256,
+    block_log_indices: &[String],
+) -> Result<ReceiptLogsProof> {
+    block_proof_context
+        .get_receipt_proof(tx_hash)
+        .and_then(|receipt_proof| {
+            let receipt_log_indices: Vec<String> = block_proof_context
+                .get_receipt(receipt_proof.tx_index)?
+                .logs
+                .iter()
+                .map(|log| log.log_index.clone())
+                .collect();
+            get_log_indices_in_receipt(block_log_indices, &receipt_log_indices, tx_hash)
+                .map(|log_indices| ReceiptLogsProof { receipt_proof, log_indices })
+        })
+}
+
+pub fn generate_log_proofs(
+    endpoint: &str,
+    address: &Address,
+    topics: &[H256],
+    from_block: u64,
+    to_block: u64,
+    finality_requirement: &FinalityRequirement,
+    maybe_cache: Option<&RpcCache>,
+) -> Result<Vec<(H256, Result<ReceiptLogsProof>)>> {
+    info!("✔ Getting logs of {:?} in blocks {}-{}...", address, from_block, to_block);
+    let logs = get_logs_in_range(endpoint, address, topics, from_block, to_block)?;
+    let blocks = group_logs_by_block_and_tx(&logs)?;
+    info!("✔ Found {} matching logs in {} blocks", logs.len(), blocks.len());
+    // NOTE: One block failing, say for not being final yet, only fails the txs in that block.
+    Ok(blocks
+        .iter()
+        .flat_map(|((_, block_hash), txs)| {
+            let maybe_block_proof_context = BlockProofContext::from_block_hash(
+                endpoint,
+                *block_hash,
+                finality_requirement,
+                maybe_cache,
+            );
+            txs
+                .values()
+                .map(|(tx_hash, block_log_indices)| {
+                    let result = match &maybe_block_proof_context {
+                        Ok(block_proof_context) =>
+                            get_receipt_logs_proof(block_proof_context, tx_hash, block_log_indices),
+                        Err(err) => Err(copy_block_err(err)),
+                    };
+                    (*tx_hash, result)
+                })
+                .collect::<Vec<(H256, Result<ReceiptLogsProof>)>>()
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock_node::MockNode;
+    use crate::utils::convert_hex_to_address;
+    use crate::test_utils::{
+        get_expected_block_1,
+        get_sample_mock_node,
+        get_sample_mock_node_fixtures,
+    };
+
+    const WETH_ADDRESS: &str = "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2";
+
+    fn get_weth_address() -> Address {
+        convert_hex_to_address(WETH_ADDRESS.to_string()).unwrap()
+    }
+
+    fn get_weth_log_proofs(
+        endpoint: &str,
+        finality_requirement: &FinalityRequirement,
+    ) -> Result<Vec<(H256, Result<ReceiptLogsProof>)>> {
+        let block_number = get_expected_block_1().number.as_u64();
+        generate_log_proofs(
+            endpoint,
+            &get_weth_address(),
+            &[],
+            block_number - 10,
+            block_number,
+            finality_requirement,
+            None,
+        )
+    }
+
+    #[test]
+    fn should_generate_one_proof_per_tx_with_matching_logs() {
+        let mock_node = get_sample_mock_node();
+        let block = get_expected_block_1();
+        let result = get_weth_log_proofs(&mock_node.get_endpoint(), &FinalityRequirement::Confirmations(0))
+            .unwrap()
+            .into_iter()
+            .map(|(tx_hash, result)| {
+                let proof = result.unwrap();
+                assert!(proof.receipt_proof.tx_hash == tx_hash);
+                proof
+            })
+            .collect::<Vec<ReceiptLogsProof>>();
+        let num_logs: usize = result.iter().map(|proof| proof.log_indices.len()).sum();
+        assert!(num_logs == 10);
+        assert!(result.len() < num_logs);
+        result
+            .iter()
+            .for_each(|proof| {
+                assert!(proof.receipt_proof.block_hash == block.hash);
+                assert!(proof.receipt_proof.tx_hash == block.transactions[proof.receipt_proof.tx_index]);
+            });
+    }
+
+    #[test]
+    fn should_fail_each_tx_in_non_final_block_without_aborting() {
+        let mock_node = get_sample_mock_node();
+        let result = get_weth_log_proofs(
+            &mock_node.get_endpoint(),
+            &FinalityRequirement::Confirmations(1_000_000_000),
+        ).unwrap();
+        assert!(!result.is_empty());
+        result
+            .iter()
+            .for_each(|(_, result)| match result {
+                Err(AppError::NotYetFinal(_)) => assert!(true),
+                _ => panic!("Tx in non-final block should not yield a proof!"),
+            });
+    }
+
+    #[test]
+    fn should_split_block_range_when_node_caps_it() {
+        let mock_node = get_sample_mock_node_fixtures()
+            .map(|fixtures| fixtures.set_max_blocks_per_logs_query(4))
+            .and_then(MockNode::start)
+            .unwrap();
+        let block_number = get_expected_block_1().number.as_u64();
+        let address = get_weth_address();
+        let endpoint = mock_node.get_endpoint();
+        match get_logs(&endpoint, &address, &[], block_number - 10, block_number + 10) {
+            Err(AppError::Custom(e)) => assert!(e.contains("exceeds the limit")),
+            _ => panic!("Mock node should cap the block range!"),
+        }
+        let result = get_logs_in_range(&endpoint, &address, &[], block_number - 10, block_number + 10)
+            .unwrap();
+        assert!(result.len() == 10);
+    }
+
+    #[test]
+    fn should_only_split_on_result_cap_errors() {
+        let get_rpc_err = |code: i64, message: &str| AppError::Custom(format!(
+            "✘ RPC call failed!\n✘ {}",
+            json!({ "jsonrpc": "2.0", "id": 1, "error": { "code": code, "message": message } }),
+        ));
+        let cap_errs = [
+            get_rpc_err(-32005, "limit exceeded"),
+            get_rpc_err(-32000, "query returned more than 10000 results"),
+            get_rpc_err(-32602, "Log response size exceeded: more than 150 results"),
+            AppError::Custom("✘ RPC call failed!\n✘ query returned more than 10000 results".to_string()),
+        ];
+        let other_errs = [
+            get_rpc_err(-32602, "invalid argument 0: too many topics"),
+            get_rpc_err(-32000, "header not found, range unavailable"),
+            get_rpc_err(-32000, "rate limit exceeded"),
+            get_rpc_err(-32000, "returned more than expected"),
+            AppError::Custom("✘ RPC call failed!\n✘ invalid argument".to_string()),
+        ];
+        assert!(cap_errs.iter().all(is_result_cap_err));
+        assert!(!other_errs.iter().any(is_result_cap_err));
+    }
+
+    #[test]
+    fn should_filter_logs_by_topic() {
+        let mock_node = get_sample_mock_node();
+        let block_number = get_expected_block_1().number.as_u64();
+        let result = get_logs_in_range(
+            &mock_node.get_endpoint(),
+            &get_weth_address(),
+            &[H256::zero()],
+            block_number,
+            block_number,
+        ).unwrap();
+        assert!(result.is_empty());
+    }
+}
@@ -0,0 +1,417 @@
+use std::fs;
+use std::path::Path;
+use ethereum_types::H256;
+use serde_json::Value as Json;
+use crate::errors::AppError;
+use crate::proof_bundle::ProofBundle;
+use crate::molecule::encode_receipt_branch;
+use crate::validate_tx_hash::validate_tx_hash;
+use crate::get_branch_from_trie::find_branch_in_trie;
+use crate::get_tx_index::get_tx_index_from_transactions;
+use crate::get_block::deserialize_block_json_to_block_struct;
+use crate::get_receipts::deserialize_receipt_json_to_receipt_struct;
+use crate::get_receipts_trie::get_receipts_trie_from_encoded_receipts;
+use crate::get_abi_output::{
+    get_abi_output,
+    DEFAULT_ABI_SIGNATURE,
+};
+use crate::get_header_chain::get_verified_rlp_encoded_header;
+use crate::get_hex_proof_from_branch::{
+    rlp_encode_node_stack,
+    get_hex_proof_from_branch,
+};
+use crate::utils::{
+    convert_hex_to_h256,
+    convert_bytes_to_hex,
+    convert_hex_to_bytes,
+};
+use crate::types::{
+    Block,
+    Bytes,
+    Result,
+    NodeStack,
+    OutputFormat,
+    ReceiptProof,
+};
+
+#[derive(Clone, Debug)]
+pub struct OfflineProof {
+    pub block: Block,
+    pub branch: NodeStack,
+    pub receipt: Bytes,
+    pub receipts: Vec<Bytes>,
+    pub receipt_proof: ReceiptProof,
+}
+
+fn get_rpc_result_from_json_string(json_string: &str) -> Result<Json> {
+    serde_json::from_str::<Json>(json_string)
+        .map_err(AppError::from)
+        .map(|json| match json.get("result") {
+            Some(result) => result.clone(),
+            None => json,
+        })
+}
+
+pub fn get_block_from_json_string(block_json: &str) -> Result<Block> {
+    get_rpc_result_from_json_string(block_json)
+        .and_then(|json| Ok(serde_json::from_value(json)?))
+        .and_then(deserialize_block_json_to_block_struct)
+}
+
+fn is_hex_string(contents: &str) -> bool {
+    let hex = contents.trim_start_matches("0x");
+    !hex.is_empty() && hex.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+pub fn get_encoded_receipt_from_bytes(bytes: Bytes) -> Result<Bytes> {
+    match String::from_utf8(bytes.clone()) {
+        Ok(ref contents) if contents.trim_start().starts_with('{') =>
+            get_rpc_result_from_json_string(contents)
+                .and_then(|json| Ok(serde_json::from_value(json)?))
+                .and_then(deserialize_receipt_json_to_receipt_struct)
+                .map(|receipt| rlp::encode(&receipt)),
+        Ok(ref contents) if is_hex_string(contents.trim()) =>
+            convert_hex_to_bytes(contents.trim().to_string()),
+        _ => Ok(bytes),
+    }
+}
+
+fn check_num_receipts(block: &Block, encoded_receipts: &[Bytes]) -> Result<()> {
+    match encoded_receipts.len() == block.transactions.len() {
+        true => Ok(()),
+        false => Err(AppError::Custom(
+            format!(
+                "✘ Got {} receipts for block {} with {} transactions!",
+                encoded_receipts.len(),
+                block.number,
+                block.transactions.len(),
+            )
+        ))
+    }
+}
+
+fn check_receipts_root(block: &Block, receipts_root: H256) -> Result<()> {
+    match receipts_root == block.receipts_root {
+        true => Ok(()),
+        false => Err(AppError::Custom(
+            format!(
+                "✘ Receipts root {:?} of given receipts does not match block {}'s {:?}!",
+                receipts_root,
+                block.number,
+                block.receipts_root,
+            )
+        ))
+    }
+}
+
+pub fn generate_offline_proof(
+    block: Block,
+    encoded_receipts: Vec<Bytes>,
+    tx_hash: &str,
+) -> Result<OfflineProof> {
+    validate_tx_hash(tx_hash.to_string())?;
+    get_verified_rlp_encoded_header(&block)?;
+    let tx_hash = convert_hex_to_h256(tx_hash.to_string())?;
+    let tx_index = get_tx_index_from_transactions(&tx_hash, &block.transactions)?;
+    check_num_receipts(&block, &encoded_receipts)?;
+    info!("✔ Building merkle-patricia trie from given receipts...");
+    let trie = get_receipts_trie_from_encoded_receipts(&encoded_receipts)?;
+    check_receipts_root(&block, trie.root)?;
+    let (_, branch) = find_branch_in_trie(trie, tx_index)?;
+    let receipt = encoded_receipts[tx_index].clone();
+    Ok(
+        OfflineProof {
+            receipt_proof: ReceiptProof {
+                tx_hash,
+                tx_index,
+                block_hash: block.hash,
+                block_number: block.number.as_u64(),
+                receipts_root: block.receipts_root,
+                receipt_data: convert_bytes_to_hex(receipt.clone()),
+                proof: get_hex_proof_from_branch(&branch)?,
+            },
+            block,
+            branch,
+            receipt,
+            receipts: encoded_receipts,
+        }
+    )
+}
+
+pub fn generate_proof_from_data(
+    block_json: &str,
+    receipts: &[String],
+    tx_hash: &str,
+) -> Result<ReceiptProof> {
+    let block = get_block_from_json_string(block_json)?;
+    receipts
+        .iter()
+        .map(|receipt| get_encoded_receipt_from_bytes(receipt.as_bytes().to_vec()))
+        .collect::<Result<Vec<Bytes>>>()
+        .and_then(|encoded_receipts| generate_offline_proof(block, encoded_receipts, tx_hash))
+        .map(|offline_proof| offline_proof.receipt_proof)
+}
+
+fn get_read_file_err(path: &Path, e: std::io::Error) -> AppError {
+    AppError::Custom(format!("✘ Cannot read file at {}!\n✘ {}", path.display(), e))
+}
+
+fn read_file(path: &Path) -> Result<Bytes> {
+    fs::read(path)
+        .map_err(|e| get_read_file_err(path, e))
+}
+
+pub fn generate_offline_proof_from_files(
+    block_path: &str,
+    receipts_dir: &str,
+    tx_hash: &str,
+) -> Result<OfflineProof> {
+    info!("✔ Reading block from {}...", block_path);
+    let block = fs::read_to_string(block_path)
+        .map_err(|e| get_read_file_err(Path::new(block_path), e))
+        .and_then(|block_json| get_block_from_json_string(&block_json))?;
+    info!("✔ Reading {} receipts from {}...", block.transactions.len(), receipts_dir);
+    block.transactions
+        .iter()
+        .map(|hash| read_file(&Path::new(receipts_dir).join(format!("{:?}", hash))))
+        .map(|bytes| bytes.and_then(get_encoded_receipt_from_bytes))
+        .collect::<Result<Vec<Bytes>>>()
+        .and_then(|encoded_receipts| generate_offline_proof(block, encoded_receipts, tx_hash))
+}
+
+pub fn get_output_from_offline_proof(
+    offline_proof: &OfflineProof,
+    output_format: OutputFormat,
+    maybe_abi_signature: Option<String>,
+) -> Result<Bytes> {
+    info!("✔ Formatting proof output...");
+    match output_format {
+        OutputFormat::Hex =>
+            Ok(format!("{}\n", offline_proof.receipt_proof.proof).into_bytes()),
+        OutputFormat::Json => serde_json::to_string(&offline_proof.receipt_proof)
+            .map_err(AppError::from)
+            .map(|json| format!("{}\n", json).into_bytes()),
+        OutputFormat::RlpBin => rlp_encode_node_stack(&offline_proof.branch),
+        OutputFormat::Molecule => encode_receipt_branch(&offline_proof.branch),
+        OutputFormat::Abi => get_abi_output(
+            &offline_proof.block,
+            &offline_proof.branch,
+            offline_proof.receipt_proof.tx_hash,
+            offline_proof.receipt_proof.tx_index,
+            offline_proof.receipt.clone(),
+            &maybe_abi_signature.unwrap_or_else(|| DEFAULT_ABI_SIGNATURE.to_string()),
+        )
+            .map(|abi| format!("{}\n", abi).into_bytes()),
+        OutputFormat::Bundle => ProofBundle::from_encoded_receipts(
+            &offline_proof.block,
+            offline_proof.receipts.clone(),
+            &offline_proof.branch,
+            offline_proof.receipt_proof.tx_index,
+        )
+            .and_then(|bundle| bundle.to_json())
+            .map(|json| format!("{}\n", json).into_bytes()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decode_proof::get_proof_nodes;
+    use crate::verify_proof::verify_receipt_proof;
+    use crate::get_keccak_hash::keccak_hash_bytes;
+    use crate::get_header_chain::get_rlp_encoded_header;
+    use crate::test_utils::{
+        PROOF_1_INDEX,
+        RECEIPTS_ROOT_1,
+        get_sample_proof_1,
+        get_temp_cache_dir,
+        SAMPLE_BLOCK_JSON_PATH,
+        SAMPLE_RECEIPT_JSON_PATH,
+        SAMPLE_TYPED_RECEIPT_JSON_PATH,
+    };
+
+    const NUM_SAMPLE_RECEIPTS: usize = 20;
+
+    fn get_sample_encoded_receipt() -> Bytes {
+        get_proof_nodes(&convert_hex_to_bytes(get_sample_proof_1()).unwrap())
+            .and_then(|proof_nodes|
+                verify_receipt_proof(
+                    &proof_nodes,
+                    &convert_hex_to_h256(RECEIPTS_ROOT_1.to_string()).unwrap(),
+                    PROOF_1_INDEX,
+                )
+            )
+            .unwrap()
+    }
+
+    fn get_sample_block_json_with_receipts(encoded_receipts: &[Bytes]) -> Json {
+        let mut json: Json = serde_json::from_str(&fs::read_to_string(SAMPLE_BLOCK_JSON_PATH).unwrap())
+            .unwrap();
+        let receipts_root = get_receipts_trie_from_encoded_receipts(encoded_receipts)
+            .unwrap()
+            .root;
+        let tx_hashes = (0..encoded_receipts.len())
+            .map(|i| format!("{:?}", H256::from_low_u64_be(i as u64 + 1)))
+            .collect::<Vec<String>>();
+        json["result"]["receiptsRoot"] = json!(format!("{:?}", receipts_root));
+        json["result"]["transactions"] = json!(tx_hashes);
+        let block = get_block_from_json_string(&json.to_string()).unwrap();
+        let hash = keccak_hash_bytes(&get_rlp_encoded_header(&block)).unwrap();
+        json["result"]["hash"] = json!(format!("{:?}", hash));
+        json
+    }
+
+    fn get_sample_hex_receipts() -> Vec<String> {
+        vec![convert_bytes_to_hex(get_sample_encoded_receipt()); NUM_SAMPLE_RECEIPTS]
+    }
+
+    fn get_sample_tx_hash(tx_index: usize) -> String {
+        format!("{:?}", H256::from_low_u64_be(tx_index as u64 + 1))
+    }
+
+    fn get_sample_offline_proof() -> OfflineProof {
+        let encoded_receipts = vec![get_sample_encoded_receipt(); NUM_SAMPLE_RECEIPTS];
+        let block_json = get_sample_block_json_with_receipts(&encoded_receipts);
+        generate_offline_proof(
+            get_block_from_json_string(&block_json.to_string()).unwrap(),
+            encoded_receipts,
+            &get_sample_tx_hash(7),
+        ).unwrap()
+    }
+
+    #[test]
+    fn should_generate_proof_from_data_that_verifies() {
+        let tx_index = 7;
+        let hex_receipts = get_sample_hex_receipts();
+        let block_json = get_sample_block_json_with_receipts(&vec![get_sample_encoded_receipt(); NUM_SAMPLE_RECEIPTS]);
+        let result = generate_proof_from_data(
+            &block_json.to_string(),
+            &hex_receipts,
+            &get_sample_tx_hash(tx_index),
+        ).unwrap();
+        assert!(result.tx_index == tx_index);
+        assert!(result.receipt_data == hex_receipts[tx_index]);
+        let receipt = get_proof_nodes(&convert_hex_to_bytes(result.proof.clone()).unwrap())
+            .and_then(|proof_nodes| verify_receipt_proof(&proof_nodes, &result.receipts_root, tx_index))
+            .unwrap();
+        assert!(receipt == get_sample_encoded_receipt());
+    }
+
+    #[test]
+    fn should_get_block_from_bare_or_rpc_response_json() {
+        let rpc_response: Json = serde_json::from_str(&fs::read_to_string(SAMPLE_BLOCK_JSON_PATH).unwrap())
+            .unwrap();
+        let from_rpc_response = get_block_from_json_string(&rpc_response.to_string())
+            .unwrap();
+        let from_bare_json = get_block_from_json_string(&rpc_response["result"].to_string())
+            .unwrap();
+        assert!(from_rpc_response.hash == from_bare_json.hash);
+        assert!(from_rpc_response.transactions == from_bare_json.transactions);
+    }
+
+    #[test]
+    fn should_get_same_encoded_receipt_from_json_hex_and_raw_bytes() {
+        let from_json = get_encoded_receipt_from_bytes(fs::read(SAMPLE_RECEIPT_JSON_PATH).unwrap())
+            .unwrap();
+        let from_hex = get_encoded_receipt_from_bytes(format!("0x{}", convert_bytes_to_hex(from_json.clone())).into_bytes())
+            .unwrap();
+        let from_raw_bytes = get_encoded_receipt_from_bytes(from_json.clone())
+            .unwrap();
+        assert!(from_hex == from_json);
+        assert!(from_raw_bytes == from_json);
+    }
+
+    #[test]
+    fn should_keep_tx_type_of_typed_receipt_json() {
+        let legacy = get_encoded_receipt_from_bytes(fs::read(SAMPLE_RECEIPT_JSON_PATH).unwrap())
+            .unwrap();
+        let typed = get_encoded_receipt_from_bytes(fs::read(SAMPLE_TYPED_RECEIPT_JSON_PATH).unwrap())
+            .unwrap();
+        let from_hex = get_encoded_receipt_from_bytes(format!("0x{}", convert_bytes_to_hex(typed.clone())).into_bytes())
+            .unwrap();
+        assert!(typed == [vec![0x02], legacy].concat());
+        assert!(from_hex == typed);
+    }
+
+    #[test]
+    fn should_err_on_receipts_root_mismatch() {
+        let encoded_receipts = vec![get_sample_encoded_receipt(); NUM_SAMPLE_RECEIPTS];
+        let block_json = get_sample_block_json_with_receipts(&encoded_receipts);
+        let block = get_block_from_json_string(&block_json.to_string()).unwrap();
+        let mut wrong_receipts = encoded_receipts.clone();
+        wrong_receipts[3] = vec![0xc0];
+        match generate_offline_proof(block, wrong_receipts, &get_sample_tx_hash(7)) {
+            Err(AppError::Custom(e)) => assert!(e.contains("does not match")),
+            _ => panic!("Proof should not be generated from the wrong receipts!"),
+        }
+    }
+
+    #[test]
+    fn should_err_when_block_hash_does_not_commit_to_header() {
+        let encoded_receipts = vec![get_sample_encoded_receipt(); NUM_SAMPLE_RECEIPTS];
+        let mut block_json = get_sample_block_json_with_receipts(&encoded_receipts);
+        block_json["result"]["hash"] = json!(format!("{:?}", H256::zero()));
+        let block = get_block_from_json_string(&block_json.to_string()).unwrap();
+        match generate_offline_proof(block, encoded_receipts, &get_sample_tx_hash(7)) {
+            Err(AppError::Custom(e)) => assert!(e.contains("hashes to")),
+            _ => panic!("Proof should not be generated for a header not matching its hash!"),
+        }
+    }
+
+    #[test]
+    fn should_err_on_wrong_number_of_receipts() {
+        let encoded_receipts = vec![get_sample_encoded_receipt(); NUM_SAMPLE_RECEIPTS];
+        let block_json = get_sample_block_json_with_receipts(&encoded_receipts);
+        let block = get_block_from_json_string(&block_json.to_string()).unwrap();
+        match generate_offline_proof(block, encoded_receipts[1..].to_vec(), &get_sample_tx_hash(7)) {
+            Err(AppError::Custom(e)) => assert!(e.contains("Got 19 receipts")),
+            _ => panic!("Proof should not be generated from too few receipts!"),
+        }
+    }
+
+    #[test]
+    fn should_generate_offline_proof_from_files() {
+        let dir = get_temp_cache_dir("from-files");
+        fs::create_dir_all(&dir).unwrap();
+        let encoded_receipts = vec![get_sample_encoded_receipt(); NUM_SAMPLE_RECEIPTS];
+        let block_path = format!("{}/block.json", dir);
+        fs::write(&block_path, get_sample_block_json_with_receipts(&encoded_receipts).to_string())
+            .unwrap();
+        encoded_receipts
+            .iter()
+            .enumerate()
+            .for_each(|(i, encoded_receipt)| match i % 2 {
+                0 => fs::write(format!("{}/{}", dir, get_sample_tx_hash(i)), encoded_receipt).unwrap(),
+                _ => fs::write(format!("{}/{}", dir, get_sample_tx_hash(i)), convert_bytes_to_hex(encoded_receipt.clone())).unwrap(),
+            });
+        let result = generate_offline_proof_from_files(&block_path, &dir, &get_sample_tx_hash(7))
+            .unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(result.receipt_proof == get_sample_offline_proof().receipt_proof);
+    }
+
+    #[test]
+    fn should_get_output_from_offline_proof_in_every_format() {
+        let offline_proof = get_sample_offline_proof();
+        let hex = get_output_from_offline_proof(&offline_proof, OutputFormat::Hex, None)
+            .unwrap();
+        let json = get_output_from_offline_proof(&offline_proof, OutputFormat::Json, None)
+            .unwrap();
+        let rlp_bin = get_output_from_offline_proof(&offline_proof, OutputFormat::RlpBin, None)
+            .unwrap();
+        let abi = get_output_from_offline_proof(
+            &offline_proof,
+            OutputFormat::Abi,
+            Some("(bytes receipt, uint256 index)".to_string()),
+        ).unwrap();
+        assert!(hex == format!("{}\n", offline_proof.receipt_proof.proof).into_bytes());
+        assert!(String::from_utf8(json).unwrap().contains("\"tx_index\":7"));
+        assert!(convert_bytes_to_hex(rlp_bin) == offline_proof.receipt_proof.proof);
+        assert!(abi.starts_with(b"0x"));
+        assert!(get_output_from_offline_proof(&offline_proof, OutputFormat::Molecule, None).is_ok());
+        let bundle = get_output_from_offline_proof(&offline_proof, OutputFormat::Bundle, None)
+            .and_then(|bundle| ProofBundle::from_json(&String::from_utf8(bundle).unwrap()))
+            .unwrap();
+        assert!(bundle.verify().unwrap() == offline_proof.receipt);
+    }
+}
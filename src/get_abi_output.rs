@@ -0,0 +1,246 @@
+use ethabi::{
+    Token,
+    ParamType,
+    param_type::Reader,
+};
+use ethereum_types::{
+    H256,
+    U256,
+};
+use crate::errors::AppError;
+use crate::decode_proof::get_proof_nodes;
+use crate::get_hex_proof_from_branch::rlp_encode_node_stack;
+use crate::get_header_chain::get_verified_rlp_encoded_header;
+use crate::utils::convert_bytes_to_hex;
+use crate::types::{
+    Block,
+    Bytes,
+    Result,
+    NodeStack,
+};
+
+pub const DEFAULT_ABI_SIGNATURE: &str =
+    "(bytes header, bytes[] proof, uint256 index, bytes receipt)";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AbiField {
+    pub name: String,
+    pub kind: ParamType,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AbiProofParts {
+    pub header: Bytes,
+    pub proof: Bytes,
+    pub index: usize,
+    pub receipt: Bytes,
+    pub tx_hash: H256,
+    pub block_hash: H256,
+    pub block_number: U256,
+    pub receipts_root: H256,
+}
+
+fn get_abi_field_err(field: &AbiField) -> AppError {
+    AppError::Custom(
+        format!(
+            "✘ ABI field `{}` cannot be encoded as `{}`!",
+            field.name,
+            field.kind,
+        )
+    )
+}
+
+fn is_supported_abi_field(field: &AbiField) -> bool {
+    match (field.name.as_str(), &field.kind) {
+        ("header", ParamType::Bytes) |
+        ("proof", ParamType::Bytes) |
+        ("receipt", ParamType::Bytes) => true,
+        ("proof", ParamType::Array(kind)) => **kind == ParamType::Bytes,
+        ("index", ParamType::Uint(_)) |
+        ("block_number", ParamType::Uint(_)) => true,
+        ("tx_hash", ParamType::FixedBytes(32)) |
+        ("block_hash", ParamType::FixedBytes(32)) |
+        ("receipts_root", ParamType::FixedBytes(32)) => true,
+        _ => false,
+    }
+}
+
+fn parse_abi_field(field_string: &str) -> Result<AbiField> {
+    match field_string.split_whitespace().collect::<Vec<&str>>()[..] {
+        [kind, name] => Reader::read(kind)
+            .map_err(|_| AppError::Custom(format!("✘ Unknown ABI type `{}`!", kind)))
+            .map(|kind| AbiField { name: name.to_string(), kind })
+            .and_then(|field| match is_supported_abi_field(&field) {
+                true => Ok(field),
+                false => Err(get_abi_field_err(&field)),
+            }),
+        _ => Err(AppError::Custom(
+            format!("✘ ABI field `{}` must be a type followed by a name!", field_string)
+        ))
+    }
+}
+
+pub fn parse_abi_signature(signature: &str) -> Result<Vec<AbiField>> {
+    let signature = signature.trim();
+    match signature.starts_with('(') && signature.ends_with(')') && signature.len() > 2 {
+        true => signature[1..signature.len() - 1]
+            .split(',')
+            .map(parse_abi_field)
+            .collect(),
+        false => Err(AppError::Custom(
+            format!(
+                "✘ ABI signature `{}` must be a non-empty tuple, eg: `{}`",
+                signature,
+                DEFAULT_ABI_SIGNATURE,
+            )
+        ))
+    }
+}
+
+fn get_abi_token(field: &AbiField, parts: &AbiProofParts) -> Result<Token> {
+    match (field.name.as_str(), &field.kind) {
+        ("header", _) => Ok(Token::Bytes(parts.header.clone())),
+        ("receipt", _) => Ok(Token::Bytes(parts.receipt.clone())),
+        ("proof", ParamType::Bytes) => Ok(Token::Bytes(parts.proof.clone())),
+        ("proof", _) => get_proof_nodes(&parts.proof)
+            .map(|nodes| Token::Array(nodes.into_iter().map(Token::Bytes).collect())),
+        ("index", _) => Ok(Token::Uint(U256::from(parts.index))),
+        ("block_number", _) => Ok(Token::Uint(parts.block_number)),
+        ("tx_hash", _) => Ok(Token::FixedBytes(parts.tx_hash.as_bytes().to_vec())),
+        ("block_hash", _) => Ok(Token::FixedBytes(parts.block_hash.as_bytes().to_vec())),
+        ("receipts_root", _) => Ok(Token::FixedBytes(parts.receipts_root.as_bytes().to_vec())),
+        _ => Err(get_abi_field_err(field)),
+    }
+}
+
+pub fn abi_encode_proof(fields: &[AbiField], parts: &AbiProofParts) -> Result<Bytes> {
+    fields
+        .iter()
+        .map(|field| get_abi_token(field, parts))
+        .collect::<Result<Vec<Token>>>()
+        .map(|tokens| ethabi::encode(&tokens))
+}
+
+fn get_abi_proof_parts(
+    block: &Block,
+    branch: &NodeStack,
+    tx_hash: H256,
+    index: usize,
+    receipt: Bytes,
+    fields: &[AbiField],
+) -> Result<AbiProofParts> {
+    Ok(
+        AbiProofParts {
+            index,
+            receipt,
+            tx_hash,
+            block_hash: block.hash,
+            block_number: block.number,
+            receipts_root: block.receipts_root,
+            proof: rlp_encode_node_stack(branch)?,
+            header: match fields.iter().any(|field| field.name == "header") {
+                true => get_verified_rlp_encoded_header(block)?,
+                false => vec![],
+            },
+        }
+    )
+}
+
+pub fn get_abi_output(
+    block: &Block,
+    branch: &NodeStack,
+    tx_hash: H256,
+    index: usize,
+    receipt: Bytes,
+    abi_signature: &str,
+) -> Result<String> {
+    let fields = parse_abi_signature(abi_signature)?;
+    get_abi_proof_parts(block, branch, tx_hash, index, receipt, &fields)
+        .and_then(|parts| abi_encode_proof(&fields, &parts))
+        .map(|encoded| format!("0x{}", convert_bytes_to_hex(encoded)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{
+        get_expected_block,
+        get_sample_proof_1,
+        get_valid_tx_hash_h256,
+    };
+    use crate::utils::convert_hex_to_bytes;
+
+    fn get_sample_abi_proof_parts() -> AbiProofParts {
+        let block = get_expected_block();
+        AbiProofParts {
+            header: get_verified_rlp_encoded_header(&block).unwrap(),
+            proof: convert_hex_to_bytes(get_sample_proof_1()).unwrap(),
+            index: 14,
+            receipt: vec![0xc0],
+            tx_hash: get_valid_tx_hash_h256().unwrap(),
+            block_hash: block.hash,
+            block_number: block.number,
+            receipts_root: block.receipts_root,
+        }
+    }
+
+    #[test]
+    fn should_parse_default_abi_signature() {
+        let result = parse_abi_signature(DEFAULT_ABI_SIGNATURE)
+            .unwrap();
+        let names: Vec<&str> = result.iter().map(|field| field.name.as_str()).collect();
+        assert!(names == vec!["header", "proof", "index", "receipt"]);
+        assert!(result[1].kind == ParamType::Array(Box::new(ParamType::Bytes)));
+        assert!(result[2].kind == ParamType::Uint(256));
+    }
+
+    #[test]
+    fn should_err_on_unsupported_abi_field() {
+        match parse_abi_signature("(bytes header, address index)") {
+            Err(AppError::Custom(e)) => assert!(e.contains("`index` cannot be encoded as `address`")),
+            _ => panic!("Address should not be accepted for the index!"),
+        }
+    }
+
+    #[test]
+    fn should_err_on_abi_signature_that_is_not_a_tuple() {
+        match parse_abi_signature("bytes header") {
+            Err(AppError::Custom(e)) => assert!(e.contains("must be a non-empty tuple")),
+            _ => panic!("Signature without parentheses should not be accepted!"),
+        }
+    }
+
+    #[test]
+    fn should_abi_encode_proof_with_default_signature() {
+        let parts = get_sample_abi_proof_parts();
+        let fields = parse_abi_signature(DEFAULT_ABI_SIGNATURE).unwrap();
+        let result = abi_encode_proof(&fields, &parts)
+            .unwrap();
+        let expected_nodes = get_proof_nodes(&parts.proof)
+            .unwrap()
+            .into_iter()
+            .map(Token::Bytes)
+            .collect();
+        let kinds: Vec<ParamType> = fields.into_iter().map(|field| field.kind).collect();
+        let decoded = ethabi::decode(&kinds, &result)
+            .unwrap();
+        assert!(decoded == vec![
+            Token::Bytes(parts.header),
+            Token::Array(expected_nodes),
+            Token::Uint(U256::from(14)),
+            Token::Bytes(parts.receipt),
+        ]);
+    }
+
+    #[test]
+    fn should_abi_encode_proof_with_custom_signature() {
+        let parts = get_sample_abi_proof_parts();
+        let fields = parse_abi_signature("(bytes32 block_hash, uint64 index, bytes proof)")
+            .unwrap();
+        let result = abi_encode_proof(&fields, &parts)
+            .unwrap();
+        assert!(result.len() == 32 * 4 + parts.proof.len() + (32 - parts.proof.len() % 32) % 32);
+        assert!(result[..32] == parts.block_hash.as_bytes()[..]);
+        assert!(result[63] == 14);
+    }
+}
@@ -0,0 +1,821 @@
+use reqwest;
+use std::time::Duration;
+use ethereum_types::{
+    H256,
+    U256,
+};
+use serde_json::Value as Json;
+use crate::errors::AppError;
+use crate::utils::{
+    convert_hex_to_h256,
+    convert_hex_to_bytes,
+};
+use crate::constants::REQWEST_TIMEOUT_TIME;
+use crate::types::{
+    Block,
+    Bytes,
+    Result,
+};
+use crate::ssz::{
+    get_depth,
+    hash_tree_root_u64,
+    get_merkle_branch,
+    hash_tree_root_u256,
+    hash_tree_root_bitlist,
+    is_valid_merkle_branch,
+    hash_tree_root_u64_list,
+    hash_tree_root_container,
+    hash_tree_root_bitvector,
+    hash_tree_root_byte_list,
+    hash_tree_root_byte_vector,
+    hash_tree_root_list_of_roots,
+    hash_tree_root_vector_of_roots,
+};
+
+pub const MAINNET_BEACON_GENESIS_TIME: u64 = 1606824023;
+const SECONDS_PER_SLOT: u64 = 12;
+const MAX_PROPOSER_SLASHINGS: usize = 16;
+const MAX_ATTESTER_SLASHINGS: usize = 2;
+const MAX_ATTESTER_SLASHINGS_ELECTRA: usize = 1;
+const MAX_ATTESTATIONS: usize = 128;
+const MAX_ATTESTATIONS_ELECTRA: usize = 8;
+const MAX_DEPOSITS: usize = 16;
+const MAX_VOLUNTARY_EXITS: usize = 16;
+const MAX_BLS_TO_EXECUTION_CHANGES: usize = 16;
+const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize = 4096;
+const MAX_VALIDATORS_PER_COMMITTEE: usize = 2048;
+const MAX_COMMITTEES_PER_SLOT: usize = 64;
+pub const SYNC_COMMITTEE_SIZE: usize = 512;
+const MAX_EXTRA_DATA_BYTES: usize = 32;
+const MAX_BYTES_PER_TRANSACTION: usize = 1 << 30;
+const MAX_TRANSACTIONS_PER_PAYLOAD: usize = 1 << 20;
+const MAX_WITHDRAWALS_PER_PAYLOAD: usize = 16;
+const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize = 8192;
+const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize = 16;
+const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize = 2;
+const RECEIPTS_ROOT_PAYLOAD_INDEX: usize = 3;
+const BLOCK_HASH_PAYLOAD_INDEX: usize = 12;
+pub const EXECUTION_PAYLOAD_BODY_INDEX: usize = 9;
+const BODY_ROOT_HEADER_INDEX: usize = 4;
+const NUM_HEADER_FIELDS: usize = 5;
+
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub enum BeaconFork {
+    Bellatrix,
+    Capella,
+    Deneb,
+    Electra,
+    Fulu,
+}
+
+impl BeaconFork {
+    pub fn from_version(version: &str) -> Result<BeaconFork> {
+        match version {
+            "bellatrix" => Ok(BeaconFork::Bellatrix),
+            "capella" => Ok(BeaconFork::Capella),
+            "deneb" => Ok(BeaconFork::Deneb),
+            "electra" => Ok(BeaconFork::Electra),
+            "fulu" => Ok(BeaconFork::Fulu),
+            _ => Err(AppError::Custom(
+                format!("✘ Beacon blocks from `{}` have no execution payload!", version)
+            ))
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BeaconBlockHeader {
+    pub slot: u64,
+    pub proposer_index: u64,
+    pub parent_root: H256,
+    pub state_root: H256,
+    pub body_root: H256,
+}
+
+impl BeaconBlockHeader {
+    fn get_field_roots(&self) -> Vec<H256> {
+        vec![
+            hash_tree_root_u64(self.slot),
+            hash_tree_root_u64(self.proposer_index),
+            self.parent_root,
+            self.state_root,
+            self.body_root,
+        ]
+    }
+
+    pub fn hash_tree_root(&self) -> Result<H256> {
+        hash_tree_root_container(&self.get_field_roots())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ExecutionPayloadProof {
+    pub beacon_block_root: H256,
+    pub beacon_block_header: BeaconBlockHeader,
+    pub execution_block_hash: H256,
+    pub block_hash_branch: Vec<H256>,
+    pub block_hash_gindex: u64,
+    pub receipts_root: H256,
+    pub receipts_root_branch: Vec<H256>,
+    pub receipts_root_gindex: u64,
+}
+
+impl ExecutionPayloadProof {
+    pub fn is_valid(&self) -> bool {
+        match (
+            get_index_from_gindex(self.block_hash_gindex),
+            get_index_from_gindex(self.receipts_root_gindex),
+        ) {
+            (Ok(block_hash_index), Ok(receipts_root_index)) => is_valid_merkle_branch(
+                &self.execution_block_hash,
+                &self.block_hash_branch,
+                block_hash_index,
+                &self.beacon_block_root,
+            ) && is_valid_merkle_branch(
+                &self.receipts_root,
+                &self.receipts_root_branch,
+                receipts_root_index,
+                &self.beacon_block_root,
+            ),
+            _ => false,
+        }
+    }
+
+    pub fn check_matches_block(&self, block: &Block) -> Result<()> {
+        match self.execution_block_hash == block.hash &&
+            self.receipts_root == block.receipts_root {
+            true => Ok(()),
+            false => Err(AppError::Custom(
+                format!(
+                    "✘ Execution payload of beacon block {:?} is not block {:?}!",
+                    self.beacon_block_root,
+                    block.hash,
+                )
+            ))
+        }
+    }
+}
+
+pub fn get_index_from_gindex(gindex: u64) -> Result<u64> {
+    match gindex {
+        0 => Err(AppError::Custom("✘ Generalized index 0 is not in any merkle tree!".to_string())),
+        _ => Ok(gindex - (1 << (63 - gindex.leading_zeros()))),
+    }
+}
+
+pub fn get_slot_from_timestamp(timestamp: u64, genesis_time: u64) -> u64 {
+    timestamp.saturating_sub(genesis_time) / SECONDS_PER_SLOT
+}
+
+pub fn get_field<'a>(json: &'a Json, key: &str) -> Result<&'a Json> {
+    match json.get(key) {
+        Some(value) => Ok(value),
+        None => Err(AppError::Custom(
+            format!("✘ No `{}` field in beacon block JSON!", key)
+        ))
+    }
+}
+
+pub fn get_str<'a>(json: &'a Json, key: &str) -> Result<&'a str> {
+    match get_field(json, key)?.as_str() {
+        Some(string) => Ok(string),
+        None => Err(AppError::Custom(
+            format!("✘ Beacon block field `{}` is not a string!", key)
+        ))
+    }
+}
+
+pub fn get_array<'a>(json: &'a Json, key: &str) -> Result<&'a Vec<Json>> {
+    match get_field(json, key)?.as_array() {
+        Some(array) => Ok(array),
+        None => Err(AppError::Custom(
+            format!("✘ Beacon block field `{}` is not an array!", key)
+        ))
+    }
+}
+
+pub fn get_u64(json: &Json, key: &str) -> Result<u64> {
+    get_str(json, key)?
+        .parse::<u64>()
+        .map_err(|_| AppError::Custom(
+            format!("✘ Beacon block field `{}` is not a u64!", key)
+        ))
+}
+
+pub fn get_bytes(json: &Json, key: &str) -> Result<Bytes> {
+    convert_hex_to_bytes(get_str(json, key)?.to_string())
+}
+
+pub fn get_h256(json: &Json, key: &str) -> Result<H256> {
+    convert_hex_to_h256(get_str(json, key)?.to_string())
+}
+
+pub fn convert_json_to_bytes(json: &Json) -> Result<Bytes> {
+    match json.as_str() {
+        Some(hex) => convert_hex_to_bytes(hex.to_string()),
+        None => Err(AppError::Custom(
+            format!("✘ Beacon block value {} is not a hex string!", json)
+        ))
+    }
+}
+
+fn convert_json_to_u64(json: &Json) -> Result<u64> {
+    match json.as_str().and_then(|decimal| decimal.parse::<u64>().ok()) {
+        Some(value) => Ok(value),
+        None => Err(AppError::Custom(
+            format!("✘ Beacon block value {} is not a u64!", json)
+        ))
+    }
+}
+
+fn convert_json_to_bytes_root(json: &Json) -> Result<H256> {
+    convert_json_to_bytes(json).and_then(|bytes| hash_tree_root_byte_vector(&bytes))
+}
+
+pub fn get_u64_root(json: &Json, key: &str) -> Result<H256> {
+    get_u64(json, key).map(hash_tree_root_u64)
+}
+
+pub fn get_bytes_root(json: &Json, key: &str) -> Result<H256> {
+    get_bytes(json, key).and_then(|bytes| hash_tree_root_byte_vector(&bytes))
+}
+
+pub fn get_list_root<F>(
+    json: &Json,
+    key: &str,
+    max_length: usize,
+    get_item_root: F,
+) -> Result<H256> where F: Fn(&Json) -> Result<H256> {
+    get_array(json, key)?
+        .iter()
+        .map(get_item_root)
+        .collect::<Result<Vec<H256>>>()
+        .and_then(|roots| hash_tree_root_list_of_roots(&roots, max_length))
+}
+
+pub fn get_container_root(field_roots: Vec<Result<H256>>) -> Result<H256> {
+    field_roots
+        .into_iter()
+        .collect::<Result<Vec<H256>>>()
+        .and_then(|roots| hash_tree_root_container(&roots))
+}
+
+fn get_checkpoint_root(json: &Json) -> Result<H256> {
+    get_container_root(vec![
+        get_u64_root(json, "epoch"),
+        get_bytes_root(json, "root"),
+    ])
+}
+
+fn get_attestation_data_root(json: &Json) -> Result<H256> {
+    get_container_root(vec![
+        get_u64_root(json, "slot"),
+        get_u64_root(json, "index"),
+        get_bytes_root(json, "beacon_block_root"),
+        get_field(json, "source").and_then(get_checkpoint_root),
+        get_field(json, "target").and_then(get_checkpoint_root),
+    ])
+}
+
+pub fn get_beacon_block_header_from_json(json: &Json) -> Result<BeaconBlockHeader> {
+    Ok(
+        BeaconBlockHeader {
+            slot: get_u64(json, "slot")?,
+            proposer_index: get_u64(json, "proposer_index")?,
+            parent_root: get_h256(json, "parent_root")?,
+            state_root: get_h256(json, "state_root")?,
+            body_root: get_h256(json, "body_root")?,
+        }
+    )
+}
+
+fn get_signed_beacon_block_header_root(json: &Json) -> Result<H256> {
+    get_container_root(vec![
+        get_field(json, "message")
+            .and_then(get_beacon_block_header_from_json)
+            .and_then(|header| header.hash_tree_root()),
+        get_bytes_root(json, "signature"),
+    ])
+}
+
+fn get_proposer_slashing_root(json: &Json) -> Result<H256> {
+    get_container_root(vec![
+        get_field(json, "signed_header_1").and_then(get_signed_beacon_block_header_root),
+        get_field(json, "signed_header_2").and_then(get_signed_beacon_block_header_root),
+    ])
+}
+
+fn get_indexed_attestation_root(json: &Json, fork: BeaconFork) -> Result<H256> {
+    let max_indices = match fork {
+        BeaconFork::Electra | BeaconFork::Fulu => MAX_VALIDATORS_PER_COMMITTEE * MAX_COMMITTEES_PER_SLOT,
+        _ => MAX_VALIDATORS_PER_COMMITTEE,
+    };
+    get_container_root(vec![
+        get_array(json, "attesting_indices")
+            .and_then(|indices|
+                indices
+                    .iter()
+                    .map(convert_json_to_u64)
+                    .collect::<Result<Vec<u64>>>()
+            )
+            .and_then(|indices| hash_tree_root_u64_list(&indices, max_indices)),
+        get_field(json, "data").and_then(get_attestation_data_root),
+        get_bytes_root(json, "signature"),
+    ])
+}
+
+fn get_attester_slashing_root(json: &Json, fork: BeaconFork) -> Result<H256> {
+    get_container_root(vec![
+        get_field(json, "attestation_1")
+            .and_then(|attestation| get_indexed_attestation_root(attestation, fork)),
+        get_field(json, "attestation_2")
+            .and_then(|attestation| get_indexed_attestation_root(attestation, fork)),
+    ])
+}
+
+fn get_attestation_root(json: &Json, fork: BeaconFork) -> Result<H256> {
+    let aggregation_bits_root = match fork {
+        BeaconFork::Electra | BeaconFork::Fulu => hash_tree_root_bitlist(
+            &get_bytes(json, "aggregation_bits")?,
+            MAX_VALIDATORS_PER_COMMITTEE * MAX_COMMITTEES_PER_SLOT,
+        ),
+        _ => hash_tree_root_bitlist(
+            &get_bytes(json, "aggregation_bits")?,
+            MAX_VALIDATORS_PER_COMMITTEE,
+        ),
+    };
+    let mut field_roots = vec![
+        aggregation_bits_root,
+        get_field(json, "data").and_then(get_attestation_data_root),
+        get_bytes_root(json, "signature"),
+    ];
+    if fork >= BeaconFork::Electra {
+        field_roots.push(
+            get_bytes(json, "committee_bits")
+                .and_then(|bits| hash_tree_root_bitvector(&bits, MAX_COMMITTEES_PER_SLOT))
+        );
+    }
+    get_container_root(field_roots)
+}
+
+fn get_deposit_root(json: &Json) -> Result<H256> {
+    let deposit_data = get_field(json, "data")?;
+    get_container_root(vec![
+        get_array(json, "proof")
+            .and_then(|proof|
+                proof
+                    .iter()
+                    .map(convert_json_to_bytes_root)
+                    .collect::<Result<Vec<H256>>>()
+            )
+            .and_then(|roots| hash_tree_root_vector_of_roots(&roots)),
+        get_container_root(vec![
+            get_bytes_root(deposit_data, "pubkey"),
+            get_bytes_root(deposit_data, "withdrawal_credentials"),
+            get_u64_root(deposit_data, "amount"),
+            get_bytes_root(deposit_data, "signature"),
+        ]),
+    ])
+}
+
+fn get_signed_voluntary_exit_root(json: &Json) -> Result<H256> {
+    let message = get_field(json, "message")?;
+    get_container_root(vec![
+        get_container_root(vec![
+            get_u64_root(message, "epoch"),
+            get_u64_root(message, "validator_index"),
+        ]),
+        get_bytes_root(json, "signature"),
+    ])
+}
+
+fn get_eth1_data_root(json: &Json) -> Result<H256> {
+    get_container_root(vec![
+        get_bytes_root(json, "deposit_root"),
+        get_u64_root(json, "deposit_count"),
+        get_bytes_root(json, "block_hash"),
+    ])
+}
+
+fn get_sync_aggregate_root(json: &Json) -> Result<H256> {
+    get_container_root(vec![
+        get_bytes(json, "sync_committee_bits")
+            .and_then(|bits| hash_tree_root_bitvector(&bits, SYNC_COMMITTEE_SIZE)),
+        get_bytes_root(json, "sync_committee_signature"),
+    ])
+}
+
+pub fn get_withdrawal_root(json: &Json) -> Result<H256> {
+    get_container_root(vec![
+        get_u64_root(json, "index"),
+        get_u64_root(json, "validator_index"),
+        get_bytes_root(json, "address"),
+        get_u64_root(json, "amount"),
+    ])
+}
+
+fn get_signed_bls_to_execution_change_root(json: &Json) -> Result<H256> {
+    let message = get_field(json, "message")?;
+    get_container_root(vec![
+        get_container_root(vec![
+            get_u64_root(message, "validator_index"),
+            get_bytes_root(message, "from_bls_pubkey"),
+            get_bytes_root(message, "to_execution_address"),
+        ]),
+        get_bytes_root(json, "signature"),
+    ])
+}
+
+fn get_execution_requests_root(json: &Json) -> Result<H256> {
+    get_container_root(vec![
+        get_list_root(json, "deposits", MAX_DEPOSIT_REQUESTS_PER_PAYLOAD, |request|
+            get_container_root(vec![
+                get_bytes_root(request, "pubkey"),
+                get_bytes_root(request, "withdrawal_credentials"),
+                get_u64_root(request, "amount"),
+                get_bytes_root(request, "signature"),
+                get_u64_root(request, "index"),
+            ])
+        ),
+        get_list_root(json, "withdrawals", MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD, |request|
+            get_container_root(vec![
+                get_bytes_root(request, "source_address"),
+                get_bytes_root(request, "validator_pubkey"),
+                get_u64_root(request, "amount"),
+            ])
+        ),
+        get_list_root(json, "consolidations", MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD, |request|
+            get_container_root(vec![
+                get_bytes_root(request, "source_address"),
+                get_bytes_root(request, "source_pubkey"),
+                get_bytes_root(request, "target_pubkey"),
+            ])
+        ),
+    ])
+}
+
+pub fn get_transactions_root(json: &Json) -> Result<H256> {
+    get_list_root(json, "transactions", MAX_TRANSACTIONS_PER_PAYLOAD, |transaction|
+        convert_json_to_bytes(transaction)
+            .and_then(|bytes| hash_tree_root_byte_list(&bytes, MAX_BYTES_PER_TRANSACTION))
+    )
+}
+
+fn get_execution_payload_or_header_field_roots(
+    json: &Json,
+    fork: BeaconFork,
+    is_header: bool,
+) -> Result<Vec<H256>> {
+    let mut field_roots = vec![
+        get_bytes_root(json, "parent_hash"),
+        get_bytes_root(json, "fee_recipient"),
+        get_bytes_root(json, "state_root"),
+        get_bytes_root(json, "receipts_root"),
+        get_bytes_root(json, "logs_bloom"),
+        get_bytes_root(json, "prev_randao"),
+        get_u64_root(json, "block_number"),
+        get_u64_root(json, "gas_limit"),
+        get_u64_root(json, "gas_used"),
+        get_u64_root(json, "timestamp"),
+        get_bytes(json, "extra_data")
+            .and_then(|bytes| hash_tree_root_byte_list(&bytes, MAX_EXTRA_DATA_BYTES)),
+        get_str(json, "base_fee_per_gas")
+            .and_then(|decimal| U256::from_dec_str(decimal).map_err(|_|
+                AppError::Custom("✘ Cannot parse `base_fee_per_gas`!".to_string())
+            ))
+            .map(|base_fee_per_gas| hash_tree_root_u256(&base_fee_per_gas)),
+        get_bytes_root(json, "block_hash"),
+        match is_header {
+            true => get_bytes_root(json, "transactions_root"),
+            false => get_transactions_root(json),
+        },
+    ];
+    if fork >= BeaconFork::Capella {
+        field_roots.push(
+            match is_header {
+                true => get_bytes_root(json, "withdrawals_root"),
+                false => get_list_root(
+                    json,
+                    "withdrawals",
+                    MAX_WITHDRAWALS_PER_PAYLOAD,
+                    get_withdrawal_root,
+                ),
+            }
+        );
+    }
+    if fork >= BeaconFork::Deneb {
+        field_roots.push(get_u64_root(json, "blob_gas_used"));
+        field_roots.push(get_u64_root(json, "excess_blob_gas"));
+    }
+    field_roots.into_iter().collect()
+}
+
+fn get_execution_payload_field_roots(
+    json: &Json,
+    fork: BeaconFork,
+) -> Result<Vec<H256>> {
+    get_execution_payload_or_header_field_roots(json, fork, false)
+}
+
+pub fn get_execution_payload_header_field_roots(
+    json: &Json,
+    fork: BeaconFork,
+) -> Result<Vec<H256>> {
+    get_execution_payload_or_header_field_roots(json, fork, true)
+}
+
+fn get_body_field_roots(
+    json: &Json,
+    fork: BeaconFork,
+    execution_payload_root: H256,
+) -> Result<Vec<H256>> {
+    let (max_attester_slashings, max_attestations) = match fork {
+        BeaconFork::Electra | BeaconFork::Fulu => (MAX_ATTESTER_SLASHINGS_ELECTRA, MAX_ATTESTATIONS_ELECTRA),
+        _ => (MAX_ATTESTER_SLASHINGS, MAX_ATTESTATIONS),
+    };
+    let mut field_roots = vec![
+        get_bytes_root(json, "randao_reveal"),
+        get_field(json, "eth1_data").and_then(get_eth1_data_root),
+        get_bytes_root(json, "graffiti"),
+        get_list_root(json, "proposer_slashings", MAX_PROPOSER_SLASHINGS, get_proposer_slashing_root),
+        get_list_root(json, "attester_slashings", max_attester_slashings, |slashing|
+            get_attester_slashing_root(slashing, fork)
+        ),
+        get_list_root(json, "attestations", max_attestations, |attestation|
+            get_attestation_root(attestation, fork)
+        ),
+        get_list_root(json, "deposits", MAX_DEPOSITS, get_deposit_root),
+        get_list_root(json, "voluntary_exits", MAX_VOLUNTARY_EXITS, get_signed_voluntary_exit_root),
+        get_field(json, "sync_aggregate").and_then(get_sync_aggregate_root),
+        Ok(execution_payload_root),
+    ];
+    if fork >= BeaconFork::Capella {
+        field_roots.push(
+            get_list_root(
+                json,
+                "bls_to_execution_changes",
+                MAX_BLS_TO_EXECUTION_CHANGES,
+                get_signed_bls_to_execution_change_root,
+            )
+        );
+    }
+    if fork >= BeaconFork::Deneb {
+        field_roots.push(
+            get_list_root(
+                json,
+                "blob_kzg_commitments",
+                MAX_BLOB_COMMITMENTS_PER_BLOCK,
+                convert_json_to_bytes_root,
+            )
+        );
+    }
+    if fork >= BeaconFork::Electra {
+        field_roots.push(
+            get_field(json, "execution_requests").and_then(get_execution_requests_root)
+        );
+    }
+    field_roots.into_iter().collect()
+}
+
+fn get_payload_field_branch_and_gindex(
+    payload_field_roots: &[H256],
+    body_field_roots: &[H256],
+    header_field_roots: &[H256],
+    payload_field_index: usize,
+) -> (Vec<H256>, u64) {
+    let payload_depth = get_depth(payload_field_roots.len());
+    let body_depth = get_depth(body_field_roots.len());
+    let header_depth = get_depth(NUM_HEADER_FIELDS);
+    let mut branch = get_merkle_branch(payload_field_roots, payload_field_index, payload_depth);
+    branch.extend(get_merkle_branch(body_field_roots, EXECUTION_PAYLOAD_BODY_INDEX, body_depth));
+    branch.extend(get_merkle_branch(header_field_roots, BODY_ROOT_HEADER_INDEX, header_depth));
+    let gindex = ((((1 << header_depth | BODY_ROOT_HEADER_INDEX as u64) << body_depth)
+        | EXECUTION_PAYLOAD_BODY_INDEX as u64) << payload_depth)
+        | payload_field_index as u64;
+    (branch, gindex)
+}
+
+pub fn generate_execution_payload_proof(
+    beacon_block: &Json,
+) -> Result<ExecutionPayloadProof> {
+    let fork = BeaconFork::from_version(get_str(beacon_block, "version")?)?;
+    let message = get_field(get_field(beacon_block, "data")?, "message")?;
+    let body = get_field(message, "body")?;
+    let payload = get_field(body, "execution_payload")?;
+    let payload_field_roots = get_execution_payload_field_roots(payload, fork)?;
+    let body_field_roots = get_body_field_roots(
+        body,
+        fork,
+        hash_tree_root_container(&payload_field_roots)?,
+    )?;
+    let beacon_block_header = BeaconBlockHeader {
+        slot: get_u64(message, "slot")?,
+        proposer_index: get_u64(message, "proposer_index")?,
+        parent_root: get_h256(message, "parent_root")?,
+        state_root: get_h256(message, "state_root")?,
+        body_root: hash_tree_root_container(&body_field_roots)?,
+    };
+    let header_field_roots = beacon_block_header.get_field_roots();
+    let (block_hash_branch, block_hash_gindex) = get_payload_field_branch_and_gindex(
+        &payload_field_roots,
+        &body_field_roots,
+        &header_field_roots,
+        BLOCK_HASH_PAYLOAD_INDEX,
+    );
+    let (receipts_root_branch, receipts_root_gindex) = get_payload_field_branch_and_gindex(
+        &payload_field_roots,
+        &body_field_roots,
+        &header_field_roots,
+        RECEIPTS_ROOT_PAYLOAD_INDEX,
+    );
+    Ok(
+        ExecutionPayloadProof {
+            block_hash_gindex,
+            block_hash_branch,
+            receipts_root_gindex,
+            receipts_root_branch,
+            beacon_block_root: beacon_block_header.hash_tree_root()?,
+            beacon_block_header,
+            receipts_root: payload_field_roots[RECEIPTS_ROOT_PAYLOAD_INDEX],
+            execution_block_hash: payload_field_roots[BLOCK_HASH_PAYLOAD_INDEX],
+        }
+    )
+}
+
+pub fn get_beacon_block_json(beacon_endpoint: &str, block_id: &str) -> Result<Json> {
+    info!("✔ Getting beacon block {}...", block_id);
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(REQWEST_TIMEOUT_TIME))
+        .build()?;
+    let mut response = client
+        .get(&format!("{}/eth/v2/beacon/blocks/{}", beacon_endpoint, block_id))
+        .header("Accept", "application/json")
+        .send()?;
+    match response.status().is_success() {
+        true => Ok(serde_json::from_str(&response.text()?)?),
+        false => Err(AppError::Custom(
+            format!("✘ Beacon node returned {} for block {}!", response.status(), block_id)
+        ))
+    }
+}
+
+pub fn generate_execution_payload_proof_for_block(
+    beacon_endpoint: &str,
+    block: &Block,
+    genesis_time: u64,
+) -> Result<ExecutionPayloadProof> {
+    let slot = get_slot_from_timestamp(block.timestamp.low_u64(), genesis_time);
+    get_beacon_block_json(beacon_endpoint, &slot.to_string())
+        .and_then(|beacon_block| generate_execution_payload_proof(&beacon_block))
+        .and_then(|proof| {
+            proof.check_matches_block(block)?;
+            Ok(proof)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use crate::ssz::get_zero_hashes;
+    use crate::test_utils::{
+        get_expected_block_1,
+        get_sample_mock_node,
+        SAMPLE_BEACON_BLOCK_JSON_PATH,
+    };
+
+    fn get_sample_beacon_block() -> Json {
+        serde_json::from_str(&fs::read_to_string(SAMPLE_BEACON_BLOCK_JSON_PATH).unwrap())
+            .unwrap()
+    }
+
+    fn get_empty_list_root(max_length: usize) -> H256 {
+        hash_tree_root_list_of_roots(&[], max_length).unwrap()
+    }
+
+    #[test]
+    fn should_get_mainnet_genesis_beacon_block_root() {
+        let expected_result = convert_hex_to_h256(
+            "0x4d611d5b93fdab69013a7f0a2f961caca0c853f87cfe9595fe50038163079360"
+                .to_string()
+        ).unwrap();
+        let zero_hash = get_zero_hashes(0)[0];
+        let empty_body_root = hash_tree_root_container(&[
+            hash_tree_root_byte_vector(&[0u8; 96]).unwrap(),
+            hash_tree_root_container(&[zero_hash; 3]).unwrap(),
+            zero_hash,
+            get_empty_list_root(MAX_PROPOSER_SLASHINGS),
+            get_empty_list_root(MAX_ATTESTER_SLASHINGS),
+            get_empty_list_root(MAX_ATTESTATIONS),
+            get_empty_list_root(MAX_DEPOSITS),
+            get_empty_list_root(MAX_VOLUNTARY_EXITS),
+        ]).unwrap();
+        let genesis_header = BeaconBlockHeader {
+            slot: 0,
+            proposer_index: 0,
+            parent_root: zero_hash,
+            state_root: convert_hex_to_h256(
+                "0x7e76880eb67bbdc86250aa578958e9d0675e64e714337855204fb5abaaf82c2b"
+                    .to_string()
+            ).unwrap(),
+            body_root: empty_body_root,
+        };
+        let result = genesis_header.hash_tree_root().unwrap();
+        assert!(result == expected_result);
+    }
+
+    #[test]
+    fn should_get_fork_from_version() {
+        assert!(BeaconFork::from_version("deneb").unwrap() == BeaconFork::Deneb);
+        assert!(BeaconFork::from_version("electra").unwrap() == BeaconFork::Electra);
+        assert!(BeaconFork::from_version("fulu").unwrap() == BeaconFork::Fulu);
+        assert!(BeaconFork::Fulu > BeaconFork::Electra);
+        match BeaconFork::from_version("altair") {
+            Err(AppError::Custom(e)) => assert!(e.contains("no execution payload")),
+            _ => panic!("Altair blocks should not be accepted!"),
+        }
+    }
+
+    #[test]
+    fn should_get_index_from_gindex() {
+        assert!(get_index_from_gindex(1).unwrap() == 0);
+        assert!(get_index_from_gindex(12).unwrap() == 4);
+        assert!(get_index_from_gindex(0b1_1001_0010_0011).unwrap() == 0b1001_0010_0011);
+    }
+
+    #[test]
+    fn should_err_on_gindex_0() {
+        match get_index_from_gindex(0) {
+            Err(AppError::Custom(e)) => assert!(e.contains("Generalized index 0")),
+            _ => panic!("Generalized index 0 should not be accepted!"),
+        }
+    }
+
+    #[test]
+    fn should_generate_valid_execution_payload_proof() {
+        let result = generate_execution_payload_proof(&get_sample_beacon_block())
+            .unwrap();
+        let expected_block = get_expected_block_1();
+        assert!(result.is_valid());
+        assert!(result.block_hash_branch.len() == 5 + 4 + 3);
+        assert!(result.receipts_root_gindex == 0b1_1001_0010_0011);
+        assert!(result.block_hash_gindex == 0b1_1001_0010_1100);
+        result.check_matches_block(&expected_block).unwrap();
+    }
+
+    #[test]
+    fn should_not_validate_proof_with_wrong_receipts_root() {
+        let mut result = generate_execution_payload_proof(&get_sample_beacon_block())
+            .unwrap();
+        result.receipts_root = H256::zero();
+        assert!(!result.is_valid());
+    }
+
+    #[test]
+    fn should_not_validate_proof_with_gindex_0() {
+        let mut result = generate_execution_payload_proof(&get_sample_beacon_block())
+            .unwrap();
+        result.block_hash_gindex = 0;
+        assert!(!result.is_valid());
+    }
+
+    #[test]
+    fn beacon_block_root_should_change_with_any_body_field() {
+        let sample_block = get_sample_beacon_block();
+        let mut altered_block = sample_block.clone();
+        altered_block["data"]["message"]["body"]["attestations"][0]["data"]["index"] =
+            json!("7");
+        let result_1 = generate_execution_payload_proof(&sample_block).unwrap();
+        let result_2 = generate_execution_payload_proof(&altered_block).unwrap();
+        assert!(result_1.beacon_block_root != result_2.beacon_block_root);
+        assert!(result_2.is_valid());
+    }
+
+    #[test]
+    fn should_fail_to_match_different_execution_block() {
+        let proof = generate_execution_payload_proof(&get_sample_beacon_block())
+            .unwrap();
+        let mut block = get_expected_block_1();
+        block.hash = H256::zero();
+        match proof.check_matches_block(&block) {
+            Err(AppError::Custom(e)) => assert!(e.contains("is not block")),
+            _ => panic!("Proof should not match a different block!"),
+        }
+    }
+
+    #[test]
+    fn should_generate_execution_payload_proof_for_block_via_mock_node() {
+        let mock_node = get_sample_mock_node();
+        let block = get_expected_block_1();
+        let slot = get_u64(&get_sample_beacon_block()["data"]["message"], "slot")
+            .unwrap();
+        let genesis_time = block.timestamp.low_u64() - slot * SECONDS_PER_SLOT;
+        let result = generate_execution_payload_proof_for_block(
+            &mock_node.get_endpoint(),
+            &block,
+            genesis_time,
+        ).unwrap();
+        assert!(result.beacon_block_header.slot == slot);
+        assert!(result.is_valid());
+    }
+}
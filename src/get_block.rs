@@ -1,6 +1,9 @@
-use crate::state::State;
 use serde_json::Value as Json;
-use crate::get_receipts::get_receipt_from_tx_hash;
+use crate::rpc_cache::{
+    RpcCache,
+    CacheKind,
+    get_response_text_via_cache,
+};
 use ethereum_types::{
     H256,
     Bloom,
@@ -19,6 +22,7 @@ use crate::types::{
     Result,
     BlockJson,
 };
+use crate::constants::BLOCK_TAGS;
 use crate::utils::{
     convert_hex_to_u256,
     convert_hex_to_h256,
@@ -58,10 +62,30 @@ pub fn deserialize_block_json_to_block_struct(
             size: convert_hex_to_u256(block_json.size)?,
             state_root: convert_hex_to_h256(block_json.stateRoot)?,
             timestamp: convert_hex_to_u256(block_json.timestamp)?,
-            total_difficulty: convert_hex_to_u256(block_json.totalDifficulty)?,
+            total_difficulty: block_json.totalDifficulty
+                .map(convert_hex_to_u256)
+                .transpose()?,
             transactions: convert_hex_strings_to_h256s(block_json.transactions)?,
             transactions_root: convert_hex_to_h256(block_json.transactionsRoot)?,
             uncles: convert_hex_strings_to_h256s(block_json.uncles)?,
+            base_fee_per_gas: block_json.baseFeePerGas
+                .map(convert_hex_to_u256)
+                .transpose()?,
+            withdrawals_root: block_json.withdrawalsRoot
+                .map(convert_hex_to_h256)
+                .transpose()?,
+            blob_gas_used: block_json.blobGasUsed
+                .map(convert_hex_to_u256)
+                .transpose()?,
+            excess_blob_gas: block_json.excessBlobGas
+                .map(convert_hex_to_u256)
+                .transpose()?,
+            parent_beacon_block_root: block_json.parentBeaconBlockRoot
+                .map(convert_hex_to_h256)
+                .transpose()?,
+            requests_hash: block_json.requestsHash
+                .map(convert_hex_to_h256)
+                .transpose()?,
         }
     )
 }
@@ -75,15 +99,26 @@ fn get_block(endpoint: &str, rpc_json: Json) -> Result<Block> {
 
 pub fn get_block_by_blockhash(
     endpoint: &str,
-    block_hash: H256
+    block_hash: H256,
+    maybe_cache: Option<&RpcCache>,
 ) -> Result<Block> {
     get_block_by_block_hash_json(convert_h256_to_prefixed_hex(block_hash)?)
-        .and_then(|json| get_block(endpoint, json))
+        .and_then(|json|
+            get_response_text_via_cache(
+                endpoint,
+                json,
+                maybe_cache,
+                CacheKind::Block,
+                block_hash,
+            )
+        )
+        .and_then(deserialize_to_block_rpc_response)
+        .and_then(|res| deserialize_block_json_to_block_struct(res.result))
 }
 
 pub fn get_block_by_number(endpoint: &str, block_num: &str) -> Result<Block> {
     let num_hex: String;
-    if block_num == "latest" {
+    if BLOCK_TAGS.contains(&block_num) {
         num_hex = block_num.to_string();
     } else {
         num_hex = convert_num_to_prefixed_hex(
@@ -94,19 +129,15 @@ pub fn get_block_by_number(endpoint: &str, block_num: &str) -> Result<Block> {
         .and_then(|json| get_block(endpoint, json))
 }
 
-fn add_block_to_state(state: State, block: Block) -> Result<State> {
-    Ok(State::set_block_in_state(state, block)?)
-}
-
-pub fn get_block_from_tx_hash_in_state_and_set_in_state(
-    state: State
-) -> Result<State> {
-    info!("✔ Getting block from transaction hash: {}", state.tx_hash);
-    let endpoint = &State::get_endpoint_from_state(&state)?;
-    let tx_hash = &convert_h256_to_prefixed_hex(state.tx_hash)?;
-    get_receipt_from_tx_hash(endpoint, tx_hash)
-        .and_then(|receipt| get_block_by_blockhash(endpoint, receipt.block_hash))
-        .and_then(|block| add_block_to_state(state, block))
+pub fn get_block_by_block_id(endpoint: &str, block_id: &str) -> Result<Block> {
+    match block_id.len() == 66 && block_id.starts_with("0x") {
+        true => get_block_by_blockhash(
+            endpoint,
+            convert_hex_to_h256(block_id.to_string())?,
+            None,
+        ),
+        false => get_block_by_number(endpoint, block_id),
+    }
 }
 
 #[cfg(test)]
@@ -114,12 +145,11 @@ mod tests {
     use std::fs;
     use super::*;
     use crate::test_utils::{
-        WORKING_ENDPOINT,
         SAMPLE_BLOCK_HASH,
+        get_sample_mock_node,
         SAMPLE_BLOCK_JSON_PATH,
         assert_block_is_correct,
         get_valid_block_hash_h256,
-        get_valid_state_with_endpoint,
     };
 
     #[test]
@@ -134,57 +164,68 @@ mod tests {
         assert_block_is_correct(result)
     }
 
+    #[test]
+    fn should_deserialize_block_json_without_total_difficulty() {
+        let mut json: Json = fs::read_to_string(SAMPLE_BLOCK_JSON_PATH)
+            .map(|string| serde_json::from_str(&string).unwrap())
+            .unwrap();
+        json["result"]
+            .as_object_mut()
+            .unwrap()
+            .remove("totalDifficulty");
+        let result = deserialize_to_block_rpc_response(json.to_string())
+            .and_then(|res| deserialize_block_json_to_block_struct(res.result))
+            .unwrap();
+        assert!(result.total_difficulty.is_none());
+        assert!(result.hash == get_valid_block_hash_h256().unwrap());
+    }
+
     #[test]
     fn should_get_block_by_block_hash() {
+        let mock_node = get_sample_mock_node();
         let result = get_block_by_blockhash(
-            WORKING_ENDPOINT,
-            get_valid_block_hash_h256().unwrap()
+            &mock_node.get_endpoint(),
+            get_valid_block_hash_h256().unwrap(),
+            None,
         ).unwrap();
         assert_block_is_correct(result);
     }
 
     #[test]
     fn should_get_block_by_block_number() {
+        let mock_node = get_sample_mock_node();
         let num_str = "8233333";
         let result = get_block_by_number(
-            WORKING_ENDPOINT,
+            &mock_node.get_endpoint(),
             num_str
         ).unwrap();
         assert_block_is_correct(result);
     }
 
+    #[test]
+    fn should_get_block_by_block_id() {
+        let mock_node = get_sample_mock_node();
+        let result_1 = get_block_by_block_id(
+            &mock_node.get_endpoint(),
+            SAMPLE_BLOCK_HASH,
+        ).unwrap();
+        let result_2 = get_block_by_block_id(
+            &mock_node.get_endpoint(),
+            "8233333",
+        ).unwrap();
+        assert_block_is_correct(result_1);
+        assert_block_is_correct(result_2);
+    }
+
     #[test]
     fn should_get_block() {
+        let mock_node = get_sample_mock_node();
         let reqwest_json = get_block_by_block_hash_json(
             SAMPLE_BLOCK_HASH.to_string()
         ).unwrap();
         let result = get_block(
-            WORKING_ENDPOINT,
+            &mock_node.get_endpoint(),
             reqwest_json
         ).unwrap();
         assert_block_is_correct(result);
-    }
-
-    #[test]
-    fn should_add_block_to_state() {
-        let num_str = "8233333";
-        let block = get_block_by_number(
-            WORKING_ENDPOINT,
-            num_str
-        ).unwrap();
-        let initial_state = get_valid_state_with_endpoint().unwrap();
-        let resultant_state = add_block_to_state(initial_state, block).unwrap();
-        let result = State::get_block_from_state(&resultant_state).unwrap();
-        assert_block_is_correct(result.clone());
-    }
-
-    #[test]
-    fn should_get_block_from_tx_hash_in_state() {
-        let initial_state = get_valid_state_with_endpoint().unwrap();
-        let resultant_state = get_block_from_tx_hash_in_state_and_set_in_state(
-            initial_state
-        ).unwrap();
-        let result = State::get_block_from_state(&resultant_state).unwrap();
-        assert_block_is_correct(result.clone())
-    }
-}
+    }}
@@ -1,6 +1,5 @@
 use hex;
-use crate::trie::Trie;
-use crate::state::State;
+use crate::patricia_trie::Trie;
 use crate::errors::AppError;
 use crate::nibble_utils::Nibbles;
 use crate::utils::convert_hex_to_u256;
@@ -19,15 +18,12 @@ fn convert_usize_index_to_trie_key(index: usize) -> Result<Nibbles> {
         .map(get_nibbles_from_bytes)
 }
 
-pub fn get_branch_from_trie(
-    receipts_trie: Trie,
-    index: usize,
-) -> Result<NodeStack> {
+pub fn find_branch_in_trie(receipts_trie: Trie, index: usize) -> Result<(Trie, NodeStack)> {
     receipts_trie
         .find(convert_usize_index_to_trie_key(index)?)
-        .and_then(|(_, _, found_stack, remaining_key)| {
+        .and_then(|(trie, _, found_stack, remaining_key)| {
             match remaining_key.len() {
-                0 => Ok(found_stack),
+                0 => Ok((trie, found_stack)),
                 _ => Err(AppError::Custom(
                     format!("✘ Error! No receipt in trie at given index: {}", index)
                 ))
@@ -35,13 +31,27 @@ pub fn get_branch_from_trie(
         })
 }
 
-pub fn get_branch_from_trie_and_put_in_state(state: State) -> Result<State> {
-    info!("✔ Pulling branch from trie...");
-    get_branch_from_trie(
-        state.get_receipts_trie_from_state()?.clone(),
-        state.get_index_from_state()?.clone()
-    )
-        .and_then(|branch| state.set_branch_in_state(branch))
+pub fn get_branch_from_trie(
+    receipts_trie: Trie,
+    index: usize,
+) -> Result<NodeStack> {
+    find_branch_in_trie(receipts_trie, index)
+        .map(|(_, branch)| branch)
+}
+
+pub fn get_branches_from_trie(
+    receipts_trie: Trie,
+    num_receipts: usize,
+) -> Result<Vec<NodeStack>> {
+    (0..num_receipts)
+        .try_fold((receipts_trie, Vec::new()), |(trie, mut branches), index|
+            find_branch_in_trie(trie, index)
+                .map(|(trie, branch)| {
+                    branches.push(branch);
+                    (trie, branches)
+                })
+        )
+        .map(|(_, branches)| branches)
 }
 
 #[cfg(test)]
@@ -51,7 +61,6 @@ mod tests {
         get_sample_tx_hashes_1,
         SAMPLE_RECEIPT_JSONS_1_PATH,
         get_sample_trie_with_sample_receipts,
-        get_valid_state_with_receipts_trie_and_index
     };
 
     #[test]
@@ -90,33 +99,16 @@ mod tests {
             _ => panic!("Getting branch should not have succeeded!")
         }
     }
-
     #[test]
-    fn should_get_branch_and_put_in_state() {
+    fn should_get_all_branches_from_trie() {
+        let num_receipts = get_sample_tx_hashes_1().len();
         let trie = get_sample_trie_with_sample_receipts(
             SAMPLE_RECEIPT_JSONS_1_PATH.to_string(),
             get_sample_tx_hashes_1()
         );
-        let state_before = get_valid_state_with_receipts_trie_and_index(
-            SAMPLE_RECEIPT_JSONS_1_PATH.to_string(),
-            get_sample_tx_hashes_1()
-        ).unwrap();
-        let index = state_before.get_index_from_state()
-            .unwrap();
-        let expected_branch = get_branch_from_trie(trie, *index)
+        let result = get_branches_from_trie(trie.clone(), num_receipts)
             .unwrap();
-        if let Ok(_) = state_before.get_branch_from_state() {
-            panic!("Should not have branch in state yet!")
-        };
-        let state_after = get_branch_from_trie_and_put_in_state(state_before)
-            .unwrap();
-        match state_after.get_branch_from_state() {
-            Err(_) => panic!("Should have branch in state now!"),
-            Ok(branch) => {
-                for i in 0..branch.len() {
-                    assert!(branch[i] == expected_branch[i]);
-                }
-            }
-        };
+        assert!(result.len() == num_receipts);
+        assert!(result[14] == get_branch_from_trie(trie, 14).unwrap());
     }
 }
@@ -0,0 +1,81 @@
+use std::fs;
+use std::path::Path;
+use crate::types::Result;
+use crate::errors::AppError;
+use crate::constants::CONFIG_FILE_PATH;
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    pub endpoint: Option<String>,
+    pub output: Option<String>,
+    pub block_tag: Option<String>,
+    pub confirmations: Option<u64>,
+}
+
+fn read_config_file(path: &str) -> Result<Config> {
+    fs::read_to_string(path)
+        .map_err(|e| AppError::Custom(
+            format!("✘ Cannot read config file at {}!\n✘ {}", path, e)
+        ))
+        .and_then(|contents| Ok(serde_json::from_str(&contents)?))
+}
+
+pub fn get_config(maybe_config_path: Option<String>) -> Result<Config> {
+    match maybe_config_path {
+        Some(path) => {
+            info!("✔ Reading config file at {}...", path);
+            read_config_file(&path)
+        },
+        None => match Path::new(CONFIG_FILE_PATH).exists() {
+            true => {
+                info!("✔ Reading config file at {}...", CONFIG_FILE_PATH);
+                read_config_file(CONFIG_FILE_PATH)
+            },
+            false => Ok(Config::default()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::get_temp_cache_dir;
+
+    fn write_config_file(name: &str, contents: &str) -> String {
+        let dir = get_temp_cache_dir(name);
+        fs::create_dir_all(&dir).unwrap();
+        let path = format!("{}/config.json", dir);
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn should_get_config_from_file() {
+        let path = write_config_file(
+            "config-file",
+            "{\"endpoint\":\"http://localhost:8545\",\"confirmations\":12}",
+        );
+        let result = get_config(Some(path)).unwrap();
+        assert!(result.endpoint == Some("http://localhost:8545".to_string()));
+        assert!(result.confirmations == Some(12));
+        assert!(result.output.is_none());
+    }
+
+    #[test]
+    fn should_err_if_given_config_file_does_not_exist() {
+        match get_config(Some("./no-such-config.json".to_string())) {
+            Err(AppError::Custom(e)) => assert!(e.contains("Cannot read config file")),
+            _ => panic!("Missing config file should not be accepted!"),
+        }
+    }
+
+    #[test]
+    fn should_err_on_unknown_config_field() {
+        let path = write_config_file("config-unknown-field", "{\"endpiont\":\"x\"}");
+        match get_config(Some(path)) {
+            Err(AppError::SerdeJsonError(_)) => assert!(true),
+            _ => panic!("Config with unknown field should not be accepted!"),
+        }
+    }
+}
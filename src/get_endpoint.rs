@@ -1,5 +1,4 @@
 use dotenv;
-use crate::state::State;
 use crate::types::Result;
 use crate::errors::AppError;
 use crate::utils::dot_env_file_exists;
@@ -21,23 +20,28 @@ fn maybe_run_dot_env() -> Result<()> {
     }
 }
 
-fn get_endpoint_from_env_vars() -> Result<String> {
+fn get_endpoint_from_env_vars() -> Result<Option<String>> {
+    // NOTE: `dotenv` never overwrites a variable that's already set, so a real
+    // `ENDPOINT` env var takes precedence over the one in the `.env` file.
     maybe_run_dot_env()
-        .and_then(|_|
-            match std::env::var("ENDPOINT") {
-                Ok(endpoint) => Ok(endpoint),
-                Err(_) => Ok(DEFAULT_ENDPOINT.to_string())
-            }
-    )
+        .map(|_| std::env::var("ENDPOINT").ok())
 }
 
-pub fn get_endpoint_and_set_in_state(state: State) -> Result<State> {
-    info!("✔ Getting RPC endpoint from environment variables...");
-    get_endpoint_from_env_vars()
-        .and_then(|endpoint| {
-            info!("✔ Endpoint retrieved: {}", endpoint);
-            State::set_endpoint_in_state(state, endpoint)
-        })
+pub fn get_endpoint(
+    maybe_cli_endpoint: Option<String>,
+    maybe_config_endpoint: Option<String>,
+) -> Result<String> {
+    info!("✔ Getting RPC endpoint...");
+    let endpoint = match maybe_cli_endpoint {
+        Some(endpoint) => endpoint,
+        None => match get_endpoint_from_env_vars()? {
+            Some(endpoint) => endpoint,
+            None => maybe_config_endpoint
+                .unwrap_or_else(|| DEFAULT_ENDPOINT.to_string()),
+        }
+    };
+    info!("✔ Endpoint retrieved: {}", endpoint);
+    Ok(endpoint)
 }
 
 #[cfg(test)]
@@ -50,7 +54,6 @@ mod tests {
         write_env_file,
         delete_env_file,
         restore_env_file,
-        get_valid_initial_state,
     };
 
     #[test]
@@ -135,10 +138,10 @@ mod tests {
 
     #[test]
     #[serial]
-    fn should_get_default_endpoint_correctly_if_no_env_file_exists() {
-        if !dot_env_file_exists() {
+    fn should_get_no_endpoint_from_env_vars_if_no_env_file_exists() {
+        if !dot_env_file_exists() && std::env::var("ENDPOINT").is_err() {
             let result = get_endpoint_from_env_vars().unwrap();
-            assert!(result == DEFAULT_ENDPOINT);
+            assert!(result.is_none());
             assert!(!dot_env_file_exists());
         }
     }
@@ -148,50 +151,49 @@ mod tests {
     fn should_get_endpoint_from_env_file_if_extant_correctly() {
         if dot_env_file_exists() {
             let file = read_env_file().unwrap();
-            let result = get_endpoint_from_env_vars().unwrap();
+            let result = get_endpoint_from_env_vars().unwrap().unwrap();
             assert!(file.contains(&result));
         }
     }
 
     #[test]
     #[serial]
-    fn should_get_default_endpoint_and_set_in_state_if_no_env_file() {
-        if !dot_env_file_exists() {
-            let expected_err = "No endpoint in state";
-            let initial_state = get_valid_initial_state()
-                .unwrap();
-            match State::get_endpoint_from_state(&initial_state) {
-                Err(AppError::Custom(e)) => assert!(e.contains(expected_err)),
-                _ => panic!("State should not have endpoint yet!")
-            }
-            let result_state = get_endpoint_and_set_in_state(initial_state)
-                .unwrap();
-            match State::get_endpoint_from_state(&result_state) {
-                Ok(endpoint) => assert!(endpoint == DEFAULT_ENDPOINT),
-                _ => panic!("Default endpoint should be set in state!")
-            }
+    fn should_get_default_endpoint_if_none_configured() {
+        if !dot_env_file_exists() && std::env::var("ENDPOINT").is_err() {
+            let result = get_endpoint(None, None).unwrap();
+            assert!(result == DEFAULT_ENDPOINT);
         }
     }
 
     #[test]
     #[serial]
-    fn should_get_custom_endpoint_and_set_in_state_if_env_file() {
+    fn should_get_config_endpoint_if_no_env_endpoint() {
+        if !dot_env_file_exists() && std::env::var("ENDPOINT").is_err() {
+            let config_endpoint = "http://config-endpoint:8545".to_string();
+            let result = get_endpoint(None, Some(config_endpoint.clone())).unwrap();
+            assert!(result == config_endpoint);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn should_get_env_file_endpoint_over_config_endpoint() {
         if dot_env_file_exists() {
-            let expected_err = "No endpoint in state";
-            let initial_state = get_valid_initial_state()
-                .unwrap();
-            match State::get_endpoint_from_state(&initial_state) {
-                Err(AppError::Custom(e)) => assert!(e.contains(expected_err)),
-                _ => panic!("State should not have endpoint yet!")
-            }
-            let file = read_env_file()
-                .unwrap();
-            let result_state = get_endpoint_and_set_in_state(initial_state)
+            let file = read_env_file().unwrap();
+            let result = get_endpoint(None, Some("http://config-endpoint:8545".to_string()))
                 .unwrap();
-            match State::get_endpoint_from_state(&result_state) {
-                Ok(endpoint) => assert!(file.contains(&endpoint)),
-                _ => panic!("Custom endpoint should be set in state!")
-            }
+            assert!(file.contains(&result));
         }
     }
+
+    #[test]
+    #[serial]
+    fn should_get_cli_endpoint_over_all_others() {
+        let cli_endpoint = "http://cli-endpoint:8545".to_string();
+        let result = get_endpoint(
+            Some(cli_endpoint.clone()),
+            Some("http://config-endpoint:8545".to_string()),
+        ).unwrap();
+        assert!(result == cli_endpoint);
+    }
 }
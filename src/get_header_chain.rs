@@ -0,0 +1,199 @@
+use rlp;
+use crate::errors::AppError;
+use crate::get_block::get_block_by_number;
+use crate::get_keccak_hash::keccak_hash_bytes;
+use crate::types::{
+    Block,
+    Bytes,
+    Result,
+};
+
+pub fn get_rlp_encoded_header(block: &Block) -> Bytes {
+    rlp::encode(block)
+}
+
+pub fn get_verified_rlp_encoded_header(block: &Block) -> Result<Bytes> {
+    let header = get_rlp_encoded_header(block);
+    keccak_hash_bytes(&header)
+        .and_then(|hash| match hash == block.hash {
+            true => Ok(header),
+            false => Err(AppError::Custom(
+                format!(
+                    "✘ Header of block {} hashes to {:?}, not {:?}!",
+                    block.number,
+                    hash,
+                    block.hash,
+                )
+            ))
+        })
+}
+
+fn check_parent_hash_links(blocks: &[Block]) -> Result<()> {
+    blocks
+        .windows(2)
+        .map(|pair| match pair[1].parent_hash == pair[0].hash {
+            true => Ok(()),
+            false => Err(AppError::Custom(
+                format!(
+                    "✘ Parent hash of block {} does not link to block {}!",
+                    pair[1].number,
+                    pair[0].number,
+                )
+            ))
+        })
+        .collect()
+}
+
+fn check_block_range(from_block: u64, to_block: u64) -> Result<()> {
+    match from_block <= to_block {
+        true => Ok(()),
+        false => Err(AppError::Custom(
+            format!(
+                "✘ Cannot make header chain from block {} down to block {}!",
+                from_block,
+                to_block,
+            )
+        ))
+    }
+}
+
+fn get_blocks_in_range(
+    endpoint: &str,
+    from_block: u64,
+    to_block: u64,
+) -> Result<Vec<Block>> {
+    (from_block..=to_block)
+        .map(|block_number|
+            get_block_by_number(endpoint, &block_number.to_string())
+        )
+        .collect()
+}
+
+pub fn generate_header_chain(
+    endpoint: &str,
+    from_block: u64,
+    to_block: u64,
+) -> Result<Vec<Bytes>> {
+    info!(
+        "✔ Generating header chain from block {} to block {}...",
+        from_block,
+        to_block,
+    );
+    check_block_range(from_block, to_block)
+        .and_then(|_| get_blocks_in_range(endpoint, from_block, to_block))
+        .and_then(|blocks| {
+            check_parent_hash_links(&blocks)?;
+            blocks
+                .iter()
+                .map(get_verified_rlp_encoded_header)
+                .collect()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethereum_types::{
+        H256,
+        U256,
+    };
+    use crate::mock_node::{
+        MockNode,
+        MockNodeFixtures,
+    };
+    use crate::test_utils::{
+        get_expected_block,
+        get_synthetic_block_chain,
+    };
+
+    const CHAIN_START: u64 = 0x7da175;
+
+    fn get_synthetic_chain_mock_node(length: usize) -> MockNode {
+        get_synthetic_block_chain(length)
+            .iter()
+            .try_fold(MockNodeFixtures::new(), |fixtures, response|
+                fixtures.add_block_response(response)
+            )
+            .and_then(MockNode::start)
+            .unwrap()
+    }
+
+    #[test]
+    fn should_rlp_encode_header_to_block_hash() {
+        let block = get_expected_block();
+        let result = get_verified_rlp_encoded_header(&block)
+            .unwrap();
+        assert!(keccak_hash_bytes(&result).unwrap() == block.hash);
+    }
+
+    #[test]
+    fn should_append_optional_post_london_fields_to_header() {
+        let block = get_expected_block();
+        let mut london_block = block.clone();
+        london_block.base_fee_per_gas = Some(U256::from(7));
+        let result = get_rlp_encoded_header(&london_block);
+        let expected_item_count = 16;
+        assert!(rlp::Rlp::new(&result).item_count().unwrap() == expected_item_count);
+        assert!(result.len() == get_rlp_encoded_header(&block).len() + 1);
+    }
+
+    #[test]
+    fn should_fail_to_verify_header_with_wrong_hash() {
+        let mut block = get_expected_block();
+        block.hash = H256::zero();
+        match get_verified_rlp_encoded_header(&block) {
+            Err(AppError::Custom(e)) => assert!(e.contains("hashes to")),
+            _ => panic!("Header with wrong hash should not verify!"),
+        }
+    }
+
+    #[test]
+    fn should_generate_header_chain() {
+        let length = 5;
+        let mock_node = get_synthetic_chain_mock_node(length);
+        let result = generate_header_chain(
+            &mock_node.get_endpoint(),
+            CHAIN_START,
+            CHAIN_START + length as u64 - 1,
+        ).unwrap();
+        assert!(result.len() == length);
+        assert!(result[0] == get_rlp_encoded_header(&get_expected_block()));
+    }
+
+    #[test]
+    fn should_fail_to_generate_header_chain_with_broken_link() {
+        let mut chain = get_synthetic_block_chain(3);
+        chain[1] = chain[1].replace(
+            &format!("{:?}", get_expected_block().hash),
+            &format!("{:?}", H256::zero()),
+        );
+        let mock_node = chain
+            .iter()
+            .try_fold(MockNodeFixtures::new(), |fixtures, response|
+                fixtures.add_block_response(response)
+            )
+            .and_then(MockNode::start)
+            .unwrap();
+        match generate_header_chain(
+            &mock_node.get_endpoint(),
+            CHAIN_START,
+            CHAIN_START + 2,
+        ) {
+            Err(AppError::Custom(e)) => assert!(e.contains("does not link")),
+            _ => panic!("Broken header chain should not verify!"),
+        }
+    }
+
+    #[test]
+    fn should_fail_to_generate_header_chain_for_reversed_range() {
+        let mock_node = get_synthetic_chain_mock_node(2);
+        match generate_header_chain(
+            &mock_node.get_endpoint(),
+            CHAIN_START + 1,
+            CHAIN_START,
+        ) {
+            Err(AppError::Custom(e)) => assert!(e.contains("Cannot make")),
+            _ => panic!("Reversed range should not yield a header chain!"),
+        }
+    }
+}
@@ -1,12 +1,12 @@
 use ethereum_types::H256;
-use tiny_keccak::keccak256;
+use receipt_proof_verifier::get_keccak_hash;
 use crate::types::{
     Bytes,
     Result,
 };
 
 pub fn keccak_hash_bytes(bytes: &Bytes) -> Result<H256> {
-    Ok(keccak256(bytes).into())
+    Ok(get_keccak_hash::keccak_hash_bytes(bytes).into())
 }
 
 #[cfg(test)]
@@ -1,11 +1,16 @@
+use crate::errors::AppError;
 use crate::types::{
     Log,
+    Block,
     Result,
+    Receipt,
     LogJson,
     ReceiptJson,
 };
 use ethereum_types::{
+    H256,
     Bloom,
+    Address,
     BloomInput,
 };
 use crate::utils::{
@@ -59,6 +64,63 @@ pub fn get_logs_bloom_from_logs(logs: &Vec<Log>) -> Result<Bloom> {
     )
 }
 
+pub fn bloom_may_contain(bloom: &Bloom, maybe_address: Option<&Address>, topics: &[H256]) -> bool {
+    maybe_address
+        .map(|address| bloom.contains_input(BloomInput::Raw(address.as_bytes())))
+        .unwrap_or(true) &&
+    topics
+        .iter()
+        .all(|topic| bloom.contains_input(BloomInput::Raw(topic.as_bytes())))
+}
+
+pub fn receipt_may_contain(
+    receipt: &Receipt,
+    maybe_address: Option<&Address>,
+    topics: &[H256],
+) -> bool {
+    bloom_may_contain(&receipt.logs_bloom, maybe_address, topics)
+}
+
+pub fn block_may_contain(
+    block: &Block,
+    maybe_address: Option<&Address>,
+    topics: &[H256],
+) -> bool {
+    bloom_may_contain(&block.logs_bloom, maybe_address, topics)
+}
+
+pub fn check_logs_bloom_of_receipt_json(receipt_json: &ReceiptJson, logs: &Vec<Log>) -> Result<Bloom> {
+    let bloom = get_logs_bloom_from_logs(logs)?;
+    match bloom == Bloom::from_slice(&convert_hex_to_bytes(receipt_json.logsBloom.clone())?[..]) {
+        true => Ok(bloom),
+        false => Err(AppError::Custom(
+            format!(
+                "✘ Logs bloom of receipt {} does not match its logs!",
+                receipt_json.transactionHash,
+            )
+        ))
+    }
+}
+
+pub fn get_logs_bloom_from_receipts(receipts: &[Receipt]) -> Bloom {
+    receipts
+        .iter()
+        .fold(Bloom::default(), |mut bloom, receipt| {
+            bloom.accrue_bloom(&receipt.logs_bloom);
+            bloom
+        })
+}
+
+pub fn check_logs_bloom_of_block(block: &Block, receipts: &[Receipt]) -> Result<Bloom> {
+    let bloom = get_logs_bloom_from_receipts(receipts);
+    match bloom == block.logs_bloom {
+        true => Ok(bloom),
+        false => Err(AppError::Custom(
+            format!("✘ Logs bloom of block {} does not match its receipts!", block.number)
+        ))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use hex;
@@ -66,9 +128,13 @@ mod tests {
     use super::*;
     use crate::make_rpc_call::deserialize_to_receipt_rpc_response;
     use crate::test_utils::{
+        get_sample_receipts,
         get_expected_receipt,
+        get_expected_block_1,
         assert_log_is_correct,
+        get_sample_tx_hashes_1,
         SAMPLE_RECEIPT_JSON_PATH,
+        SAMPLE_RECEIPT_JSONS_1_PATH,
     };
 
     #[test]
@@ -110,4 +176,58 @@ mod tests {
         let result = calculate_bloom_from_log(&log);
         assert!(result.as_bytes() == expected_bloom_bytes)
     }
+
+    #[test]
+    fn should_check_bloom_may_contain_address_and_topics() {
+        let receipt = get_expected_receipt();
+        let log = receipt.logs[0].clone();
+        let other_hash = H256::from_slice(&[0x11; 32]);
+        assert!(receipt_may_contain(&receipt, Some(&log.address), &log.topics));
+        assert!(receipt_may_contain(&receipt, None, &log.topics[..1]));
+        assert!(!receipt_may_contain(&receipt, Some(&log.address), &[other_hash]));
+        assert!(!bloom_may_contain(&Bloom::default(), Some(&log.address), &[]));
+        assert!(bloom_may_contain(&Bloom::default(), None, &[]));
+    }
+
+    #[test]
+    fn should_check_block_may_contain_address() {
+        let block = get_expected_block_1();
+        let weth = convert_hex_to_address("0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2".to_string())
+            .unwrap();
+        assert!(block_may_contain(&block, Some(&weth), &[]));
+    }
+
+    #[test]
+    fn should_check_logs_bloom_of_receipt_json() {
+        let receipt_string = fs::read_to_string(SAMPLE_RECEIPT_JSON_PATH)
+            .unwrap();
+        let mut receipt_json = deserialize_to_receipt_rpc_response(receipt_string)
+            .unwrap()
+            .result;
+        let logs = get_logs_from_receipt_json(&receipt_json).unwrap();
+        let result = check_logs_bloom_of_receipt_json(&receipt_json, &logs).unwrap();
+        assert!(result == get_expected_receipt().logs_bloom);
+        receipt_json.logsBloom = format!("0x{}", "00".repeat(256));
+        match check_logs_bloom_of_receipt_json(&receipt_json, &logs) {
+            Err(AppError::Custom(e)) => assert!(e.contains("does not match its logs")),
+            _ => panic!("Mismatched receipt logs bloom should not be accepted!"),
+        }
+    }
+
+    #[test]
+    fn should_check_logs_bloom_of_block() {
+        let mut block = get_expected_block_1();
+        let receipts = get_sample_receipts(
+            SAMPLE_RECEIPT_JSONS_1_PATH.to_string(),
+            get_sample_tx_hashes_1(),
+        );
+        let result = check_logs_bloom_of_block(&block, &receipts).unwrap();
+        assert!(result == block.logs_bloom);
+        block.logs_bloom = Bloom::default();
+        assert!(get_logs_bloom_from_receipts(&receipts) != block.logs_bloom);
+        match check_logs_bloom_of_block(&block, &receipts) {
+            Err(AppError::Custom(e)) => assert!(e.contains("does not match its receipts")),
+            _ => panic!("Mismatched block logs bloom should not be accepted!"),
+        }
+    }
 }
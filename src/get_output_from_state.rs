@@ -0,0 +1,208 @@
+use rlp;
+use crate::state::State;
+use crate::errors::AppError;
+use crate::proof_builder::Proof;
+use crate::molecule::encode_receipt_branch;
+use crate::get_abi_output::get_abi_output;
+use crate::utils::convert_bytes_to_hex;
+use crate::get_hex_proof_from_branch::rlp_encode_node_stack;
+use crate::types::{
+    Bytes,
+    Result,
+    Receipt,
+    OutputFormat,
+};
+
+pub fn get_log_entry_data(receipt: &Receipt, log_index: usize) -> Result<String> {
+    match receipt.logs.get(log_index) {
+        Some(log) => Ok(convert_bytes_to_hex(rlp::encode(log))),
+        None => Err(AppError::Custom(
+            format!(
+                "✘ Log index {} is out of range for a receipt with {} logs!",
+                log_index,
+                receipt.logs.len(),
+            )
+        ))
+    }
+}
+
+fn get_json_output(proof: &Proof, state: &State) -> Result<String> {
+    let mut output = serde_json::to_value(proof.get_receipt_proof()?)?;
+    if let Some(log_index) = state.log_index {
+        output["log_index"] = json!(log_index);
+        output["log_entry_data"] = json!(get_log_entry_data(proof.get_receipt(), log_index)?);
+    }
+    Ok(output.to_string())
+}
+
+pub fn get_output_from_proof(proof: &Proof, state: &State) -> Result<Bytes> {
+    info!("✔ Formatting proof output...");
+    match State::get_output_format_from_state(state)? {
+        OutputFormat::Hex => proof.get_hex_proof()
+            .map(|hex_proof| format!("{}\n", hex_proof).into_bytes()),
+        OutputFormat::Json => get_json_output(proof, state)
+            .map(|json| format!("{}\n", json).into_bytes()),
+        OutputFormat::RlpBin => rlp_encode_node_stack(&proof.branch),
+        OutputFormat::Molecule => encode_receipt_branch(&proof.branch),
+        OutputFormat::Abi => State::get_abi_signature_from_state(state)
+            .and_then(|abi_signature|
+                get_abi_output(
+                    &proof.block,
+                    &proof.branch,
+                    proof.tx_hash,
+                    proof.tx_index,
+                    rlp::encode(proof.get_receipt()),
+                    abi_signature,
+                )
+            )
+            .map(|abi| format!("{}\n", abi).into_bytes()),
+        OutputFormat::Bundle => proof.get_proof_bundle()
+            .and_then(|bundle| bundle.to_json())
+            .map(|bundle| format!("{}\n", bundle).into_bytes()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value as Json;
+    use ethabi::{
+        Token,
+        ParamType,
+    };
+    use crate::types::FinalityRequirement;
+    use crate::decode_proof::get_proof_nodes;
+    use crate::molecule::decode_receipt_branch;
+    use crate::proof_builder::build_proof_from_state;
+    use crate::get_branch_from_trie::get_branch_from_trie;
+    use crate::get_receipts_trie::get_receipts_trie_from_receipts;
+    use crate::get_hex_proof_from_branch::get_hex_proof_from_branch;
+    use crate::utils::{
+        convert_hex_to_h256,
+        convert_hex_to_bytes,
+    };
+    use crate::test_utils::{
+        PROOF_1_INDEX,
+        get_sample_receipts,
+        get_sample_mock_node,
+        get_sample_tx_hashes_1,
+        SAMPLE_RECEIPT_JSONS_1_PATH,
+    };
+
+    fn get_sample_proof_and_state(
+        output_format: OutputFormat,
+        maybe_log_index: Option<usize>,
+    ) -> (Proof, State) {
+        let mock_node = get_sample_mock_node();
+        let tx_hash = get_sample_tx_hashes_1()[PROOF_1_INDEX].clone();
+        let state = State::init(
+            convert_hex_to_h256(tx_hash.clone()).unwrap(),
+            tx_hash,
+            Some(mock_node.get_endpoint()),
+        )
+            .and_then(|state|
+                state.set_finality_requirement_in_state(
+                    FinalityRequirement::Confirmations(0)
+                )
+            )
+            .and_then(|state| state.set_output_format_in_state(output_format))
+            .and_then(|state| match maybe_log_index {
+                Some(log_index) => state.set_log_index_in_state(log_index),
+                None => Ok(state),
+            })
+            .unwrap();
+        (build_proof_from_state(&state).unwrap(), state)
+    }
+
+    fn get_output(output_format: OutputFormat) -> Bytes {
+        let (proof, state) = get_sample_proof_and_state(output_format, None);
+        get_output_from_proof(&proof, &state)
+            .unwrap()
+    }
+
+    fn get_expected_hex_proof() -> String {
+        let receipts = get_sample_receipts(
+            SAMPLE_RECEIPT_JSONS_1_PATH.to_string(),
+            get_sample_tx_hashes_1(),
+        );
+        get_receipts_trie_from_receipts(&receipts)
+            .and_then(|trie| get_branch_from_trie(trie, PROOF_1_INDEX))
+            .and_then(|branch| get_hex_proof_from_branch(&branch))
+            .unwrap()
+    }
+
+    #[test]
+    fn should_get_hex_output_from_proof() {
+        let result = get_output(OutputFormat::Hex);
+        assert!(result == format!("{}\n", get_expected_hex_proof()).into_bytes());
+    }
+
+    #[test]
+    fn should_get_rlp_bin_output_from_proof() {
+        let result = get_output(OutputFormat::RlpBin);
+        assert!(convert_bytes_to_hex(result) == get_expected_hex_proof());
+    }
+
+    #[test]
+    fn should_get_molecule_output_from_proof() {
+        let result = get_output(OutputFormat::Molecule);
+        let expected_result = convert_hex_to_bytes(get_expected_hex_proof())
+            .and_then(|proof| get_proof_nodes(&proof))
+            .unwrap();
+        assert!(decode_receipt_branch(&result).unwrap() == expected_result);
+    }
+
+    #[test]
+    fn should_get_abi_output_from_proof() {
+        let (proof, state) = get_sample_proof_and_state(OutputFormat::Abi, None);
+        let state = state
+            .set_abi_signature_in_state("(uint256 index, bytes proof)".to_string())
+            .unwrap();
+        let output = String::from_utf8(get_output_from_proof(&proof, &state).unwrap())
+            .unwrap();
+        let expected_proof = convert_hex_to_bytes(get_expected_hex_proof())
+            .unwrap();
+        let result = convert_hex_to_bytes(output.trim().to_string())
+            .and_then(|encoded|
+                ethabi::decode(&[ParamType::Uint(256), ParamType::Bytes], &encoded)
+                    .map_err(|e| AppError::Custom(e.to_string()))
+            )
+            .unwrap();
+        assert!(output.starts_with("0x"));
+        assert!(result == vec![Token::Uint(PROOF_1_INDEX.into()), Token::Bytes(expected_proof)]);
+    }
+
+    #[test]
+    fn should_get_json_output_from_proof() {
+        let (proof, state) = get_sample_proof_and_state(OutputFormat::Json, None);
+        let output = get_output_from_proof(&proof, &state)
+            .unwrap();
+        let result: Json = serde_json::from_slice(&output).unwrap();
+        assert!(result["tx_index"] == PROOF_1_INDEX);
+        assert!(result["receipts_root"] == json!(proof.trie.root));
+        assert!(result["proof"] == get_expected_hex_proof());
+        assert!(result.get("log_index").is_none());
+    }
+
+    #[test]
+    fn should_get_json_output_with_log_from_proof() {
+        let (proof, state) = get_sample_proof_and_state(OutputFormat::Json, Some(2));
+        let expected_log_entry_data = convert_bytes_to_hex(
+            rlp::encode(&proof.get_receipt().logs[2])
+        );
+        let output = get_output_from_proof(&proof, &state)
+            .unwrap();
+        let result: Json = serde_json::from_slice(&output).unwrap();
+        assert!(result["log_index"] == 2);
+        assert!(result["log_entry_data"] == expected_log_entry_data);
+    }
+
+    #[test]
+    fn should_err_on_out_of_range_log_index() {
+        let (proof, state) = get_sample_proof_and_state(OutputFormat::Json, Some(27));
+        match get_output_from_proof(&proof, &state) {
+            Err(AppError::Custom(e)) => assert!(e.contains("with 27 logs")),
+            _ => panic!("Out of range log index should not be accepted!"),
+        }
+    }
+}
@@ -1,13 +1,16 @@
-use crate::state::State;
+use crate::errors::AppError;
+use crate::constants::MAX_TX_TYPE;
 use crate::get_rpc_call_jsons::get_transaction_receipt_json;
-use crate::make_rpc_call::{
-    make_rpc_call,
-    get_response_text,
-    deserialize_to_receipt_rpc_response,
+use crate::make_rpc_call::deserialize_to_receipt_rpc_response;
+use crate::rpc_cache::{
+    RpcCache,
+    CacheKind,
+    get_response_text_via_cache,
 };
 use ethereum_types::{
     H160,
     H256,
+    U256,
     Address,
 };
 use crate::utils::{
@@ -23,18 +26,31 @@ use crate::types::{
     ReceiptJson,
 };
 use crate::get_log::{
-    get_logs_bloom_from_logs,
     get_logs_from_receipt_json,
+    check_logs_bloom_of_receipt_json,
 };
 
+fn get_tx_type_from_receipt_json(receipt: &ReceiptJson) -> Result<Option<u8>> {
+    // NOTE: Legacy receipts are either untyped or of type 0x0, & are encoded without a type byte.
+    match &receipt.r#type {
+        None => Ok(None),
+        Some(tx_type) => match convert_hex_to_u256(tx_type.clone())? {
+            tx_type_u256 if tx_type_u256.is_zero() => Ok(None),
+            tx_type_u256 if tx_type_u256 <= U256::from(MAX_TX_TYPE) => Ok(Some(tx_type_u256.low_u32() as u8)),
+            _ => Err(AppError::Custom(format!("✘ Receipt has invalid tx type {}!", tx_type))),
+        },
+    }
+}
+
 pub fn deserialize_receipt_json_to_receipt_struct(
     receipt: ReceiptJson
 ) -> Result<Receipt> {
     let logs = get_logs_from_receipt_json(&receipt)?;
+    let logs_bloom = check_logs_bloom_of_receipt_json(&receipt, &logs)?;
+    let tx_type = get_tx_type_from_receipt_json(&receipt)?;
     Ok(
         Receipt {
             from: convert_hex_to_address(receipt.from)?,
-            logs_bloom: get_logs_bloom_from_logs(&logs)?,
             gas_used: convert_hex_to_u256(receipt.gasUsed)?,
             block_hash: convert_hex_to_h256(receipt.blockHash)?,
             block_number: convert_hex_to_u256(receipt.blockNumber)?,
@@ -65,68 +81,74 @@ pub fn deserialize_receipt_json_to_receipt_struct(
                 )?,
             },
             logs,
+            tx_type,
+            logs_bloom,
         }
     )
 }
 
 pub fn get_receipt_from_tx_hash(
     endpoint: &str,
-    tx_hash: &str
+    tx_hash: &str,
+    maybe_cache: Option<&RpcCache>,
 ) -> Result<Receipt> {
+    let tx_hash_h256 = convert_hex_to_h256(tx_hash.to_string())?;
     get_transaction_receipt_json(&tx_hash)
-        .and_then(|rpc_json| make_rpc_call(endpoint, rpc_json))
-        .and_then(get_response_text)
+        .and_then(|rpc_json|
+            get_response_text_via_cache(
+                endpoint,
+                rpc_json,
+                maybe_cache,
+                CacheKind::Receipt,
+                tx_hash_h256,
+            )
+        )
         .and_then(deserialize_to_receipt_rpc_response)
         .and_then(|res| deserialize_receipt_json_to_receipt_struct(res.result))
 }
 
-fn get_receipts_from_tx_hashes(
+pub fn get_receipts_from_tx_hashes(
     endpoint: &str,
     tx_hashes: &Vec<H256>,
+    maybe_cache: Option<&RpcCache>,
 ) -> Result<Vec<Receipt>> {
     tx_hashes
         .iter()
         .map(|tx_hash| {
              get_receipt_from_tx_hash(
                  endpoint,
-                 &convert_h256_to_prefixed_hex(*tx_hash)?
+                 &convert_h256_to_prefixed_hex(*tx_hash)?,
+                 maybe_cache,
              )
         })
         .collect::<Result<Vec<Receipt>>>()
 }
 
-pub fn get_all_receipts_from_block_in_state_and_set_in_state(
-    state: State
-) -> Result<State> {
-    info!("✔ Getting all receipts from block...");
-    get_receipts_from_tx_hashes(
-        &State::get_endpoint_from_state(&state)?,
-        &State::get_block_from_state(&state)?.transactions,
-    )
-        .and_then(|receipts| State::set_receipts_in_state(state, receipts))
-}
-
 #[cfg(test)]
 mod tests {
     use std::fs;
     use super::*;
+    use serde_json::{
+        json,
+        Value as Json,
+    };
+    use ethereum_types::Bloom;
     use crate::make_rpc_call::deserialize_to_receipt_rpc_response;
     use crate::test_utils::{
-        TX_INDEX,
         SAMPLE_TX_HASH,
-        WORKING_ENDPOINT,
-        get_expected_block,
+        get_sample_mock_node,
         get_valid_tx_hash_h256,
         SAMPLE_RECEIPT_JSON_PATH,
         assert_receipt_is_correct,
-        get_valid_state_with_endpoint,
     };
 
     #[test]
     fn should_get_receipt_from_tx_hash() {
+        let mock_node = get_sample_mock_node();
         let result = get_receipt_from_tx_hash(
-            WORKING_ENDPOINT,
+            &mock_node.get_endpoint(),
             SAMPLE_TX_HASH,
+            None,
         ).unwrap();
         assert_receipt_is_correct(result)
     }
@@ -143,33 +165,67 @@ mod tests {
         assert_receipt_is_correct(result)
     }
 
+    fn get_sample_receipt_json_with_tx_type(tx_type: &str) -> ReceiptJson {
+        let mut json: Json = fs::read_to_string(SAMPLE_RECEIPT_JSON_PATH)
+            .map(|string| serde_json::from_str(&string).unwrap())
+            .unwrap();
+        json["result"]["type"] = json!(tx_type);
+        deserialize_to_receipt_rpc_response(json.to_string())
+            .unwrap()
+            .result
+    }
+
+    #[test]
+    fn should_prefix_typed_receipt_encoding_with_tx_type() {
+        let legacy_receipt = deserialize_receipt_json_to_receipt_struct(
+            get_sample_receipt_json_with_tx_type("0x0")
+        ).unwrap();
+        let typed_receipt = deserialize_receipt_json_to_receipt_struct(
+            get_sample_receipt_json_with_tx_type("0x2")
+        ).unwrap();
+        assert!(legacy_receipt.tx_type.is_none());
+        assert!(typed_receipt.tx_type == Some(0x02));
+        let result = rlp::encode(&typed_receipt);
+        assert!(result[0] == 0x02);
+        assert!(result[1..] == rlp::encode(&legacy_receipt)[..]);
+    }
+
+    #[test]
+    fn should_encode_typed_receipt_as_type_byte_then_rlp() {
+        let mut receipt = deserialize_receipt_json_to_receipt_struct(
+            get_sample_receipt_json_with_tx_type("0x2")
+        ).unwrap();
+        receipt.status = true;
+        receipt.cumulative_gas_used = U256::from(21_000);
+        receipt.logs_bloom = Bloom::zero();
+        receipt.logs = vec![];
+        let mut expected_result = vec![0x02, 0xf9, 0x01, 0x08, 0x01, 0x82, 0x52, 0x08, 0xb9, 0x01, 0x00];
+        expected_result.append(&mut vec![0u8; 256]);
+        expected_result.push(0xc0);
+        assert!(rlp::encode(&receipt) == expected_result);
+    }
+
+    #[test]
+    fn should_err_on_invalid_tx_type() {
+        match deserialize_receipt_json_to_receipt_struct(get_sample_receipt_json_with_tx_type("0x80")) {
+            Err(AppError::Custom(e)) => assert!(e.contains("invalid tx type 0x80")),
+            _ => panic!("Tx type above 0x7f should not be accepted!"),
+        }
+    }
+
     #[test]
     fn should_get_receipts_from_tx_hashes_correctly() {
+        let mock_node = get_sample_mock_node();
         let tx_hash_h256 = get_valid_tx_hash_h256()
             .unwrap();
         let mut tx_hashes = Vec::new();
         tx_hashes.push(tx_hash_h256);
         tx_hashes.push(tx_hash_h256);
         let result = get_receipts_from_tx_hashes(
-            WORKING_ENDPOINT,
-            &tx_hashes
+            &mock_node.get_endpoint(),
+            &tx_hashes,
+            None,
         ).unwrap();
         assert_receipt_is_correct(result[0].clone());
         assert_receipt_is_correct(result[1].clone());
-    }
-
-    #[test] #[ignore] // ~100 receipts to get ∴ too expensive! Run w/ cargo +nightly test --ignored
-    fn should_get_all_receipts_and_set_in_state() {
-        let initial_state = get_valid_state_with_endpoint()
-            .unwrap();
-        let block = get_expected_block();
-        let state_with_block = State::set_block_in_state(initial_state, block)
-            .unwrap();
-        let resultant_state = get_all_receipts_from_block_in_state_and_set_in_state(
-            state_with_block
-        ).unwrap();
-        let receipts_from_state = State::get_receipts_from_state(&resultant_state)
-            .unwrap();
-        assert_receipt_is_correct(receipts_from_state[TX_INDEX].clone());
-    }
-}
+    }}
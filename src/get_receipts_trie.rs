@@ -1,25 +1,38 @@
-use crate::state::State;
-use crate::rlp_codec::get_rlp_encoded_receipts_and_nibble_tuples;
+use ethereum_types::U256;
+use crate::nibble_utils::get_nibbles_from_bytes;
+use crate::rlp_codec::{
+    rlp_encode_transaction_index,
+    get_rlp_encoded_receipts_and_nibble_tuples,
+};
 use crate::types::{
+    Bytes,
     Result,
     Receipt,
 };
-use crate::trie::{
+use crate::patricia_trie::{
     Trie,
     put_in_trie_recursively,
 };
 
-fn get_receipts_trie_from_receipts(receipts: &Vec<Receipt>) -> Result<Trie> {
+pub fn get_receipts_trie_from_receipts(receipts: &Vec<Receipt>) -> Result<Trie> {
     get_rlp_encoded_receipts_and_nibble_tuples(receipts)
         .and_then(|key_value_tuples|
             put_in_trie_recursively(Trie::get_new_trie()?, key_value_tuples, 0)
         )
 }
 
-pub fn get_receipts_trie_and_set_in_state(state: State) -> Result<State> {
-    info!("✔ Building merkle-patricia trie from receipts...");
-    get_receipts_trie_from_receipts(state.get_receipts_from_state()?)
-        .and_then(|trie| state.set_receipts_trie_in_state(trie))
+pub fn get_receipts_trie_from_encoded_receipts(encoded_receipts: &[Bytes]) -> Result<Trie> {
+    encoded_receipts
+        .iter()
+        .enumerate()
+        .map(|(i, encoded_receipt)|
+            rlp_encode_transaction_index(&U256::from(i))
+                .map(|key| (get_nibbles_from_bytes(key), encoded_receipt.clone()))
+        )
+        .collect::<Result<Vec<_>>>()
+        .and_then(|key_value_tuples|
+            put_in_trie_recursively(Trie::get_new_trie()?, key_value_tuples, 0)
+        )
 }
 
 #[cfg(test)]
@@ -32,7 +45,6 @@ mod tests {
         get_sample_receipts,
         get_sample_tx_hashes_1,
         get_sample_tx_hashes_2,
-        get_valid_initial_state,
         SAMPLE_RECEIPT_JSONS_1_PATH,
         SAMPLE_RECEIPT_JSONS_2_PATH,
     };
@@ -49,26 +61,6 @@ mod tests {
             .unwrap();
         assert!(root_hex == RECEIPTS_ROOT_1);
     }
-
-    #[test]
-    fn should_get_receipts_trie_1_from_state() {
-        let state = get_valid_initial_state()
-            .unwrap();
-        let receipts = get_sample_receipts(
-            SAMPLE_RECEIPT_JSONS_1_PATH.to_string(),
-            get_sample_tx_hashes_1()
-        );
-        let state_with_receipts = state.set_receipts_in_state(receipts)
-            .unwrap();
-        let result = get_receipts_trie_and_set_in_state(state_with_receipts)
-            .unwrap();
-        let trie_from_state = result.get_receipts_trie_from_state()
-            .unwrap();
-        let root_hex = convert_h256_to_prefixed_hex(trie_from_state.root)
-            .unwrap();
-        assert!(root_hex == RECEIPTS_ROOT_1);
-    }
-
     #[test]
     fn should_get_receipts_trie_2_from_receipts() {
         let receipts = get_sample_receipts(
@@ -80,24 +72,4 @@ mod tests {
         let root_hex = convert_h256_to_prefixed_hex(result.root)
             .unwrap();
         assert!(root_hex == RECEIPTS_ROOT_2);
-    }
-
-    #[test]
-    fn should_get_receipts_trie_2_from_state() {
-        let state = get_valid_initial_state()
-            .unwrap();
-        let receipts = get_sample_receipts(
-            SAMPLE_RECEIPT_JSONS_2_PATH.to_string(),
-            get_sample_tx_hashes_2()
-        );
-        let state_with_receipts = state.set_receipts_in_state(receipts)
-            .unwrap();
-        let result = get_receipts_trie_and_set_in_state(state_with_receipts)
-            .unwrap();
-        let trie_from_state = result.get_receipts_trie_from_state()
-            .unwrap();
-        let root_hex = convert_h256_to_prefixed_hex(trie_from_state.root)
-            .unwrap();
-        assert!(root_hex == RECEIPTS_ROOT_2);
-    }
-}
+    }}
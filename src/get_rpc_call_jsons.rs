@@ -34,6 +34,27 @@ pub fn get_transaction_receipt_json(tx_hash: &str) -> Result<Value> {
     )
 }
 
+pub fn get_logs_json(
+    address: &str,
+    topics: &[String],
+    from_block: &str,
+    to_block: &str,
+) -> Result<Value> {
+    Ok(
+        json!({
+            "id": "1",
+            "jsonrpc": "2.0",
+            "method": "eth_getLogs",
+            "params": [{
+                "address": address,
+                "topics": topics,
+                "fromBlock": from_block,
+                "toBlock": to_block,
+            }],
+        })
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -75,5 +96,16 @@ mod tests {
         assert!("\"eth_getTransactionReceipt\"" == result["method"].to_string());
         assert!(expected_result == result["params"][0].to_string());
     }
-}
 
+    #[test]
+    fn should_get_logs_json_correctly() {
+        let topics = vec!["0xc0ffee".to_string()];
+        let result = get_logs_json("0xdecaf", &topics, "0x1", "0x2")
+            .unwrap();
+        assert!("\"eth_getLogs\"" == result["method"].to_string());
+        assert!("\"0xdecaf\"" == result["params"][0]["address"].to_string());
+        assert!("[\"0xc0ffee\"]" == result["params"][0]["topics"].to_string());
+        assert!("\"0x1\"" == result["params"][0]["fromBlock"].to_string());
+        assert!("\"0x2\"" == result["params"][0]["toBlock"].to_string());
+    }
+}
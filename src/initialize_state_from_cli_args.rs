@@ -1,22 +1,184 @@
 use crate::state::State;
-use crate::types::Result;
+use crate::errors::AppError;
+use crate::rpc_cache::RpcCache;
+use crate::types::{
+    Result,
+    OutputFormat,
+    FinalityRequirement,
+};
+use crate::get_config::{
+    Config,
+    get_config,
+};
 use crate::parse_cli_args::CliArgs;
+use crate::get_endpoint::get_endpoint;
+use crate::get_abi_output::{
+    parse_abi_signature,
+    DEFAULT_ABI_SIGNATURE,
+};
 use crate::utils::convert_hex_to_h256;
-use crate::constants::DEFAULT_ENDPOINT;
+use crate::constants::{
+    DEFAULT_CACHE_SIZE_IN_MB,
+    DEFAULT_MIN_CONFIRMATIONS,
+};
+
+const NUM_BYTES_IN_MB: u64 = 1024 * 1024;
+
+pub fn get_maybe_cache(
+    maybe_cache_dir: Option<String>,
+    maybe_cache_size_in_mb: Option<u64>,
+    finality_requirement: &FinalityRequirement,
+) -> Option<RpcCache> {
+    maybe_cache_dir.map(|cache_dir| {
+        let cache_size_in_mb = maybe_cache_size_in_mb
+            .unwrap_or(DEFAULT_CACHE_SIZE_IN_MB);
+        info!(
+            "✔ Using cache at {} with max size of {}MB",
+            cache_dir,
+            cache_size_in_mb,
+        );
+        RpcCache::new(&cache_dir, cache_size_in_mb * NUM_BYTES_IN_MB)
+            .finality(finality_requirement.clone())
+    })
+}
+
+fn maybe_set_cache_in_state(
+    state: State,
+    maybe_cache_dir: Option<String>,
+    maybe_cache_size_in_mb: Option<u64>,
+    finality_requirement: &FinalityRequirement,
+) -> Result<State> {
+    match get_maybe_cache(maybe_cache_dir, maybe_cache_size_in_mb, finality_requirement) {
+        None => Ok(state),
+        Some(cache) => State::set_cache_in_state(state, cache),
+    }
+}
+
+fn get_finality_requirement(
+    maybe_block_tag: Option<String>,
+    maybe_confirmations: Option<u64>,
+) -> Result<FinalityRequirement> {
+    match maybe_block_tag {
+        Some(tag) => match tag.as_ref() {
+            "safe" | "finalized" => Ok(FinalityRequirement::BlockTag(tag)),
+            _ => Err(AppError::Custom(
+                format!("✘ Block tag must be `finalized` or `safe`, not `{}`!", tag)
+            ))
+        },
+        None => Ok(FinalityRequirement::Confirmations(
+            maybe_confirmations.unwrap_or(DEFAULT_MIN_CONFIRMATIONS)
+        ))
+    }
+}
+
+pub fn get_finality_requirement_from_cli_args(
+    cli_args: &CliArgs,
+    config: &Config,
+) -> Result<FinalityRequirement> {
+    match cli_args.flag_block_tag.is_some() || cli_args.flag_confirmations.is_some() {
+        true => get_finality_requirement(
+            cli_args.flag_block_tag.clone(),
+            cli_args.flag_confirmations,
+        ),
+        false => get_finality_requirement(
+            config.block_tag.clone(),
+            config.confirmations,
+        ),
+    }
+}
+
+pub fn get_output_format(maybe_output: Option<String>) -> Result<OutputFormat> {
+    match maybe_output.as_ref().map(String::as_str) {
+        None | Some("hex") => Ok(OutputFormat::Hex),
+        Some("json") => Ok(OutputFormat::Json),
+        Some("rlp-bin") => Ok(OutputFormat::RlpBin),
+        Some("molecule") => Ok(OutputFormat::Molecule),
+        Some("abi") => Ok(OutputFormat::Abi),
+        Some("bundle") => Ok(OutputFormat::Bundle),
+        Some(output) => Err(AppError::Custom(
+            format!(
+                "✘ Output must be `hex`, `json`, `rlp-bin`, `molecule`, `abi` or `bundle`, not `{}`!",
+                output,
+            )
+        ))
+    }
+}
+
+fn maybe_set_log_index_in_state(
+    state: State,
+    maybe_log_index: Option<usize>,
+) -> Result<State> {
+    match maybe_log_index {
+        None => Ok(state),
+        Some(log_index) => match State::get_output_format_from_state(&state)? {
+            OutputFormat::Json => State::set_log_index_in_state(state, log_index),
+            _ => Err(AppError::Custom(
+                "✘ A log index can only be used with `json` output!".to_string()
+            ))
+        }
+    }
+}
+
+fn maybe_set_abi_signature_in_state(
+    state: State,
+    maybe_abi_signature: Option<String>,
+) -> Result<State> {
+    match State::get_output_format_from_state(&state)? {
+        OutputFormat::Abi => {
+            let abi_signature = maybe_abi_signature
+                .unwrap_or_else(|| DEFAULT_ABI_SIGNATURE.to_string());
+            parse_abi_signature(&abi_signature)
+                .and_then(|_| State::set_abi_signature_in_state(state, abi_signature))
+        },
+        _ => match maybe_abi_signature {
+            None => Ok(state),
+            Some(_) => Err(AppError::Custom(
+                "✘ A signature can only be used with `abi` output!".to_string()
+            ))
+        }
+    }
+}
 
 pub fn initialize_state_from_cli_args(cli_args: CliArgs) -> Result<State> {
     info!("✔ Initializing state from CLI args...");
+    let config = get_config(cli_args.flag_config.clone())?;
+    let finality_requirement = get_finality_requirement_from_cli_args(
+        &cli_args,
+        &config,
+    )?;
+    let maybe_cache_dir = cli_args.flag_cache_dir;
+    let maybe_cache_size_in_mb = cli_args.flag_cache_size;
+    let maybe_log_index = cli_args.flag_log_index;
+    let maybe_abi_signature = cli_args.flag_signature;
+    let output_format = get_output_format(cli_args.flag_output.or(config.output))?;
     State::init(
         convert_hex_to_h256(cli_args.arg_txhash.clone())?,
         cli_args.arg_txhash,
-        Some(String::from(DEFAULT_ENDPOINT))
+        Some(get_endpoint(cli_args.flag_endpoint, config.endpoint)?),
     )
+        .and_then(|state|
+            maybe_set_cache_in_state(
+                state,
+                maybe_cache_dir,
+                maybe_cache_size_in_mb,
+                &finality_requirement,
+            )
+        )
+        .and_then(|state|
+            State::set_finality_requirement_in_state(state, finality_requirement)
+        )
+        .and_then(|state| State::set_output_format_in_state(state, output_format))
+        .and_then(|state| maybe_set_log_index_in_state(state, maybe_log_index))
+        .and_then(|state| maybe_set_abi_signature_in_state(state, maybe_abi_signature))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::test_utils::get_valid_tx_hash_hex;
+    use crate::test_utils::{
+        get_sample_cli_args,
+        get_valid_tx_hash_hex,
+    };
 
     #[test]
     fn should_create_state_from_cli_args() {
@@ -29,9 +191,107 @@ mod tests {
             arg_txhash: tx_hash,
             flag_verbose: expected_verbosity,
             flag_disableLogs: disable_logging,
+            ..get_sample_cli_args()
         };
         let state = initialize_state_from_cli_args(cli_args)
             .unwrap();
         assert!(state.tx_hash == expected_tx_hash);
+        assert!(state.cache.is_none());
+        assert!(
+            state.finality_requirement ==
+            Some(FinalityRequirement::Confirmations(DEFAULT_MIN_CONFIRMATIONS))
+        );
+    }
+
+    #[test]
+    fn should_set_cache_in_state_when_cache_dir_given() {
+        let cli_args = CliArgs {
+            arg_txhash: get_valid_tx_hash_hex(),
+            flag_cache_dir: Some("cache-dir".to_string()),
+            flag_cache_size: Some(2),
+            ..get_sample_cli_args()
+        };
+        let state = initialize_state_from_cli_args(cli_args)
+            .unwrap();
+        let cache = state.cache.unwrap();
+        assert!(cache.cache_dir == std::path::PathBuf::from("cache-dir"));
+        assert!(cache.max_size_in_bytes == 2 * NUM_BYTES_IN_MB);
+    }
+
+    #[test]
+    fn should_get_block_tag_finality_requirement() {
+        let result = get_finality_requirement(Some("safe".to_string()), Some(1))
+            .unwrap();
+        assert!(result == FinalityRequirement::BlockTag("safe".to_string()));
+    }
+
+    #[test]
+    fn should_err_on_unknown_block_tag() {
+        match get_finality_requirement(Some("latest".to_string()), None) {
+            Err(AppError::Custom(e)) => assert!(e.contains("`latest`")),
+            _ => panic!("`latest` should not be accepted as a finality tag!"),
+        }
+    }
+
+    #[test]
+    fn should_set_cli_endpoint_and_output_format_in_state() {
+        let mut cli_args = get_sample_cli_args();
+        cli_args.flag_output = Some("json".to_string());
+        cli_args.flag_log_index = Some(3);
+        let state = initialize_state_from_cli_args(cli_args)
+            .unwrap();
+        assert!(State::get_endpoint_from_state(&state).unwrap() == "http://localhost:8545");
+        assert!(state.output_format == Some(OutputFormat::Json));
+        assert!(state.log_index == Some(3));
+    }
+
+    #[test]
+    fn should_err_on_unknown_output_format() {
+        match get_output_format(Some("yaml".to_string())) {
+            Err(AppError::Custom(e)) => assert!(e.contains("`yaml`")),
+            _ => panic!("`yaml` should not be accepted as an output format!"),
+        }
+    }
+
+    #[test]
+    fn should_err_on_log_index_without_json_output() {
+        let mut cli_args = get_sample_cli_args();
+        cli_args.flag_output = Some("rlp-bin".to_string());
+        cli_args.flag_log_index = Some(3);
+        match initialize_state_from_cli_args(cli_args) {
+            Err(AppError::Custom(e)) => assert!(e.contains("`json` output")),
+            _ => panic!("Log index should require `json` output!"),
+        }
+    }
+
+    #[test]
+    fn should_set_default_abi_signature_in_state_for_abi_output() {
+        let mut cli_args = get_sample_cli_args();
+        cli_args.flag_output = Some("abi".to_string());
+        let state = initialize_state_from_cli_args(cli_args)
+            .unwrap();
+        assert!(state.output_format == Some(OutputFormat::Abi));
+        assert!(state.abi_signature.as_deref() == Some(DEFAULT_ABI_SIGNATURE));
+    }
+
+    #[test]
+    fn should_err_on_invalid_abi_signature() {
+        let mut cli_args = get_sample_cli_args();
+        cli_args.flag_output = Some("abi".to_string());
+        cli_args.flag_signature = Some("(bytes header, bytes logs)".to_string());
+        match initialize_state_from_cli_args(cli_args) {
+            Err(AppError::Custom(e)) => assert!(e.contains("`logs`")),
+            _ => panic!("Unknown ABI field should not be accepted!"),
+        }
+    }
+
+    #[test]
+    fn should_err_on_abi_signature_without_abi_output() {
+        let mut cli_args = get_sample_cli_args();
+        cli_args.flag_signature = Some(DEFAULT_ABI_SIGNATURE.to_string());
+        match initialize_state_from_cli_args(cli_args) {
+            Err(AppError::Custom(e)) => assert!(e.contains("`abi` output")),
+            _ => panic!("Signature should require `abi` output!"),
+        }
     }
 }
@@ -2,35 +2,105 @@
 // #![feature(const_vec_new)]
 #![feature(exclusive_range_pattern)]
 
+#[cfg(feature = "native")]
+mod block_proof_context;
+#[cfg(feature = "native")]
+mod check_block_finality;
+#[cfg(feature = "native")]
+mod check_receipts_root;
+#[cfg(feature = "native")]
 mod connect_to_node;
 mod constants;
+mod decode_proof;
 mod errors;
+mod eth_spv_proof_json;
+#[cfg(feature = "ffi")]
+mod ffi;
+#[cfg(feature = "native")]
+mod generate_batch_proofs;
+#[cfg(feature = "native")]
+mod generate_log_proofs;
+#[cfg(feature = "native")]
+mod generate_proof_from_data;
+#[cfg(feature = "ethash")]
+mod ethash;
+#[cfg(feature = "native")]
+mod get_abi_output;
+#[cfg(feature = "native")]
+mod get_beacon_proof;
+#[cfg(feature = "native")]
 mod get_block;
 mod get_branch_from_trie;
+#[cfg(feature = "native")]
+mod get_config;
 mod get_database;
+#[cfg(feature = "native")]
 mod get_endpoint;
+#[cfg(feature = "native")]
+mod get_header_chain;
 mod get_hex_proof_from_branch;
 mod get_keccak_hash;
+#[cfg(feature = "native")]
 mod get_log;
+#[cfg(feature = "native")]
+mod get_output_from_state;
+#[cfg(feature = "native")]
 mod get_receipts;
 mod get_receipts_trie;
+#[cfg(feature = "native")]
 mod get_rpc_call_jsons;
+#[cfg(feature = "native")]
 mod get_tx_index;
+#[cfg(feature = "native")]
 mod initialize_state_from_cli_args;
+#[cfg(feature = "native")]
+mod light_client;
+#[cfg(feature = "native")]
 mod make_rpc_call;
+#[cfg(feature = "native")]
+mod mock_node;
+#[cfg(feature = "native")]
+mod molecule;
 mod nibble_utils;
+#[cfg(feature = "native")]
 mod parse_cli_args;
 mod path_codec;
+mod patricia_trie;
+#[cfg(feature = "native")]
+mod proof_builder;
+#[cfg(feature = "native")]
+mod proof_bundle;
+#[cfg(feature = "native")]
+mod proof_server;
 mod rlp_codec;
+#[cfg(feature = "native")]
+mod rpc_cache;
+#[cfg(feature = "native")]
+mod run_cli_command;
+#[cfg(feature = "native")]
+mod shutdown_signal;
+#[cfg(feature = "native")]
+mod ssz;
+#[cfg(feature = "native")]
 mod state;
+#[cfg(feature = "native")]
 mod test_utils;
-mod trie;
+#[cfg(feature = "native")]
+mod transport;
 mod trie_nodes;
 mod types;
+#[cfg(feature = "native")]
 mod usage_info;
 mod utils;
+#[cfg(feature = "native")]
 mod validate_cli_args;
+#[cfg(feature = "native")]
 mod validate_tx_hash;
+mod verify_proof;
+#[cfg(feature = "wasm")]
+mod wasm_bindings;
+#[cfg(feature = "native")]
+mod watch_blocks;
 
 #[macro_use]
 extern crate log;
@@ -42,180 +112,435 @@ extern crate serde_derive;
 #[macro_use]
 extern crate serial_test_derive;
 
-use crate::get_block::get_block_from_tx_hash_in_state_and_set_in_state;
-use crate::get_branch_from_trie::get_branch_from_trie_and_put_in_state;
-use crate::get_hex_proof_from_branch::get_hex_proof_from_branch_in_state;
-use crate::get_receipts::{
-    get_all_receipts_from_block_in_state_and_set_in_state, get_receipt_from_tx_hash,
+#[cfg(feature = "native")]
+use crate::constants::DEFAULT_MIN_CONFIRMATIONS;
+#[cfg(feature = "native")]
+use ethereum_types::{Address, H256};
+pub use crate::types::FinalityRequirement;
+#[cfg(feature = "native")]
+pub use crate::proof_builder::{Proof, ProofBuilder};
+#[cfg(feature = "native")]
+pub use crate::transport::Transport;
+#[cfg(feature = "native")]
+pub use crate::proof_bundle::{ProofBundle, PROOF_BUNDLE_VERSION};
+#[cfg(feature = "native")]
+pub use crate::block_proof_context::BlockProofContext;
+#[cfg(feature = "native")]
+pub use crate::get_abi_output::{
+    abi_encode_proof, parse_abi_signature, AbiField, AbiProofParts, DEFAULT_ABI_SIGNATURE,
 };
-use crate::get_receipts_trie::get_receipts_trie_and_set_in_state;
-use crate::get_tx_index::get_tx_index_and_add_to_state;
-use crate::state::State;
-use crate::types::{EthSpvProof, Receipt};
-use crate::utils::convert_hex_to_h256;
-use ethabi::{Event, EventParam, ParamType, RawLog, Token};
-use rlp::{Encodable, RlpStream};
+#[cfg(feature = "native")]
+pub use crate::get_header_chain::generate_header_chain;
+#[cfg(feature = "native")]
+pub use crate::get_beacon_proof::{
+    generate_execution_payload_proof, generate_execution_payload_proof_for_block,
+    get_beacon_block_json, BeaconBlockHeader, BeaconFork, ExecutionPayloadProof,
+    MAINNET_BEACON_GENESIS_TIME,
+};
+#[cfg(feature = "native")]
+pub use crate::light_client::{
+    verify_light_client_bootstrap, verify_light_client_update, ChainSpec, LightClientBootstrap,
+    LightClientHeader, LightClientStore, LightClientUpdate, SyncCommittee,
+};
+pub use crate::eth_spv_proof_json::{EthSpvProofJson, ETH_SPV_PROOF_JSON_VERSION};
+#[cfg(feature = "native")]
+pub use crate::generate_log_proofs::ReceiptLogsProof;
+#[cfg(feature = "native")]
+pub use crate::generate_proof_from_data::generate_proof_from_data;
+#[cfg(feature = "native")]
+pub use crate::molecule::{
+    decode_block_header, decode_eth_spv_proof, decode_receipt_branch, encode_block_header,
+    encode_eth_spv_proof, encode_receipt_branch, EthHeader,
+};
+#[cfg(feature = "native")]
+pub use crate::proof_server::{
+    ProofServer, ServerContext, DEFAULT_MAX_CONCURRENT_REQUESTS, DEFAULT_SERVER_ADDRESS,
+};
+pub use crate::types::{Block, EthSpvProof, ReceiptProof};
+#[cfg(feature = "ethash")]
+pub use crate::ethash::{
+    generate_dag_witness, verify_block_pow, verify_dag_node_merkle_proof, DagWitness, EthashCache,
+    EthashDataset,
+};
+pub use crate::errors::AppError;
+
+/// Merkle-patricia trie construction & traversal, plus the receipts trie of a block.
+pub mod trie {
+    pub use crate::get_branch_from_trie::{find_branch_in_trie, get_branch_from_trie};
+    pub use crate::get_receipts_trie::{
+        get_receipts_trie_from_encoded_receipts, get_receipts_trie_from_receipts,
+    };
+    pub use crate::patricia_trie::Trie;
+    pub use crate::trie_nodes::{
+        get_node_from_database, rlp_decode_node, BranchNode, ExtensionNode, LeafNode, Node,
+    };
+    pub use crate::types::{ChildNodes, Database, NodeStack};
+}
+
+/// Nibble arrays as used for trie keys & paths.
+pub mod nibbles {
+    pub use crate::nibble_utils::{
+        convert_nibble_to_bytes, get_common_prefix_nibbles, get_length_in_nibbles,
+        get_nibble_at_index, get_nibbles_from_bytes, get_nibbles_from_offset_bytes,
+        slice_nibbles_at_nibble_index, Nibbles,
+    };
+}
+
+/// RLP & hex-prefix encodings of receipts, trie keys, paths & proofs.
+pub mod codec {
+    pub use crate::decode_proof::{get_decoded_proof_string, get_proof_nodes};
+    pub use crate::get_hex_proof_from_branch::rlp_encode_node_stack;
+    pub use crate::path_codec::{
+        decode_path_to_nibbles_and_node_type, encode_extension_path_from_nibbles,
+        encode_leaf_path_from_nibbles,
+    };
+    pub use crate::rlp_codec::rlp_encode_transaction_index;
+    pub use crate::types::{Byte, Bytes};
+}
+
+/// JSON-RPC calls for the blocks, receipts & logs that proofs are built from.
+#[cfg(feature = "native")]
+pub mod rpc {
+    pub use crate::check_block_finality::check_block_finality;
+    pub use crate::generate_log_proofs::get_logs_in_range;
+    pub use crate::get_block::{get_block_by_block_id, get_block_by_blockhash, get_block_by_number};
+    pub use crate::get_receipts::{get_receipt_from_tx_hash, get_receipts_from_tx_hashes};
+    pub use crate::rpc_cache::{CacheKind, RpcCache};
+    pub use crate::types::{Log, LogJson, Receipt};
+}
+
+/// Receipt proofs of single transactions, whole blocks & matching logs.
+pub mod proof {
+    #[cfg(feature = "native")]
+    pub use crate::block_proof_context::BlockProofContext;
+    #[cfg(feature = "native")]
+    pub use crate::generate_log_proofs::ReceiptLogsProof;
+    #[cfg(feature = "native")]
+    pub use crate::generate_proof_from_data::{
+        generate_offline_proof, generate_offline_proof_from_files, generate_proof_from_data,
+        OfflineProof,
+    };
+    pub use crate::get_hex_proof_from_branch::get_hex_proof_from_branch;
+    #[cfg(feature = "native")]
+    pub use crate::proof_builder::{Proof, ProofBuilder};
+#[cfg(feature = "native")]
+pub use crate::transport::Transport;
+    pub use crate::types::{EthSpvProof, FinalityRequirement, HexProof, ReceiptProof};
+}
+
+/// Verification of receipt proofs against a receipts root.
+pub mod verify {
+    pub use crate::decode_proof::{get_proof_file_from_bytes, ProofFile};
+    #[cfg(feature = "native")]
+    pub use crate::decode_proof::read_proof_file;
+    #[cfg(feature = "native")]
+    pub use crate::proof_bundle::{read_proof_bundle_file, ProofBundle, PROOF_BUNDLE_VERSION};
+    pub use crate::verify_proof::verify_receipt_proof;
+}
 
+#[cfg(feature = "native")]
+use ethabi::{Event, EventParam, ParamType};
+
+#[cfg(feature = "native")]
 pub fn generate_eth_proof(
     tx_hash: String,
     endpoint: String,
 ) -> Result<EthSpvProof, errors::AppError> {
-    let proof = State::init(
-        convert_hex_to_h256(tx_hash.clone())?,
-        tx_hash.clone(),
-        Some(endpoint.clone()),
+    generate_eth_proof_with_finality(
+        tx_hash,
+        endpoint,
+        FinalityRequirement::Confirmations(DEFAULT_MIN_CONFIRMATIONS),
+    )
+}
+
+#[cfg(feature = "native")]
+pub fn generate_receipt_proofs(
+    tx_hashes: Vec<String>,
+    endpoint: String,
+    finality_requirement: FinalityRequirement,
+) -> Vec<(String, Result<ReceiptProof, errors::AppError>)> {
+    generate_batch_proofs::generate_receipt_proofs(
+        &endpoint,
+        &tx_hashes,
+        &finality_requirement,
+        None,
     )
-    .and_then(get_block_from_tx_hash_in_state_and_set_in_state)
-    .and_then(get_all_receipts_from_block_in_state_and_set_in_state)
-    .and_then(get_tx_index_and_add_to_state)
-    .and_then(get_receipts_trie_and_set_in_state)
-    .and_then(get_branch_from_trie_and_put_in_state)
-    .and_then(get_hex_proof_from_branch_in_state);
-    let mut res_receipt =
-        get_receipt_from_tx_hash(endpoint.clone().as_str(), tx_hash.clone().as_str());
-    let mut stream = RlpStream::new();
-    let receipt = res_receipt.unwrap();
-    let logs = &receipt.logs;
-    receipt.rlp_append(&mut stream);
-    let receipt_data = hex::encode(stream.out());
-    // let mut log_data = String::new();
-    let mut log_index = -1;
-    let mut is_exist = false;
-
-    // let mut eth_spv_proof = EthSpvProof::default();
+}
+
+#[cfg(feature = "native")]
+pub fn prove_logs(
+    address: Address,
+    topics: Vec<H256>,
+    from_block: u64,
+    to_block: u64,
+    endpoint: String,
+    finality_requirement: FinalityRequirement,
+) -> Result<Vec<(H256, Result<ReceiptLogsProof, errors::AppError>)>, errors::AppError> {
+    generate_log_proofs::generate_log_proofs(
+        &endpoint,
+        &address,
+        &topics,
+        from_block,
+        to_block,
+        &finality_requirement,
+        None,
+    )
+}
+
+#[cfg(feature = "native")]
+fn get_locked_event() -> Event {
+    let get_param = |name: &str, kind: ParamType, indexed: bool| EventParam {
+        name: name.to_owned(),
+        kind,
+        indexed,
+    };
+    Event {
+        name: "Locked".to_string(),
+        inputs: vec![
+            get_param("token", ParamType::Address, true),
+            get_param("sender", ParamType::Address, true),
+            get_param("lockedAmount", ParamType::Uint(256), false),
+            get_param("bridgeFee", ParamType::Uint(256), false),
+            get_param("recipientLockscript", ParamType::Bytes, false),
+            get_param("replayResistOutpoint", ParamType::Bytes, false),
+            get_param("sudtExtraData", ParamType::Bytes, false),
+        ],
+        anonymous: false,
+    }
+}
+
+#[cfg(feature = "native")]
+pub fn generate_eth_proof_with_finality(
+    tx_hash: String,
+    endpoint: String,
+    finality_requirement: FinalityRequirement,
+) -> Result<EthSpvProof, errors::AppError> {
+    let proof = ProofBuilder::new(Transport::new(endpoint))
+        .tx(tx_hash)
+        .event(get_locked_event())
+        .finality(finality_requirement)
+        .build()?;
+    let receipt = proof.get_receipt();
     let mut eth_spv_proof = EthSpvProof {
         log_index: -1,
         receipt_index: receipt.transaction_index.as_u64(),
-        receipt_data,
+        receipt_data: hex::encode(rlp::encode(receipt)),
+        proof: proof.get_hex_proof()?,
         block_hash: receipt.block_hash,
         ..Default::default()
     };
-    for item in logs {
-        log_index += 1;
-        if hex::encode(item.clone().topics[0].0) == constants::LOCK_EVENT_STRING {
-            let event = Event {
-                name: "Locked".to_string(),
-                inputs: vec![
-                    EventParam {
-                        name: "token".to_owned(),
-                        kind: ParamType::Address,
-                        indexed: true,
-                    },
-                    EventParam {
-                        name: "sender".to_owned(),
-                        kind: ParamType::Address,
-                        indexed: true,
-                    },
-                    EventParam {
-                        name: "lockedAmount".to_owned(),
-                        kind: ParamType::Uint(256),
-                        indexed: false,
-                    },
-                    EventParam {
-                        name: "bridgeFee".to_owned(),
-                        kind: ParamType::Uint(256),
-                        indexed: false,
-                    },
-                    EventParam {
-                        name: "recipientLockscript".to_owned(),
-                        kind: ParamType::Bytes,
-                        indexed: false,
-                    },
-                    EventParam {
-                        name: "replayResistOutpoint".to_owned(),
-                        kind: ParamType::Bytes,
-                        indexed: false,
-                    },
-                    EventParam {
-                        name: "sudtExtraData".to_owned(),
-                        kind: ParamType::Bytes,
-                        indexed: false,
-                    },
-                ],
-                anonymous: false,
-            };
-            let raw_log = RawLog {
-                topics: item.clone().topics,
-                data: item.clone().data,
-            };
-            let result = event.parse_log(raw_log).unwrap();
-            println!("parse event log: {:?}", result);
-            for v in result.params {
-                match v.name.as_str() {
-                    "token" => {
-                        eth_spv_proof.token = v.value.to_address().unwrap();
-                    }
-                    "lockedAmount" => {
-                        eth_spv_proof.lock_amount = v.value.to_uint().unwrap().as_u128();
-                    }
-                    "bridgeFee" => {
-                        eth_spv_proof.bridge_fee = v.value.to_uint().unwrap().as_u128();
-                    }
-                    "recipientLockscript" => {
-                        eth_spv_proof.recipient_lockscript = v.value.to_bytes().unwrap();
-                    }
-                    "replayResistOutpoint" => {
-                        eth_spv_proof.replay_resist_outpoint = v.value.to_bytes().unwrap();
-                    }
-                    "sudtExtraData" => {
-                        eth_spv_proof.sudt_extra_data = v.value.to_bytes().unwrap();
-                    }
-                    _ => {}
-                }
+    let log_index = match proof.log_index {
+        Some(log_index) => log_index,
+        None => {
+            return Err(errors::AppError::Custom(String::from(
+                "the locked tx is not exist.",
+            )))
+        }
+    };
+    let result = proof.get_decoded_log()?;
+    info!("✔ Parsed event log: {:?}", result);
+    for v in result.params {
+        match v.name.as_str() {
+            "token" => {
+                eth_spv_proof.token = v.value.to_address().unwrap();
+            }
+            "lockedAmount" => {
+                eth_spv_proof.lock_amount = v.value.to_uint().unwrap().as_u128();
             }
-            let mut stream = RlpStream::new();
-            item.rlp_append(&mut stream);
-            eth_spv_proof.log_index = log_index;
-            eth_spv_proof.log_entry_data = hex::encode(stream.out());
-            is_exist = true;
-            break;
+            "bridgeFee" => {
+                eth_spv_proof.bridge_fee = v.value.to_uint().unwrap().as_u128();
+            }
+            "recipientLockscript" => {
+                eth_spv_proof.recipient_lockscript = v.value.to_bytes().unwrap();
+            }
+            "replayResistOutpoint" => {
+                eth_spv_proof.replay_resist_outpoint = v.value.to_bytes().unwrap();
+            }
+            "sudtExtraData" => {
+                eth_spv_proof.sudt_extra_data = v.value.to_bytes().unwrap();
+            }
+            _ => {}
         }
     }
-    if !is_exist {
-        return Err(errors::AppError::Custom(String::from(
-            "the locked tx is not exist.",
-        )));
-    }
-
+    eth_spv_proof.log_index = log_index as i32;
+    eth_spv_proof.log_entry_data = hex::encode(rlp::encode(proof.get_log()?));
     Ok(eth_spv_proof)
 }
 
-#[test]
-fn test_get_hex_proof() {
-    let endpoint = "http://127.0.0.1:9545 ";
-    let tx_hash = "0xe3e774843422ef9930b4b83eb9c16849851d222e0b526907d9a74cb3556f2391";
-    let proof = generate_eth_proof(String::from(tx_hash), String::from(endpoint));
-    match proof {
-        Ok(proof) => {
-            println!("{:?}", proof.clone());
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rlp::{Encodable, RlpStream};
+    use crate::get_receipts::get_receipt_from_tx_hash;
+    use crate::mock_node::{
+        MockNode,
+        NULL_RESULT_RESPONSE,
+    };
+    use crate::utils::convert_hex_to_bytes;
+    use crate::test_utils::{
+        PROOF_1_INDEX,
+        SAMPLE_TX_HASH,
+        SAMPLE_BRIDGE_FEE,
+        SAMPLE_LOCKED_AMOUNT,
+        get_sample_receipts,
+        get_sample_mock_node,
+        get_sample_tx_hashes_1,
+        assert_receipt_is_correct,
+        SAMPLE_RECEIPT_JSONS_1_PATH,
+        get_sample_mock_node_fixtures,
+        get_sample_proof_1,
+        get_sample_lock_event_mock_node,
+    };
+
+    #[test]
+    fn should_generate_hex_proof_end_to_end_via_mock_node() {
+        let mock_node = get_sample_mock_node();
+        let result = ProofBuilder::new(Transport::new(mock_node.get_endpoint()))
+            .tx(get_sample_tx_hashes_1()[PROOF_1_INDEX].clone())
+            .confirmations(0)
+            .build()
+            .and_then(|proof| proof.get_hex_proof())
+            .unwrap();
+        assert!(result == get_sample_proof_1());
+    }
+
+    #[test]
+    fn should_fail_to_generate_eth_proof_for_tx_without_lock_event() {
+        let mock_node = get_sample_mock_node();
+        let tx_hash = get_sample_tx_hashes_1()[PROOF_1_INDEX].clone();
+        match generate_eth_proof_with_finality(
+            tx_hash,
+            mock_node.get_endpoint(),
+            FinalityRequirement::Confirmations(0),
+        ) {
+            Err(errors::AppError::Custom(e)) =>
+                assert!(e == "the locked tx is not exist."),
+            _ => panic!("Tx without lock event should not yield a proof!"),
         }
-        Err(err) => {
-            println!("{:?}", err);
+    }
+
+    #[test]
+    fn should_fail_to_generate_eth_proof_for_tx_in_non_final_block() {
+        let mock_node = get_sample_mock_node();
+        let tx_hash = get_sample_tx_hashes_1()[PROOF_1_INDEX].clone();
+        match generate_eth_proof(tx_hash, mock_node.get_endpoint()) {
+            Err(errors::AppError::NotYetFinal(_)) => assert!(true),
+            _ => panic!("Tx in non-final block should not yield a proof!"),
         }
     }
-    // assert_eq!(proof.unwrap(), "f901b2f901af822080b901a9f901a60182d0d9b9010000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000010000000000000000000000000000000000000000000000000000000408000000000000000000000000000000000000000000000000000000000000000000000001000000000000000000000010000000000000000000000000000000000000000000000000000000400000000000100000000000000000000000000080000000000000000000000000000000000000000000100002000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002000000000000000000000000f89df89b94dac17f958d2ee523a2206206994597c13d831ec7f863a0ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3efa00000000000000000000000006cc5f688a315f3dc28a7781717a9a798a59fda7ba00000000000000000000000007e7a32d9dc98c485c489be8e732f97b4ffe3a4cda000000000000000000000000000000000000000000000000000000001a13b8600");
-}
 
-#[test]
-fn test_get_receipt_from_txhash() {
-    let tx_hash = "0xb540248a9cca048c5861dec953d7a776bc1944319b9bd27a462469c8a437f4ff";
-    let endpoint = "https://mainnet.infura.io/v3/9c7178cede9f4a8a84a151d058bd609c";
-    let res = get_receipt_from_tx_hash(endpoint, tx_hash);
-    println!("{:?}", res);
-    let mut stream = RlpStream::new();
-    res.unwrap().rlp_append(&mut stream);
-    println!("{:?}", hex::encode(stream.out()));
-}
+    #[test]
+    fn should_not_leave_proof_empty_in_generated_eth_spv_proof() {
+        let mock_node = get_sample_lock_event_mock_node();
+        let endpoint = mock_node.get_endpoint();
+        let tx_hash = get_sample_tx_hashes_1()[PROOF_1_INDEX].clone();
+        let result = generate_eth_proof_with_finality(
+            tx_hash,
+            endpoint.clone(),
+            FinalityRequirement::Confirmations(0),
+        ).unwrap();
+        assert!(!result.proof.is_empty());
+        let receipts_root = rpc::get_block_by_blockhash(&endpoint, result.block_hash, None)
+            .unwrap()
+            .receipts_root;
+        let proof_nodes = codec::get_proof_nodes(&convert_hex_to_bytes(result.proof).unwrap())
+            .unwrap();
+        let verified_receipt = verify::verify_receipt_proof(&proof_nodes, &receipts_root, PROOF_1_INDEX)
+            .unwrap();
+        assert!(hex::encode(verified_receipt) == result.receipt_data);
+        assert!(result.lock_amount == SAMPLE_LOCKED_AMOUNT);
+        assert!(result.bridge_fee == SAMPLE_BRIDGE_FEE);
+    }
+
+    #[test]
+    fn should_err_not_panic_when_receipt_is_missing() {
+        let mock_node = get_sample_mock_node();
+        let missing_tx_hash = format!("0x{}", "00".repeat(32));
+        match generate_eth_proof(missing_tx_hash, mock_node.get_endpoint()) {
+            Err(errors::AppError::Custom(e)) =>
+                assert!(e.contains("No receipt found")),
+            _ => panic!("Missing receipt should not yield a proof!"),
+        }
+    }
+
+    #[test]
+    fn should_fail_to_generate_eth_proof_for_bad_receipt_json_shape() {
+        let tx_hash = get_sample_tx_hashes_1()[PROOF_1_INDEX].clone();
+        let bad_response = NULL_RESULT_RESPONSE.replace("null", "{\"foo\":1}");
+        let mock_node = get_sample_mock_node_fixtures()
+            .map(|fixtures|
+                fixtures.add_raw_receipt_response(&tx_hash, &bad_response)
+            )
+            .and_then(MockNode::start)
+            .unwrap();
+        match generate_eth_proof(tx_hash, mock_node.get_endpoint()) {
+            Err(errors::AppError::SerdeJsonError(_)) => assert!(true),
+            _ => panic!("Malformed receipt should not yield a proof!"),
+        }
+    }
+
+    #[test]
+    fn should_fail_to_generate_eth_proof_for_non_working_endpoint() {
+        let endpoint = "non-working-endpoint".to_string();
+        match generate_eth_proof(SAMPLE_TX_HASH.to_string(), endpoint) {
+            Ok(_) => panic!("Should not generate proof w/ non-working endpoint!"),
+            Err(_) => assert!(true),
+        }
+    }
+
+    #[test]
+    fn should_get_receipt_from_tx_hash_via_mock_node() {
+        let mock_node = get_sample_mock_node();
+        let result = get_receipt_from_tx_hash(
+            &mock_node.get_endpoint(),
+            SAMPLE_TX_HASH,
+            None,
+        ).unwrap();
+        assert_receipt_is_correct(result);
+    }
+
+    #[test]
+    fn should_rlp_encode_logs_from_receipt_via_mock_node() {
+        let mock_node = get_sample_mock_node();
+        let logs = get_receipt_from_tx_hash(
+            &mock_node.get_endpoint(),
+            SAMPLE_TX_HASH,
+            None,
+        ).unwrap().logs;
+        assert!(!logs.is_empty());
+        logs
+            .iter()
+            .for_each(|log| {
+                let mut stream = RlpStream::new();
+                log.rlp_append(&mut stream);
+                assert!(!stream.out().is_empty());
+            });
+    }
+
+    #[test]
+    fn should_get_locked_event_with_lock_event_signature() {
+        let signature = get_locked_event().signature();
+        assert!(hex::encode(signature.as_bytes()) == constants::LOCK_EVENT_STRING);
+    }
 
-#[test]
-fn test_get_log_from_txhash() {
-    let tx_hash = "0xcc699808af959a6c058a3b77f14f9dc18658c02b1b427d9d3cde01e370802ccf";
-    let endpoint = "http://127.0.0.1:9545";
-    let res = get_receipt_from_tx_hash(endpoint, tx_hash).unwrap().logs;
-    println!("{:?}", res);
-    for item in res {
-        let mut stream = RlpStream::new();
-        item.rlp_append(&mut stream);
-        println!("{:?}", hex::encode(stream.out()));
+    #[test]
+    fn should_build_and_verify_proof_via_public_modules() {
+        let receipts = get_sample_receipts(
+            SAMPLE_RECEIPT_JSONS_1_PATH.to_string(),
+            get_sample_tx_hashes_1(),
+        );
+        let receipts_trie = trie::get_receipts_trie_from_receipts(&receipts)
+            .unwrap();
+        let receipts_root = receipts_trie.root;
+        let branch = trie::get_branch_from_trie(receipts_trie, PROOF_1_INDEX)
+            .unwrap();
+        let hex_proof = proof::get_hex_proof_from_branch(&branch)
+            .unwrap();
+        assert!(hex_proof == get_sample_proof_1());
+        let proof_nodes = codec::get_proof_nodes(&convert_hex_to_bytes(hex_proof).unwrap())
+            .unwrap();
+        let result = verify::verify_receipt_proof(&proof_nodes, &receipts_root, PROOF_1_INDEX)
+            .unwrap();
+        assert!(result == rlp::encode(&receipts[PROOF_1_INDEX]));
     }
 }
@@ -0,0 +1,721 @@
+use ethereum_types::H256;
+use serde_json::Value as Json;
+use blst::BLST_ERROR;
+use blst::min_pk::{
+    PublicKey,
+    Signature,
+};
+use crate::errors::AppError;
+use crate::utils::{
+    convert_hex_to_h256,
+    convert_hex_to_bytes,
+};
+use crate::types::{
+    Bytes,
+    Result,
+};
+use crate::ssz::{
+    sha256_pair,
+    hash_tree_root_container,
+    is_valid_merkle_branch,
+    hash_tree_root_byte_vector,
+    hash_tree_root_vector_of_roots,
+};
+use crate::get_beacon_proof::{
+    get_u64,
+    get_str,
+    get_h256,
+    get_field,
+    get_array,
+    get_bytes,
+    BeaconFork,
+    get_index_from_gindex,
+    BeaconBlockHeader,
+    SYNC_COMMITTEE_SIZE,
+    ExecutionPayloadProof,
+    EXECUTION_PAYLOAD_BODY_INDEX,
+    get_beacon_block_header_from_json,
+    get_execution_payload_header_field_roots,
+};
+
+pub const MAINNET_GENESIS_VALIDATORS_ROOT: &str =
+    "0x4b363db94e286120d76eb905340fdd4e54bfe9f06bf33ff6cf5ad27f511bfe95";
+const BLS_SIGNATURE_DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+const DOMAIN_SYNC_COMMITTEE: [u8; 4] = [7, 0, 0, 0];
+const SLOTS_PER_EPOCH: u64 = 32;
+const EPOCHS_PER_SYNC_COMMITTEE_PERIOD: u64 = 256;
+const FINALIZED_ROOT_GINDEX: u64 = 105;
+const FINALIZED_ROOT_GINDEX_ELECTRA: u64 = 169;
+const CURRENT_SYNC_COMMITTEE_GINDEX: u64 = 54;
+const CURRENT_SYNC_COMMITTEE_GINDEX_ELECTRA: u64 = 86;
+const NEXT_SYNC_COMMITTEE_GINDEX: u64 = 55;
+const NEXT_SYNC_COMMITTEE_GINDEX_ELECTRA: u64 = 87;
+
+#[derive(Clone, Debug)]
+pub struct ChainSpec {
+    pub genesis_validators_root: H256,
+    pub fork_versions: Vec<(u64, [u8; 4])>,
+}
+
+impl ChainSpec {
+    pub fn mainnet() -> Result<Self> {
+        Ok(
+            ChainSpec {
+                genesis_validators_root: convert_hex_to_h256(
+                    MAINNET_GENESIS_VALIDATORS_ROOT.to_string()
+                )?,
+                fork_versions: vec![
+                    (0, [0, 0, 0, 0]),
+                    (74240, [1, 0, 0, 0]),
+                    (144896, [2, 0, 0, 0]),
+                    (194048, [3, 0, 0, 0]),
+                    (269568, [4, 0, 0, 0]),
+                    (364032, [5, 0, 0, 0]),
+                    (411392, [6, 0, 0, 0]),
+                ],
+            }
+        )
+    }
+
+    fn get_fork_version_at_slot(&self, slot: u64) -> [u8; 4] {
+        let epoch = slot / SLOTS_PER_EPOCH;
+        self.fork_versions
+            .iter()
+            .filter(|(fork_epoch, _)| *fork_epoch <= epoch)
+            .map(|(_, version)| *version)
+            .last()
+            .unwrap_or([0, 0, 0, 0])
+    }
+
+    fn get_sync_committee_domain(&self, signature_slot: u64) -> Result<H256> {
+        let mut version_chunk = [0u8; 32];
+        version_chunk[..4].copy_from_slice(
+            &self.get_fork_version_at_slot(signature_slot.max(1) - 1)
+        );
+        hash_tree_root_container(&[H256::from(version_chunk), self.genesis_validators_root])
+            .map(|fork_data_root| {
+                let mut domain = [0u8; 32];
+                domain[..4].copy_from_slice(&DOMAIN_SYNC_COMMITTEE);
+                domain[4..].copy_from_slice(&fork_data_root.as_bytes()[..28]);
+                H256::from(domain)
+            })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SyncCommittee {
+    pub pubkeys: Vec<Bytes>,
+    pub aggregate_pubkey: Bytes,
+}
+
+impl SyncCommittee {
+    pub fn from_json(json: &Json) -> Result<Self> {
+        let pubkeys = get_hex_array(json, "pubkeys")?
+            .iter()
+            .map(|pubkey| convert_hex_to_bytes_of_length(pubkey, 48))
+            .collect::<Result<Vec<Bytes>>>()?;
+        match pubkeys.len() == SYNC_COMMITTEE_SIZE {
+            true => Ok(
+                SyncCommittee {
+                    pubkeys,
+                    aggregate_pubkey: get_bytes(json, "aggregate_pubkey")?,
+                }
+            ),
+            false => Err(AppError::Custom(
+                format!("✘ Sync committee has {} pubkeys, not {}!", pubkeys.len(), SYNC_COMMITTEE_SIZE)
+            ))
+        }
+    }
+
+    pub fn hash_tree_root(&self) -> Result<H256> {
+        self.pubkeys
+            .iter()
+            .map(|pubkey| hash_tree_root_byte_vector(pubkey))
+            .collect::<Result<Vec<H256>>>()
+            .and_then(|roots| hash_tree_root_vector_of_roots(&roots))
+            .and_then(|pubkeys_root|
+                hash_tree_root_container(&[
+                    pubkeys_root,
+                    hash_tree_root_byte_vector(&self.aggregate_pubkey)?,
+                ])
+            )
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct LightClientHeader {
+    pub beacon: BeaconBlockHeader,
+    pub execution_root: H256,
+    pub execution_block_hash: H256,
+    pub execution_receipts_root: H256,
+    pub execution_branch: Vec<H256>,
+}
+
+impl LightClientHeader {
+    pub fn from_json(json: &Json, fork: BeaconFork) -> Result<Self> {
+        let execution = get_field(json, "execution")?;
+        Ok(
+            LightClientHeader {
+                beacon: get_field(json, "beacon")
+                    .and_then(get_beacon_block_header_from_json)?,
+                execution_root: get_execution_payload_header_field_roots(execution, fork)
+                    .and_then(|roots| hash_tree_root_container(&roots))?,
+                execution_block_hash: get_h256(execution, "block_hash")?,
+                execution_receipts_root: get_h256(execution, "receipts_root")?,
+                execution_branch: get_branch(json, "execution_branch")?,
+            }
+        )
+    }
+
+    pub fn is_valid(&self) -> bool {
+        is_valid_merkle_branch(
+            &self.execution_root,
+            &self.execution_branch,
+            EXECUTION_PAYLOAD_BODY_INDEX as u64,
+            &self.beacon.body_root,
+        )
+    }
+
+    pub fn check_execution_payload_proof(&self, proof: &ExecutionPayloadProof) -> Result<()> {
+        match proof.is_valid() && proof.beacon_block_root == self.beacon.hash_tree_root()? {
+            true => Ok(()),
+            false => Err(AppError::Custom(
+                format!(
+                    "✘ Execution payload proof does not anchor to beacon block at slot {}!",
+                    self.beacon.slot,
+                )
+            ))
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct LightClientBootstrap {
+    pub header: LightClientHeader,
+    pub current_sync_committee: SyncCommittee,
+}
+
+#[derive(Clone, Debug)]
+pub struct LightClientStore {
+    pub finalized_header: LightClientHeader,
+    pub current_sync_committee: SyncCommittee,
+    pub next_sync_committee: Option<SyncCommittee>,
+}
+
+fn get_sync_committee_period_at_slot(slot: u64) -> u64 {
+    slot / SLOTS_PER_EPOCH / EPOCHS_PER_SYNC_COMMITTEE_PERIOD
+}
+
+impl LightClientStore {
+    pub fn from_bootstrap(bootstrap: LightClientBootstrap) -> Self {
+        LightClientStore {
+            finalized_header: bootstrap.header,
+            current_sync_committee: bootstrap.current_sync_committee,
+            next_sync_committee: None,
+        }
+    }
+
+    fn get_period(&self) -> u64 {
+        get_sync_committee_period_at_slot(self.finalized_header.beacon.slot)
+    }
+
+    /// Gets the committee that signs at the given slot: the current one within the store's
+    /// sync committee period, or the next one, if known, in the period after it.
+    pub fn get_sync_committee_at_slot(&self, signature_slot: u64) -> Result<&SyncCommittee> {
+        let store_period = self.get_period();
+        let signature_period = get_sync_committee_period_at_slot(signature_slot);
+        match (signature_period, &self.next_sync_committee) {
+            (period, _) if period == store_period => Ok(&self.current_sync_committee),
+            (period, Some(next_sync_committee)) if period == store_period + 1 =>
+                Ok(next_sync_committee),
+            _ => Err(AppError::Custom(
+                format!(
+                    "✘ No known sync committee for period {} of signature slot {}, the store is in period {}!",
+                    signature_period,
+                    signature_slot,
+                    store_period,
+                )
+            ))
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct LightClientUpdate {
+    pub attested_header: LightClientHeader,
+    pub finalized_header: Option<LightClientHeader>,
+    pub next_sync_committee: Option<SyncCommittee>,
+    pub signature_slot: u64,
+}
+
+fn get_hex_array(json: &Json, key: &str) -> Result<Vec<String>> {
+    get_array(json, key)?
+        .iter()
+        .map(|item| match item.as_str() {
+            Some(hex) => Ok(hex.to_string()),
+            None => Err(AppError::Custom(
+                format!("✘ Item in `{}` array is not a hex string!", key)
+            ))
+        })
+        .collect()
+}
+
+fn convert_hex_to_bytes_of_length(hex: &str, length: usize) -> Result<Bytes> {
+    convert_hex_to_bytes(hex.to_string())
+        .and_then(|bytes| match bytes.len() == length {
+            true => Ok(bytes),
+            false => Err(AppError::Custom(
+                format!("✘ Expected {} bytes but got {}!", length, bytes.len())
+            ))
+        })
+}
+
+fn get_branch(json: &Json, key: &str) -> Result<Vec<H256>> {
+    get_hex_array(json, key)?
+        .into_iter()
+        .map(convert_hex_to_h256)
+        .collect()
+}
+
+fn get_fork_from_json(json: &Json) -> Result<BeaconFork> {
+    get_str(json, "version")
+        .and_then(BeaconFork::from_version)
+        .and_then(|fork| match fork >= BeaconFork::Capella {
+            true => Ok(fork),
+            false => Err(AppError::Custom(
+                "✘ Light client headers before Capella have no execution payload!".to_string()
+            ))
+        })
+}
+
+fn check_light_client_header_is_valid(header: &LightClientHeader) -> Result<()> {
+    match header.is_valid() {
+        true => Ok(()),
+        false => Err(AppError::Custom(
+            format!("✘ Invalid execution branch in header at slot {}!", header.beacon.slot)
+        ))
+    }
+}
+
+fn check_state_branch(
+    leaf: &H256,
+    branch: &[H256],
+    gindex: u64,
+    state_root: &H256,
+    description: &str,
+) -> Result<()> {
+    match is_valid_merkle_branch(leaf, branch, get_index_from_gindex(gindex)?, state_root) {
+        true => Ok(()),
+        false => Err(AppError::Custom(
+            format!("✘ Invalid {} branch against state root {:?}!", description, state_root)
+        ))
+    }
+}
+
+fn is_zero_branch(branch: &[H256]) -> bool {
+    branch.iter().all(|node| node.is_zero())
+}
+
+fn get_participant_pubkeys(
+    sync_committee: &SyncCommittee,
+    sync_committee_bits: &[u8],
+) -> Result<Vec<PublicKey>> {
+    match sync_committee_bits.len() * 8 == SYNC_COMMITTEE_SIZE {
+        false => Err(AppError::Custom(
+            format!("✘ Sync committee bits are {} bytes long!", sync_committee_bits.len())
+        )),
+        true => sync_committee.pubkeys
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| sync_committee_bits[i / 8] >> (i % 8) & 1 == 1)
+            .map(|(_, pubkey)|
+                PublicKey::key_validate(pubkey).map_err(|e| AppError::Custom(
+                    format!("✘ Invalid sync committee pubkey: {:?}", e)
+                ))
+            )
+            .collect()
+    }
+}
+
+fn verify_sync_aggregate(
+    sync_aggregate: &Json,
+    sync_committee: &SyncCommittee,
+    signing_root: &H256,
+) -> Result<()> {
+    let pubkeys = get_participant_pubkeys(
+        sync_committee,
+        &get_bytes(sync_aggregate, "sync_committee_bits")?,
+    )?;
+    if pubkeys.len() * 3 < SYNC_COMMITTEE_SIZE * 2 {
+        return Err(AppError::Custom(
+            format!(
+                "✘ Only {} sync committee participants, but a 2/3 supermajority of {} is needed!",
+                pubkeys.len(),
+                SYNC_COMMITTEE_SIZE,
+            )
+        ));
+    }
+    let signature = Signature::sig_validate(
+        &get_bytes(sync_aggregate, "sync_committee_signature")?,
+        true,
+    ).map_err(|e| AppError::Custom(format!("✘ Invalid sync committee signature: {:?}", e)))?;
+    let pubkey_refs: Vec<&PublicKey> = pubkeys.iter().collect();
+    match signature.fast_aggregate_verify(
+        true,
+        signing_root.as_bytes(),
+        BLS_SIGNATURE_DST,
+        &pubkey_refs,
+    ) {
+        BLST_ERROR::BLST_SUCCESS => Ok(()),
+        e => Err(AppError::Custom(
+            format!("✘ Sync committee signature did not verify: {:?}", e)
+        ))
+    }
+}
+
+fn check_next_sync_committee_matches_store(
+    store: &LightClientStore,
+    attested_header: &LightClientHeader,
+    next_sync_committee: &SyncCommittee,
+) -> Result<()> {
+    let is_in_store_period =
+        get_sync_committee_period_at_slot(attested_header.beacon.slot) == store.get_period();
+    match &store.next_sync_committee {
+        Some(known_committee) if is_in_store_period && known_committee != next_sync_committee =>
+            Err(AppError::Custom(
+                "✘ Next sync committee differs from the one already in the store!".to_string()
+            )),
+        _ => Ok(()),
+    }
+}
+
+pub fn verify_light_client_bootstrap(
+    bootstrap: &Json,
+    trusted_block_root: &H256,
+) -> Result<LightClientBootstrap> {
+    info!("✔ Verifying light client bootstrap...");
+    let fork = get_fork_from_json(bootstrap)?;
+    let data = get_field(bootstrap, "data")?;
+    let header = LightClientHeader::from_json(get_field(data, "header")?, fork)?;
+    let current_sync_committee = get_field(data, "current_sync_committee")
+        .and_then(SyncCommittee::from_json)?;
+    check_light_client_header_is_valid(&header)?;
+    if &header.beacon.hash_tree_root()? != trusted_block_root {
+        return Err(AppError::Custom(
+            format!("✘ Bootstrap header is not trusted block {:?}!", trusted_block_root)
+        ));
+    }
+    check_state_branch(
+        &current_sync_committee.hash_tree_root()?,
+        &get_branch(data, "current_sync_committee_branch")?,
+        match fork {
+            BeaconFork::Electra | BeaconFork::Fulu => CURRENT_SYNC_COMMITTEE_GINDEX_ELECTRA,
+            _ => CURRENT_SYNC_COMMITTEE_GINDEX,
+        },
+        &header.beacon.state_root,
+        "current sync committee",
+    )?;
+    Ok(LightClientBootstrap { header, current_sync_committee })
+}
+
+pub fn verify_light_client_update(
+    update: &Json,
+    store: &LightClientStore,
+    chain_spec: &ChainSpec,
+) -> Result<LightClientUpdate> {
+    info!("✔ Verifying light client update...");
+    let fork = get_fork_from_json(update)?;
+    let data = get_field(update, "data")?;
+    let attested_header = LightClientHeader::from_json(get_field(data, "attested_header")?, fork)?;
+    let signature_slot = get_u64(data, "signature_slot")?;
+    check_light_client_header_is_valid(&attested_header)?;
+    if signature_slot <= attested_header.beacon.slot {
+        return Err(AppError::Custom(
+            format!(
+                "✘ Signature slot {} is not after attested slot {}!",
+                signature_slot,
+                attested_header.beacon.slot,
+            )
+        ));
+    }
+    let sync_committee = store.get_sync_committee_at_slot(signature_slot)?;
+    let finality_branch = get_branch(data, "finality_branch")?;
+    let finalized_header = match is_zero_branch(&finality_branch) {
+        true => None,
+        false => {
+            let header = LightClientHeader::from_json(get_field(data, "finalized_header")?, fork)?;
+            check_light_client_header_is_valid(&header)?;
+            if header.beacon.slot > attested_header.beacon.slot {
+                return Err(AppError::Custom(
+                    "✘ Finalized header is newer than attested header!".to_string()
+                ));
+            }
+            check_state_branch(
+                &header.beacon.hash_tree_root()?,
+                &finality_branch,
+                match fork {
+                    BeaconFork::Electra | BeaconFork::Fulu => FINALIZED_ROOT_GINDEX_ELECTRA,
+                    _ => FINALIZED_ROOT_GINDEX,
+                },
+                &attested_header.beacon.state_root,
+                "finality",
+            )?;
+            Some(header)
+        }
+    };
+    let next_sync_committee_branch = get_branch(data, "next_sync_committee_branch")?;
+    let next_sync_committee = match is_zero_branch(&next_sync_committee_branch) {
+        true => None,
+        false => {
+            let committee = get_field(data, "next_sync_committee")
+                .and_then(SyncCommittee::from_json)?;
+            check_state_branch(
+                &committee.hash_tree_root()?,
+                &next_sync_committee_branch,
+                match fork {
+                    BeaconFork::Electra | BeaconFork::Fulu => NEXT_SYNC_COMMITTEE_GINDEX_ELECTRA,
+                    _ => NEXT_SYNC_COMMITTEE_GINDEX,
+                },
+                &attested_header.beacon.state_root,
+                "next sync committee",
+            )?;
+            check_next_sync_committee_matches_store(store, &attested_header, &committee)?;
+            Some(committee)
+        }
+    };
+    let signing_root = sha256_pair(
+        &attested_header.beacon.hash_tree_root()?,
+        &chain_spec.get_sync_committee_domain(signature_slot)?,
+    );
+    verify_sync_aggregate(get_field(data, "sync_aggregate")?, sync_committee, &signing_root)?;
+    info!("✔ Light client update for slot {} verified!", attested_header.beacon.slot);
+    Ok(
+        LightClientUpdate {
+            attested_header,
+            finalized_header,
+            next_sync_committee,
+            signature_slot,
+        }
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use crate::get_beacon_proof::generate_execution_payload_proof;
+    use crate::test_utils::{
+        RECEIPTS_ROOT_1,
+        get_expected_block_1,
+        SAMPLE_GENESIS_VALIDATORS_ROOT,
+        SAMPLE_BEACON_BLOCK_JSON_PATH,
+        SAMPLE_LIGHT_CLIENT_BOOTSTRAP_ROOT,
+        SAMPLE_LIGHT_CLIENT_UPDATE_JSON_PATH,
+        SAMPLE_LIGHT_CLIENT_BOOTSTRAP_JSON_PATH,
+    };
+
+    fn read_json(path: &str) -> Json {
+        serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+    }
+
+    fn get_sample_chain_spec() -> ChainSpec {
+        ChainSpec {
+            genesis_validators_root: convert_hex_to_h256(
+                SAMPLE_GENESIS_VALIDATORS_ROOT.to_string()
+            ).unwrap(),
+            fork_versions: vec![(0, [4, 0, 0, 0])],
+        }
+    }
+
+    fn get_sample_bootstrap() -> LightClientBootstrap {
+        verify_light_client_bootstrap(
+            &read_json(SAMPLE_LIGHT_CLIENT_BOOTSTRAP_JSON_PATH),
+            &convert_hex_to_h256(SAMPLE_LIGHT_CLIENT_BOOTSTRAP_ROOT.to_string()).unwrap(),
+        ).unwrap()
+    }
+
+    fn get_sample_store() -> LightClientStore {
+        LightClientStore::from_bootstrap(get_sample_bootstrap())
+    }
+
+    fn get_sync_committee_bits(num_participants: usize) -> Json {
+        let mut bits = vec![0u8; SYNC_COMMITTEE_SIZE / 8];
+        (0..num_participants).for_each(|i| bits[i / 8] |= 1 << (i % 8));
+        json!(format!("0x{}", hex::encode(bits)))
+    }
+
+    fn assert_update_fails(update: &Json, expected_error: &str) {
+        match verify_light_client_update(
+            update,
+            &get_sample_store(),
+            &get_sample_chain_spec(),
+        ) {
+            Err(AppError::Custom(e)) => assert!(e.contains(expected_error)),
+            _ => panic!("Update should not verify!"),
+        }
+    }
+
+    #[test]
+    fn should_get_mainnet_fork_version_at_slot() {
+        let chain_spec = ChainSpec::mainnet().unwrap();
+        assert!(chain_spec.get_fork_version_at_slot(0) == [0, 0, 0, 0]);
+        assert!(chain_spec.get_fork_version_at_slot(194048 * 32 - 1) == [2, 0, 0, 0]);
+        assert!(chain_spec.get_fork_version_at_slot(194048 * 32) == [3, 0, 0, 0]);
+        assert!(chain_spec.get_fork_version_at_slot(411392 * 32 - 1) == [5, 0, 0, 0]);
+        assert!(chain_spec.get_fork_version_at_slot(411392 * 32) == [6, 0, 0, 0]);
+    }
+
+    #[test]
+    fn should_verify_light_client_bootstrap() {
+        let result = get_sample_bootstrap();
+        assert!(result.header.beacon.slot == 8192);
+        assert!(result.current_sync_committee.pubkeys.len() == SYNC_COMMITTEE_SIZE);
+    }
+
+    #[test]
+    fn should_fail_to_verify_bootstrap_for_untrusted_root() {
+        match verify_light_client_bootstrap(
+            &read_json(SAMPLE_LIGHT_CLIENT_BOOTSTRAP_JSON_PATH),
+            &H256::zero(),
+        ) {
+            Err(AppError::Custom(e)) => assert!(e.contains("is not trusted block")),
+            _ => panic!("Bootstrap for untrusted root should not verify!"),
+        }
+    }
+
+    #[test]
+    fn should_verify_light_client_update() {
+        let result = verify_light_client_update(
+            &read_json(SAMPLE_LIGHT_CLIENT_UPDATE_JSON_PATH),
+            &get_sample_store(),
+            &get_sample_chain_spec(),
+        ).unwrap();
+        assert!(result.attested_header.beacon.slot == 9000);
+        assert!(result.finalized_header.unwrap().beacon.slot == 8960);
+        assert!(result.next_sync_committee.is_some());
+        assert!(result.signature_slot == 9001);
+    }
+
+    #[test]
+    fn should_fail_to_verify_update_signed_by_other_committee() {
+        let update = read_json(SAMPLE_LIGHT_CLIENT_UPDATE_JSON_PATH);
+        let mut store = get_sample_store();
+        store.current_sync_committee = SyncCommittee::from_json(
+            &update["data"]["next_sync_committee"]
+        ).unwrap();
+        match verify_light_client_update(&update, &store, &get_sample_chain_spec()) {
+            Err(AppError::Custom(e)) => assert!(e.contains("did not verify")),
+            _ => panic!("Update signed by another committee should not verify!"),
+        }
+    }
+
+    #[test]
+    fn should_fail_to_verify_update_for_other_fork_version() {
+        let mut chain_spec = get_sample_chain_spec();
+        chain_spec.fork_versions = vec![(0, [5, 0, 0, 0])];
+        match verify_light_client_update(
+            &read_json(SAMPLE_LIGHT_CLIENT_UPDATE_JSON_PATH),
+            &get_sample_store(),
+            &chain_spec,
+        ) {
+            Err(AppError::Custom(e)) => assert!(e.contains("did not verify")),
+            _ => panic!("Update for another fork version should not verify!"),
+        }
+    }
+
+    #[test]
+    fn should_fail_to_verify_update_with_tampered_attested_header() {
+        let mut update = read_json(SAMPLE_LIGHT_CLIENT_UPDATE_JSON_PATH);
+        update["data"]["attested_header"]["beacon"]["proposer_index"] = json!("1");
+        assert_update_fails(&update, "did not verify");
+    }
+
+    #[test]
+    fn should_fail_to_verify_update_with_tampered_finalized_header() {
+        let mut update = read_json(SAMPLE_LIGHT_CLIENT_UPDATE_JSON_PATH);
+        update["data"]["finalized_header"]["beacon"]["proposer_index"] = json!("1");
+        assert_update_fails(&update, "Invalid finality branch");
+    }
+
+    #[test]
+    fn should_fail_to_verify_update_with_tampered_execution_header() {
+        let mut update = read_json(SAMPLE_LIGHT_CLIENT_UPDATE_JSON_PATH);
+        update["data"]["attested_header"]["execution"]["receipts_root"] =
+            json!(format!("{:?}", H256::zero()));
+        assert_update_fails(&update, "Invalid execution branch");
+    }
+
+    #[test]
+    fn should_fail_to_verify_update_without_participants() {
+        let mut update = read_json(SAMPLE_LIGHT_CLIENT_UPDATE_JSON_PATH);
+        update["data"]["sync_aggregate"]["sync_committee_bits"] = get_sync_committee_bits(0);
+        assert_update_fails(&update, "Only 0 sync committee participants");
+    }
+
+    #[test]
+    fn should_fail_to_verify_update_without_supermajority() {
+        let mut update = read_json(SAMPLE_LIGHT_CLIENT_UPDATE_JSON_PATH);
+        update["data"]["sync_aggregate"]["sync_committee_bits"] = get_sync_committee_bits(341);
+        assert_update_fails(&update, "Only 341 sync committee participants");
+    }
+
+    #[test]
+    fn should_check_signature_of_update_with_bare_supermajority() {
+        let mut update = read_json(SAMPLE_LIGHT_CLIENT_UPDATE_JSON_PATH);
+        update["data"]["sync_aggregate"]["sync_committee_bits"] = get_sync_committee_bits(342);
+        assert_update_fails(&update, "did not verify");
+    }
+
+    #[test]
+    fn should_fail_to_verify_update_signed_in_period_without_known_committee() {
+        let mut update = read_json(SAMPLE_LIGHT_CLIENT_UPDATE_JSON_PATH);
+        update["data"]["signature_slot"] = json!("16385");
+        assert_update_fails(&update, "No known sync committee for period 2");
+    }
+
+    #[test]
+    fn should_check_update_signed_in_next_period_against_next_committee() {
+        let mut update = read_json(SAMPLE_LIGHT_CLIENT_UPDATE_JSON_PATH);
+        update["data"]["signature_slot"] = json!("16385");
+        let mut store = get_sample_store();
+        store.next_sync_committee = Some(
+            SyncCommittee::from_json(&update["data"]["next_sync_committee"]).unwrap()
+        );
+        match verify_light_client_update(&update, &store, &get_sample_chain_spec()) {
+            Err(AppError::Custom(e)) => assert!(e.contains("did not verify")),
+            _ => panic!("Update in next period should be checked against next committee!"),
+        }
+    }
+
+    #[test]
+    fn should_fail_to_verify_update_with_next_committee_differing_from_store() {
+        let mut store = get_sample_store();
+        store.next_sync_committee = Some(store.current_sync_committee.clone());
+        match verify_light_client_update(
+            &read_json(SAMPLE_LIGHT_CLIENT_UPDATE_JSON_PATH),
+            &store,
+            &get_sample_chain_spec(),
+        ) {
+            Err(AppError::Custom(e)) => assert!(e.contains("differs from the one already in the store")),
+            _ => panic!("Update with conflicting next committee should not verify!"),
+        }
+    }
+
+    #[test]
+    fn should_anchor_receipts_root_to_sync_committee_end_to_end() {
+        let update = verify_light_client_update(
+            &read_json(SAMPLE_LIGHT_CLIENT_UPDATE_JSON_PATH),
+            &get_sample_store(),
+            &get_sample_chain_spec(),
+        ).unwrap();
+        let proof = generate_execution_payload_proof(
+            &read_json(SAMPLE_BEACON_BLOCK_JSON_PATH)
+        ).unwrap();
+        update.attested_header.check_execution_payload_proof(&proof).unwrap();
+        proof.check_matches_block(&get_expected_block_1()).unwrap();
+        assert!(
+            update.attested_header.execution_receipts_root ==
+                convert_hex_to_h256(RECEIPTS_ROOT_1.to_string()).unwrap()
+        );
+    }
+}
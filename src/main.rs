@@ -2,19 +2,33 @@
 // #![feature(const_vec_new)]
 #![feature(exclusive_range_pattern)]
 
-mod trie;
+mod ssz;
 mod utils;
 mod state;
+mod molecule;
 mod types;
 mod errors;
 mod get_log;
 mod rlp_codec;
 mod constants;
 mod get_block;
+mod rpc_cache;
 mod trie_nodes;
 mod usage_info;
 mod test_utils;
+mod transport;
+mod mock_node;
 mod path_codec;
+mod patricia_trie;
+mod proof_server;
+mod proof_builder;
+mod proof_bundle;
+mod shutdown_signal;
+mod watch_blocks;
+mod light_client;
+mod get_config;
+mod decode_proof;
+mod verify_proof;
 mod get_receipts;
 mod nibble_utils;
 mod get_database;
@@ -22,13 +36,23 @@ mod get_tx_index;
 mod get_endpoint;
 mod make_rpc_call;
 mod parse_cli_args;
+mod run_cli_command;
 mod get_keccak_hash;
 mod connect_to_node;
 mod validate_tx_hash;
 mod validate_cli_args;
+mod check_receipts_root;
 mod get_receipts_trie;
+mod generate_batch_proofs;
+mod generate_proof_from_data;
+mod block_proof_context;
+mod get_header_chain;
+mod get_beacon_proof;
 mod get_rpc_call_jsons;
 mod get_branch_from_trie;
+mod check_block_finality;
+mod get_abi_output;
+mod get_output_from_state;
 mod get_hex_proof_from_branch;
 mod initialize_state_from_cli_args;
 
@@ -37,34 +61,23 @@ mod initialize_state_from_cli_args;
 #[macro_use] extern crate serde_derive;
 #[cfg(test)] #[macro_use] extern crate serial_test_derive;
 
+use std::io::Write;
 use crate::parse_cli_args::parse_cli_args;
-use crate::connect_to_node::connect_to_node;
+use crate::run_cli_command::run_cli_command;
 use crate::validate_cli_args::validate_cli_args;
-use crate::get_endpoint::get_endpoint_and_set_in_state;
-use crate::get_tx_index::get_tx_index_and_add_to_state;
-use crate::get_receipts_trie::get_receipts_trie_and_set_in_state;
-use crate::get_block::get_block_from_tx_hash_in_state_and_set_in_state;
-use crate::get_branch_from_trie::get_branch_from_trie_and_put_in_state;
-use crate::get_hex_proof_from_branch::get_hex_proof_from_branch_in_state;
-use crate::initialize_state_from_cli_args::initialize_state_from_cli_args;
-use crate::get_receipts::get_all_receipts_from_block_in_state_and_set_in_state;
 
 fn main() {
     match parse_cli_args()
         .and_then(validate_cli_args)
-        .and_then(initialize_state_from_cli_args)
-        .and_then(get_endpoint_and_set_in_state)
-        .and_then(connect_to_node)
-        .and_then(get_block_from_tx_hash_in_state_and_set_in_state)
-        .and_then(get_all_receipts_from_block_in_state_and_set_in_state)
-        .and_then(get_tx_index_and_add_to_state)
-        .and_then(get_receipts_trie_and_set_in_state)
-        .and_then(get_branch_from_trie_and_put_in_state)
-        .and_then(get_hex_proof_from_branch_in_state) {
-            Ok(hex_proof) => {
-                info!("✔ Hex Proof:\n");
-                trace!("{}", hex_proof);
-                println!("{}", hex_proof);
+        .and_then(run_cli_command) {
+            Ok(output) => {
+                info!("✔ Output:\n");
+                trace!("{}", String::from_utf8_lossy(&output));
+                let mut stdout = std::io::stdout();
+                if let Err(e) = stdout.write_all(&output).and_then(|_| stdout.flush()) {
+                    error!("{}", e);
+                    std::process::exit(1);
+                }
             },
             Err(e) => {
                 error!("{}", e);
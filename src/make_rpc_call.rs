@@ -5,6 +5,7 @@ use serde_json::Value as Json;
 use crate::constants::REQWEST_TIMEOUT_TIME;
 use crate::types::{
     Result,
+    LogsRpcResponse,
     BlockRpcResponse,
     ReceiptRpcResponse,
 };
@@ -52,6 +53,12 @@ pub fn deserialize_to_receipt_rpc_response(
     Ok(serde_json::from_str(&rpc_call_result)?)
 }
 
+pub fn deserialize_to_logs_rpc_response(
+    rpc_call_result: String
+) -> Result<LogsRpcResponse> {
+    Ok(serde_json::from_str(&rpc_call_result)?)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -63,29 +70,35 @@ mod tests {
     };
     use crate::test_utils::{
         SAMPLE_TX_HASH,
-        WORKING_ENDPOINT,
         SAMPLE_BLOCK_HASH,
+        get_sample_mock_node,
         assert_block_is_correct,
         assert_receipt_is_correct,
     };
 
     #[test]
     fn should_make_rpc_call_correctly() {
+        let mock_node = get_sample_mock_node();
         let block_hash = SAMPLE_BLOCK_HASH.to_string();
         let rpc_call_json = get_block_by_block_hash_json(block_hash)
             .unwrap();
-        let result = make_rpc_call(WORKING_ENDPOINT, rpc_call_json)
-            .unwrap();
+        let result = make_rpc_call(
+            &mock_node.get_endpoint(),
+            rpc_call_json
+        ).unwrap();
         assert!(result.status() == 200);
     }
 
     #[test]
     fn should_get_response_text_correctly() {
+        let mock_node = get_sample_mock_node();
         let block_hash = SAMPLE_BLOCK_HASH.to_string();
         let rpc_call_json = get_block_by_block_hash_json(block_hash)
             .unwrap();
-        let reqwest_response = make_rpc_call(WORKING_ENDPOINT, rpc_call_json)
-            .unwrap();
+        let reqwest_response = make_rpc_call(
+            &mock_node.get_endpoint(),
+            rpc_call_json
+        ).unwrap();
         let result = get_response_text(reqwest_response)
             .unwrap();
         let rpc_result_struct = deserialize_to_block_rpc_response(result)
@@ -98,11 +111,14 @@ mod tests {
 
     #[test]
     fn should_deserialize_rpc_call_to_block_rpc_response_correctly() {
+        let mock_node = get_sample_mock_node();
         let block_hash = SAMPLE_BLOCK_HASH.to_string();
         let rpc_call_json = get_block_by_block_hash_json(block_hash)
             .unwrap();
-        let reqwest_response = make_rpc_call(WORKING_ENDPOINT, rpc_call_json)
-            .unwrap();
+        let reqwest_response = make_rpc_call(
+            &mock_node.get_endpoint(),
+            rpc_call_json
+        ).unwrap();
         let response_text = get_response_text(reqwest_response)
             .unwrap();
         let rpc_result_struct = deserialize_to_block_rpc_response(response_text)
@@ -115,11 +131,14 @@ mod tests {
 
     #[test]
     fn should_deserialize_rpc_call_to_receipt_rpc_response_correctly() {
+        let mock_node = get_sample_mock_node();
         let tx_hash = SAMPLE_TX_HASH.to_string();
         let rpc_call_json = get_transaction_receipt_json(&tx_hash)
             .unwrap();
-        let reqwest_response = make_rpc_call(WORKING_ENDPOINT, rpc_call_json)
-            .unwrap();
+        let reqwest_response = make_rpc_call(
+            &mock_node.get_endpoint(),
+            rpc_call_json
+        ).unwrap();
         let response_text = get_response_text(reqwest_response)
             .unwrap();
         let rpc_result_struct = deserialize_to_receipt_rpc_response(response_text)
@@ -0,0 +1,441 @@
+#![cfg(test)]
+#![allow(dead_code)]
+
+use std::fs;
+use std::thread;
+use std::sync::Arc;
+use std::time::Duration;
+use std::collections::HashMap;
+use std::net::{
+    SocketAddr,
+    TcpStream,
+    TcpListener,
+};
+use std::sync::atomic::{
+    Ordering,
+    AtomicBool,
+};
+use serde_json::Value as Json;
+use crate::errors::AppError;
+use crate::types::Result;
+use crate::proof_server::{
+    read_http_request,
+    write_http_response,
+};
+
+pub const NULL_RESULT_RESPONSE: &str =
+    "{\"jsonrpc\":\"2.0\",\"id\":\"1\",\"result\":null}";
+
+#[derive(Clone, Debug, Default)]
+pub struct MockNodeFixtures {
+    pub receipts: HashMap<String, String>,
+    pub blocks_by_hash: HashMap<String, String>,
+    pub blocks_by_number: HashMap<u64, String>,
+    pub block_tags: HashMap<String, u64>,
+    pub beacon_blocks: HashMap<String, String>,
+    pub max_blocks_per_logs_query: Option<u64>,
+}
+
+impl MockNodeFixtures {
+    pub fn new() -> Self {
+        MockNodeFixtures::default()
+    }
+
+    pub fn add_block_from_file(self, path: &str) -> Result<Self> {
+        self.add_block_response(&fs::read_to_string(path)?)
+    }
+
+    pub fn add_block_response(mut self, response: &str) -> Result<Self> {
+        let response = response.to_string();
+        let json: Json = serde_json::from_str(&response)?;
+        let hash = get_string_from_result(&json, "hash")?;
+        let number = convert_hex_string_to_u64(
+            &get_string_from_result(&json, "number")?
+        )?;
+        self.blocks_by_hash.insert(hash.to_lowercase(), response.clone());
+        self.blocks_by_number.insert(number, response);
+        Ok(self)
+    }
+
+    pub fn add_receipt_from_file(mut self, path: &str) -> Result<Self> {
+        let response = fs::read_to_string(path)?;
+        let json: Json = serde_json::from_str(&response)?;
+        let tx_hash = get_string_from_result(&json, "transactionHash")?;
+        self.receipts.insert(tx_hash.to_lowercase(), response);
+        Ok(self)
+    }
+
+    pub fn add_receipts_from_dir(self, path: &str) -> Result<Self> {
+        fs::read_dir(path)?
+            .map(|entry| Ok(entry?.path()))
+            .collect::<Result<Vec<_>>>()?
+            .iter()
+            .filter(|path| path.is_file())
+            .try_fold(self, |fixtures, path|
+                fixtures.add_receipt_from_file(&path.to_string_lossy())
+            )
+    }
+
+    pub fn add_raw_receipt_response(
+        mut self,
+        tx_hash: &str,
+        response: &str,
+    ) -> Self {
+        self.receipts.insert(tx_hash.to_lowercase(), response.to_string());
+        self
+    }
+
+    pub fn add_raw_block_response(
+        mut self,
+        block_hash: &str,
+        response: &str,
+    ) -> Self {
+        self.blocks_by_hash
+            .insert(block_hash.to_lowercase(), response.to_string());
+        self
+    }
+
+    pub fn add_block_tag(mut self, tag: &str, block_number: u64) -> Self {
+        self.block_tags.insert(tag.to_string(), block_number);
+        self
+    }
+
+    pub fn set_max_blocks_per_logs_query(mut self, max_blocks: u64) -> Self {
+        self.max_blocks_per_logs_query = Some(max_blocks);
+        self
+    }
+
+    pub fn add_beacon_block_from_file(mut self, path: &str) -> Result<Self> {
+        let response = fs::read_to_string(path)?;
+        let json: Json = serde_json::from_str(&response)?;
+        match json["data"]["message"]["slot"].as_str() {
+            Some(slot) => {
+                self.beacon_blocks.insert(slot.to_string(), response);
+                Ok(self)
+            }
+            None => Err(AppError::Custom(
+                format!("✘ Beacon block fixture {} has no slot!", path)
+            ))
+        }
+    }
+
+    fn get_latest_block_response(&self) -> Option<&String> {
+        self.blocks_by_number
+            .keys()
+            .max()
+            .and_then(|number| self.blocks_by_number.get(number))
+    }
+
+    fn get_block_by_number_response(&self, param: &Json) -> Option<&String> {
+        match param.as_str() {
+            Some("latest") => self.get_latest_block_response(),
+            Some(tag) if self.block_tags.contains_key(tag) => self
+                .block_tags
+                .get(tag)
+                .and_then(|number| self.blocks_by_number.get(number)),
+            Some(hex) => convert_hex_string_to_u64(hex)
+                .ok()
+                .and_then(|number| self.blocks_by_number.get(&number)),
+            None => None,
+        }
+    }
+
+    fn get_logs_response(&self, filter: &Json) -> String {
+        let get_number = |key: &str| filter[key]
+            .as_str()
+            .and_then(|hex| convert_hex_string_to_u64(hex).ok());
+        let (from_block, to_block) = (get_number("fromBlock"), get_number("toBlock"));
+        let matches_value = |expected: &Json, actual: &Json| match expected {
+            Json::Null => true,
+            Json::Array(values) => values.iter().any(|value| is_same_hex(value, actual)),
+            value => is_same_hex(value, actual),
+        };
+        let mut logs: Vec<Json> = self.receipts
+            .values()
+            .filter_map(|response| serde_json::from_str::<Json>(response).ok())
+            .filter_map(|json| json["result"]["logs"].as_array().cloned())
+            .flatten()
+            .filter(|log| {
+                let block_number = log["blockNumber"]
+                    .as_str()
+                    .and_then(|hex| convert_hex_string_to_u64(hex).ok())
+                    .unwrap_or_default();
+                from_block.map(|from| block_number >= from).unwrap_or(true) &&
+                to_block.map(|to| block_number <= to).unwrap_or(true) &&
+                matches_value(&filter["address"], &log["address"]) &&
+                filter["topics"]
+                    .as_array()
+                    .map(|topics| topics
+                        .iter()
+                        .enumerate()
+                        .all(|(i, topic)| matches_value(topic, &log["topics"][i]))
+                    )
+                    .unwrap_or(true)
+            })
+            .collect();
+        logs.sort_by_key(|log| (
+            log["blockNumber"].as_str().and_then(|hex| convert_hex_string_to_u64(hex).ok()),
+            log["logIndex"].as_str().and_then(|hex| convert_hex_string_to_u64(hex).ok()),
+        ));
+        let num_blocks = to_block
+            .unwrap_or(u64::max_value())
+            .saturating_sub(from_block.unwrap_or_default()) + 1;
+        match self.max_blocks_per_logs_query {
+            Some(max_blocks) if num_blocks > max_blocks => json!({
+                "id": "1",
+                "jsonrpc": "2.0",
+                "error": {
+                    "code": -32005,
+                    "message": format!("block range exceeds the limit of {} blocks", max_blocks),
+                },
+            }).to_string(),
+            _ => json!({ "id": "1", "jsonrpc": "2.0", "result": logs }).to_string(),
+        }
+    }
+
+    pub fn get_response_to_request(&self, request: &Json) -> String {
+        let first_param = &request["params"][0];
+        let maybe_response = match request["method"].as_str() {
+            Some("eth_getBlockByHash") => first_param
+                .as_str()
+                .and_then(|hash| self.blocks_by_hash.get(&hash.to_lowercase())),
+            Some("eth_getBlockByNumber") =>
+                self.get_block_by_number_response(first_param),
+            Some("eth_getTransactionReceipt") => first_param
+                .as_str()
+                .and_then(|hash| self.receipts.get(&hash.to_lowercase())),
+            Some("eth_getLogs") => return self.get_logs_response(first_param),
+            _ => return get_method_not_found_response(&request["method"]),
+        };
+        match maybe_response {
+            Some(response) => response.clone(),
+            None => NULL_RESULT_RESPONSE.to_string(),
+        }
+    }
+}
+
+pub struct MockNode {
+    address: SocketAddr,
+    is_shutting_down: Arc<AtomicBool>,
+}
+
+impl MockNode {
+    pub fn start(fixtures: MockNodeFixtures) -> Result<MockNode> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let address = listener.local_addr()?;
+        let is_shutting_down = Arc::new(AtomicBool::new(false));
+        let shutdown_flag = is_shutting_down.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                if shutdown_flag.load(Ordering::SeqCst) {
+                    break;
+                }
+                if let Ok(stream) = stream {
+                    let _ = handle_connection(stream, &fixtures);
+                }
+            }
+        });
+        Ok(MockNode { address, is_shutting_down })
+    }
+
+    pub fn get_endpoint(&self) -> String {
+        format!("http://{}", self.address)
+    }
+}
+
+impl Drop for MockNode {
+    fn drop(&mut self) {
+        self.is_shutting_down.store(true, Ordering::SeqCst);
+        let _ = TcpStream::connect(self.address); // NOTE: Unblocks the listener.
+    }
+}
+
+fn get_string_from_result(json: &Json, key: &str) -> Result<String> {
+    match json["result"][key].as_str() {
+        Some(string) => Ok(string.to_string()),
+        None => Err(AppError::Custom(
+            format!("✘ Fixture has no `{}` field in its result!", key)
+        ))
+    }
+}
+
+fn is_same_hex(expected: &Json, actual: &Json) -> bool {
+    match (expected.as_str(), actual.as_str()) {
+        (Some(expected), Some(actual)) => expected.to_lowercase() == actual.to_lowercase(),
+        _ => false,
+    }
+}
+
+fn convert_hex_string_to_u64(hex: &str) -> Result<u64> {
+    match u64::from_str_radix(&hex.replace("0x", ""), 16) {
+        Ok(number) => Ok(number),
+        Err(_) => Err(AppError::Custom(
+            format!("✘ Cannot convert {} to a block number!", hex)
+        ))
+    }
+}
+
+fn get_method_not_found_response(method: &Json) -> String {
+    json!({
+        "id": "1",
+        "jsonrpc": "2.0",
+        "error": {
+            "code": -32601,
+            "message": format!("Method {} not found", method),
+        },
+    }).to_string()
+}
+
+fn get_beacon_block_id_from_request_line(request_line: &str) -> Option<&str> {
+    let beacon_blocks_path = "GET /eth/v2/beacon/blocks/";
+    match request_line.starts_with(beacon_blocks_path) {
+        false => None,
+        true => request_line[beacon_blocks_path.len()..]
+            .split(&[' ', '?'][..])
+            .next(),
+    }
+}
+
+fn handle_connection(
+    stream: TcpStream,
+    fixtures: &MockNodeFixtures,
+) -> Result<()> {
+    let (request_line, request_body) = read_http_request(&stream, Duration::from_secs(5))?;
+    let maybe_beacon_block_id = get_beacon_block_id_from_request_line(&request_line);
+    let (status, response_body) = match maybe_beacon_block_id {
+        Some(block_id) => match fixtures.beacon_blocks.get(block_id) {
+            Some(beacon_block) => ("200 OK", beacon_block.clone()),
+            None => ("404 Not Found", json!({ "code": 404 }).to_string()),
+        },
+        None => match serde_json::from_str::<Json>(&request_body) {
+            Ok(request) => ("200 OK", fixtures.get_response_to_request(&request)),
+            Err(_) => ("200 OK", get_method_not_found_response(&Json::Null)),
+        },
+    };
+    write_http_response(&stream, status, &response_body)
+}
+
+mod tests {
+    use super::*;
+    use crate::test_utils::{
+        SAMPLE_TX_HASH,
+        SAMPLE_BLOCK_HASH,
+        SAMPLE_BLOCK_JSON_PATH,
+        SAMPLE_RECEIPT_JSON_PATH,
+        SAMPLE_BLOCK_JSON_1_PATH,
+        SAMPLE_RECEIPT_JSONS_1_PATH,
+        SAMPLE_BEACON_BLOCK_JSON_PATH,
+        get_sample_tx_hashes_1,
+    };
+
+    fn get_request(method: &str, param: &str) -> Json {
+        json!({
+            "id": "1",
+            "jsonrpc": "2.0",
+            "method": method,
+            "params": [ param, false ],
+        })
+    }
+
+    #[test]
+    fn should_load_block_fixture_by_hash_and_number() {
+        let fixtures = MockNodeFixtures::new()
+            .add_block_from_file(SAMPLE_BLOCK_JSON_PATH)
+            .unwrap();
+        assert!(fixtures.blocks_by_hash.contains_key(SAMPLE_BLOCK_HASH));
+        assert!(fixtures.blocks_by_number.contains_key(&0x7da175));
+    }
+
+    #[test]
+    fn should_load_receipt_fixtures_from_dir() {
+        let fixtures = MockNodeFixtures::new()
+            .add_receipts_from_dir(SAMPLE_RECEIPT_JSONS_1_PATH)
+            .unwrap();
+        assert!(fixtures.receipts.len() == get_sample_tx_hashes_1().len());
+        get_sample_tx_hashes_1()
+            .iter()
+            .for_each(|hash| assert!(fixtures.receipts.contains_key(hash)));
+    }
+
+    #[test]
+    fn should_respond_with_latest_block() {
+        let fixtures = MockNodeFixtures::new()
+            .add_block_from_file(SAMPLE_BLOCK_JSON_PATH)
+            .and_then(|fixtures|
+                fixtures.add_block_from_file(SAMPLE_BLOCK_JSON_1_PATH)
+            )
+            .unwrap();
+        let request = get_request("eth_getBlockByNumber", "latest");
+        let response = fixtures.get_response_to_request(&request);
+        let result: Json = serde_json::from_str(&response).unwrap();
+        assert!(result["result"]["number"] == "0x81c1fc");
+    }
+
+    #[test]
+    fn should_respond_with_tagged_block() {
+        let fixtures = MockNodeFixtures::new()
+            .add_block_from_file(SAMPLE_BLOCK_JSON_PATH)
+            .and_then(|fixtures|
+                fixtures.add_block_from_file(SAMPLE_BLOCK_JSON_1_PATH)
+            )
+            .unwrap()
+            .add_block_tag("finalized", 0x7da175);
+        let request = get_request("eth_getBlockByNumber", "finalized");
+        let response = fixtures.get_response_to_request(&request);
+        let result: Json = serde_json::from_str(&response).unwrap();
+        assert!(result["result"]["number"] == "0x7da175");
+    }
+
+    #[test]
+    fn should_respond_with_null_result_for_missing_receipt() {
+        let fixtures = MockNodeFixtures::new()
+            .add_receipt_from_file(SAMPLE_RECEIPT_JSON_PATH)
+            .unwrap();
+        let missing_hash = format!("0x{}", "00".repeat(32));
+        let request = get_request("eth_getTransactionReceipt", &missing_hash);
+        let result = fixtures.get_response_to_request(&request);
+        assert!(result == NULL_RESULT_RESPONSE);
+    }
+
+    #[test]
+    fn should_respond_with_error_for_unknown_method() {
+        let fixtures = MockNodeFixtures::new();
+        let request = get_request("eth_unknownMethod", SAMPLE_TX_HASH);
+        let result = fixtures.get_response_to_request(&request);
+        assert!(result.contains("error"));
+        assert!(result.contains("eth_unknownMethod"));
+    }
+
+    #[test]
+    fn should_serve_fixtures_over_http() {
+        let fixtures = MockNodeFixtures::new()
+            .add_receipt_from_file(SAMPLE_RECEIPT_JSON_PATH)
+            .unwrap();
+        let expected_result = fixtures.receipts[SAMPLE_TX_HASH].clone();
+        let mock_node = MockNode::start(fixtures).unwrap();
+        let client = reqwest::Client::new();
+        let result = client
+            .post(&mock_node.get_endpoint())
+            .json(&get_request("eth_getTransactionReceipt", SAMPLE_TX_HASH))
+            .send()
+            .and_then(|mut response| response.text())
+            .unwrap();
+        assert!(result == expected_result);
+    }
+
+    #[test]
+    fn should_serve_beacon_blocks_over_http() {
+        let fixtures = MockNodeFixtures::new()
+            .add_beacon_block_from_file(SAMPLE_BEACON_BLOCK_JSON_PATH)
+            .unwrap();
+        let mock_node = MockNode::start(fixtures).unwrap();
+        let client = reqwest::Client::new();
+        let get_status = |slot: &str| client
+            .get(&format!("{}/eth/v2/beacon/blocks/{}", mock_node.get_endpoint(), slot))
+            .send()
+            .unwrap()
+            .status();
+        assert!(get_status("9000").is_success());
+        assert!(get_status("9001") == reqwest::StatusCode::NOT_FOUND);
+    }
+}
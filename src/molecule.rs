@@ -0,0 +1,458 @@
+use std::convert::{
+    TryFrom,
+    TryInto,
+};
+use ethereum_types::{
+    H160,
+    H256,
+};
+use crate::errors::AppError;
+use crate::decode_proof::get_proof_nodes;
+use crate::get_header_chain::get_verified_rlp_encoded_header;
+use crate::utils::{
+    convert_bytes_to_hex,
+    convert_hex_to_bytes,
+};
+use crate::types::{
+    Block,
+    Bytes,
+    Result,
+    NodeStack,
+    EthSpvProof,
+};
+
+// NOTE: Encodings follow the schema in `schemas/eth_spv.mol`.
+const NUM_HEADER_FIELDS: usize = 4;
+const NUM_ETH_SPV_PROOF_FIELDS: usize = 14;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct EthHeader {
+    pub header_data: Bytes,
+    pub hash: H256,
+    pub number: u64,
+    pub receipts_root: H256,
+}
+
+fn get_molecule_err(msg: &str) -> AppError {
+    AppError::Custom(format!("✘ Invalid molecule data: {}!", msg))
+}
+
+fn encode_u32(value: usize) -> Bytes {
+    (value as u32).to_le_bytes().to_vec()
+}
+
+fn decode_u32(bytes: &[u8], offset: usize) -> Result<usize> {
+    bytes
+        .get(offset..offset + 4)
+        .ok_or_else(|| get_molecule_err("too short for a 4 byte length"))
+        .map(|slice| u32::from_le_bytes(slice.try_into().unwrap()) as usize)
+}
+
+fn decode_fixed_bytes(bytes: &[u8], length: usize) -> Result<Bytes> {
+    match bytes.len() == length {
+        true => Ok(bytes.to_vec()),
+        false => Err(get_molecule_err(
+            &format!("expected {} bytes, got {}", length, bytes.len())
+        )),
+    }
+}
+
+pub fn encode_uint64(value: u64) -> Bytes {
+    value.to_le_bytes().to_vec()
+}
+
+pub fn decode_uint64(bytes: &[u8]) -> Result<u64> {
+    decode_fixed_bytes(bytes, 8)
+        .map(|bytes| u64::from_le_bytes(bytes[..].try_into().unwrap()))
+}
+
+pub fn encode_uint128(value: u128) -> Bytes {
+    value.to_le_bytes().to_vec()
+}
+
+pub fn decode_uint128(bytes: &[u8]) -> Result<u128> {
+    decode_fixed_bytes(bytes, 16)
+        .map(|bytes| u128::from_le_bytes(bytes[..].try_into().unwrap()))
+}
+
+pub fn encode_bytes(bytes: &[u8]) -> Bytes {
+    [encode_u32(bytes.len()), bytes.to_vec()].concat()
+}
+
+pub fn decode_bytes(bytes: &[u8]) -> Result<Bytes> {
+    decode_u32(bytes, 0)
+        .and_then(|length| decode_fixed_bytes(&bytes[4..], length))
+}
+
+pub fn encode_dynvec(items: &[Bytes]) -> Bytes {
+    let header_size = 4 * (items.len() + 1);
+    let total_size = header_size + items.iter().map(Vec::len).sum::<usize>();
+    let mut encoded = encode_u32(total_size);
+    items
+        .iter()
+        .fold(header_size, |offset, item| {
+            encoded.append(&mut encode_u32(offset));
+            offset + item.len()
+        });
+    items
+        .iter()
+        .for_each(|item| encoded.extend_from_slice(item));
+    encoded
+}
+
+pub fn decode_dynvec(bytes: &[u8]) -> Result<Vec<Bytes>> {
+    let total_size = decode_u32(bytes, 0)?;
+    if total_size != bytes.len() {
+        return Err(get_molecule_err(
+            &format!("total size is {} but got {} bytes", total_size, bytes.len())
+        ))
+    }
+    if total_size == 4 {
+        return Ok(vec![])
+    }
+    let header_size = decode_u32(bytes, 4)?;
+    if header_size % 4 != 0 || header_size < 8 || header_size > total_size {
+        return Err(get_molecule_err(&format!("bad header size {}", header_size)))
+    }
+    let mut offsets = (1..header_size / 4)
+        .map(|i| decode_u32(bytes, 4 * i))
+        .collect::<Result<Vec<usize>>>()?;
+    offsets.push(total_size);
+    offsets
+        .windows(2)
+        .map(|pair| match pair[0] <= pair[1] {
+            true => Ok(bytes[pair[0]..pair[1]].to_vec()),
+            false => Err(get_molecule_err("offsets are not in order")),
+        })
+        .collect()
+}
+
+pub fn encode_table(fields: &[Bytes]) -> Bytes {
+    encode_dynvec(fields)
+}
+
+pub fn decode_table(bytes: &[u8], num_fields: usize) -> Result<Vec<Bytes>> {
+    decode_dynvec(bytes)
+        .and_then(|fields| match fields.len() == num_fields {
+            true => Ok(fields),
+            false => Err(get_molecule_err(
+                &format!("expected {} table fields, got {}", num_fields, fields.len())
+            )),
+        })
+}
+
+pub fn encode_bytes_vec(items: &[Bytes]) -> Bytes {
+    encode_dynvec(
+        &items
+            .iter()
+            .map(|item| encode_bytes(item))
+            .collect::<Vec<Bytes>>()
+    )
+}
+
+pub fn decode_bytes_vec(bytes: &[u8]) -> Result<Vec<Bytes>> {
+    decode_dynvec(bytes)?
+        .iter()
+        .map(|item| decode_bytes(item))
+        .collect()
+}
+
+pub fn encode_receipt_branch(branch: &NodeStack) -> Result<Bytes> {
+    branch
+        .iter()
+        .map(|node| node.get_rlp_encoding())
+        .collect::<Result<Vec<Bytes>>>()
+        .map(|encoded_nodes| encode_bytes_vec(&encoded_nodes))
+}
+
+pub fn decode_receipt_branch(bytes: &[u8]) -> Result<Vec<Bytes>> {
+    decode_bytes_vec(bytes)
+}
+
+pub fn encode_block_header(block: &Block) -> Result<Bytes> {
+    get_verified_rlp_encoded_header(block)
+        .map(|header_data|
+            encode_table(&[
+                encode_bytes(&header_data),
+                block.hash.as_bytes().to_vec(),
+                encode_uint64(block.number.as_u64()),
+                block.receipts_root.as_bytes().to_vec(),
+            ])
+        )
+}
+
+pub fn decode_block_header(bytes: &[u8]) -> Result<EthHeader> {
+    let fields = decode_table(bytes, NUM_HEADER_FIELDS)?;
+    Ok(
+        EthHeader {
+            header_data: decode_bytes(&fields[0])?,
+            hash: H256::from_slice(&decode_fixed_bytes(&fields[1], 32)?),
+            number: decode_uint64(&fields[2])?,
+            receipts_root: H256::from_slice(&decode_fixed_bytes(&fields[3], 32)?),
+        }
+    )
+}
+
+fn get_bytes_from_hex_field(hex: &str) -> Result<Bytes> {
+    match hex.is_empty() {
+        true => Ok(vec![]),
+        false => convert_hex_to_bytes(hex.to_string()),
+    }
+}
+
+fn get_proof_nodes_from_hex(hex_proof: &str) -> Result<Vec<Bytes>> {
+    match hex_proof.is_empty() {
+        true => Ok(vec![]),
+        false => convert_hex_to_bytes(hex_proof.to_string())
+            .and_then(|proof| get_proof_nodes(&proof)),
+    }
+}
+
+fn get_log_index_as_u64(log_index: i32) -> Result<u64> {
+    match log_index < 0 {
+        true => Err(AppError::Custom(
+            format!("✘ Cannot molecule encode proof with log index {}!", log_index)
+        )),
+        false => Ok(log_index as u64),
+    }
+}
+
+fn get_log_index_from_u64(log_index: u64) -> Result<i32> {
+    i32::try_from(log_index)
+        .map_err(|_| get_molecule_err(&format!("log index {} does not fit in an i32", log_index)))
+}
+
+pub fn encode_eth_spv_proof(eth_spv_proof: &EthSpvProof) -> Result<Bytes> {
+    Ok(
+        encode_table(&[
+            encode_uint64(get_log_index_as_u64(eth_spv_proof.log_index)?),
+            encode_bytes(&get_bytes_from_hex_field(&eth_spv_proof.log_entry_data)?),
+            encode_uint64(eth_spv_proof.receipt_index),
+            encode_bytes(&get_bytes_from_hex_field(&eth_spv_proof.receipt_data)?),
+            encode_bytes(&get_bytes_from_hex_field(&eth_spv_proof.header_data)?),
+            encode_bytes_vec(&get_proof_nodes_from_hex(&eth_spv_proof.proof)?),
+            eth_spv_proof.token.as_bytes().to_vec(),
+            encode_uint128(eth_spv_proof.lock_amount),
+            encode_uint128(eth_spv_proof.bridge_fee),
+            encode_bytes(eth_spv_proof.ckb_recipient.as_bytes()),
+            eth_spv_proof.block_hash.as_bytes().to_vec(),
+            encode_bytes(&eth_spv_proof.recipient_lockscript),
+            encode_bytes(&eth_spv_proof.replay_resist_outpoint),
+            encode_bytes(&eth_spv_proof.sudt_extra_data),
+        ])
+    )
+}
+
+fn get_hex_proof_from_nodes(nodes: Vec<Bytes>) -> String {
+    match nodes.is_empty() {
+        true => String::new(),
+        false => {
+            let mut rlp_stream = rlp::RlpStream::new_list(nodes.len());
+            nodes
+                .iter()
+                .for_each(|node| { rlp_stream.append_raw(node, 1); });
+            convert_bytes_to_hex(rlp_stream.out())
+        }
+    }
+}
+
+pub fn decode_eth_spv_proof(bytes: &[u8]) -> Result<EthSpvProof> {
+    let fields = decode_table(bytes, NUM_ETH_SPV_PROOF_FIELDS)?;
+    Ok(
+        EthSpvProof {
+            log_index: get_log_index_from_u64(decode_uint64(&fields[0])?)?,
+            log_entry_data: convert_bytes_to_hex(decode_bytes(&fields[1])?),
+            receipt_index: decode_uint64(&fields[2])?,
+            receipt_data: convert_bytes_to_hex(decode_bytes(&fields[3])?),
+            header_data: convert_bytes_to_hex(decode_bytes(&fields[4])?),
+            proof: get_hex_proof_from_nodes(decode_bytes_vec(&fields[5])?),
+            token: H160::from_slice(&decode_fixed_bytes(&fields[6], 20)?),
+            lock_amount: decode_uint128(&fields[7])?,
+            bridge_fee: decode_uint128(&fields[8])?,
+            ckb_recipient: String::from_utf8(decode_bytes(&fields[9])?)
+                .map_err(|_| get_molecule_err("`ckb_recipient` is not UTF-8"))?,
+            block_hash: H256::from_slice(&decode_fixed_bytes(&fields[10], 32)?),
+            recipient_lockscript: decode_bytes(&fields[11])?,
+            replay_resist_outpoint: decode_bytes(&fields[12])?,
+            sudt_extra_data: decode_bytes(&fields[13])?,
+        }
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::get_branch_from_trie::get_branch_from_trie;
+    use crate::get_hex_proof_from_branch::rlp_encode_node_stack;
+    use crate::test_utils::{
+        PROOF_1_INDEX,
+        get_expected_block,
+        get_sample_proof_1,
+        get_sample_tx_hashes_1,
+        get_valid_block_hash_h256,
+        SAMPLE_RECEIPT_JSONS_1_PATH,
+        get_sample_trie_with_sample_receipts,
+    };
+
+    fn get_sample_eth_spv_proof() -> EthSpvProof {
+        EthSpvProof {
+            log_index: 2,
+            log_entry_data: "f85894".to_string(),
+            receipt_index: 14,
+            receipt_data: "f90164".to_string(),
+            header_data: String::new(),
+            proof: get_sample_proof_1(),
+            token: H160::from_slice(&[0xaa; 20]),
+            lock_amount: 1_000_000,
+            bridge_fee: 1_000,
+            ckb_recipient: "ckt1qyq".to_string(),
+            block_hash: get_valid_block_hash_h256().unwrap(),
+            recipient_lockscript: vec![0x49, 0x00],
+            replay_resist_outpoint: vec![0x01, 0x02, 0x03],
+            sudt_extra_data: vec![],
+        }
+    }
+
+    #[test]
+    fn should_encode_bytes_as_fixvec() {
+        let result = encode_bytes(&[0x12, 0x34]);
+        assert!(result == vec![0x02, 0x00, 0x00, 0x00, 0x12, 0x34]);
+    }
+
+    #[test]
+    fn should_encode_dynvec_with_header_of_offsets() {
+        let items = vec![vec![0x01], vec![0x02, 0x03]];
+        let expected_result = vec![
+            0x0f, 0x00, 0x00, 0x00,
+            0x0c, 0x00, 0x00, 0x00,
+            0x0d, 0x00, 0x00, 0x00,
+            0x01, 0x02, 0x03,
+        ];
+        let result = encode_dynvec(&items);
+        assert!(result == expected_result);
+        assert!(decode_dynvec(&result).unwrap() == items);
+    }
+
+    #[test]
+    fn should_encode_empty_dynvec_as_total_size_only() {
+        let result = encode_dynvec(&[]);
+        assert!(result == vec![0x04, 0x00, 0x00, 0x00]);
+        assert!(decode_dynvec(&result).unwrap().is_empty());
+    }
+
+    #[test]
+    fn should_err_when_decoding_truncated_table() {
+        let encoded = encode_table(&[encode_uint64(1), encode_bytes(&[0xff])]);
+        match decode_table(&encoded[..encoded.len() - 1], 2) {
+            Err(AppError::Custom(e)) => assert!(e.contains("total size")),
+            _ => panic!("Truncated table should not decode!"),
+        }
+    }
+
+    #[test]
+    fn should_round_trip_receipt_branch() {
+        let trie = get_sample_trie_with_sample_receipts(
+            SAMPLE_RECEIPT_JSONS_1_PATH.to_string(),
+            get_sample_tx_hashes_1(),
+        );
+        let branch = get_branch_from_trie(trie, PROOF_1_INDEX).unwrap();
+        let result = decode_receipt_branch(&encode_receipt_branch(&branch).unwrap())
+            .unwrap();
+        let expected_result = get_proof_nodes(&rlp_encode_node_stack(&branch).unwrap())
+            .unwrap();
+        assert!(result == expected_result);
+    }
+
+    #[test]
+    fn should_round_trip_block_header() {
+        let block = get_expected_block();
+        let result = decode_block_header(&encode_block_header(&block).unwrap())
+            .unwrap();
+        assert!(result.hash == block.hash);
+        assert!(result.number == block.number.as_u64());
+        assert!(result.receipts_root == block.receipts_root);
+        assert!(result.header_data == get_verified_rlp_encoded_header(&block).unwrap());
+    }
+
+    #[test]
+    fn should_round_trip_eth_spv_proof() {
+        let eth_spv_proof = get_sample_eth_spv_proof();
+        let encoded = encode_eth_spv_proof(&eth_spv_proof).unwrap();
+        let result = decode_eth_spv_proof(&encoded).unwrap();
+        assert!(decode_u32(&encoded, 0).unwrap() == encoded.len());
+        assert!(result.proof == eth_spv_proof.proof);
+        assert!(result.token == eth_spv_proof.token);
+        assert!(result.lock_amount == eth_spv_proof.lock_amount);
+        assert!(result.ckb_recipient == eth_spv_proof.ckb_recipient);
+        assert!(result.sudt_extra_data == eth_spv_proof.sudt_extra_data);
+        assert!(encode_eth_spv_proof(&result).unwrap() == encoded);
+    }
+
+    fn get_golden_eth_spv_proof_bytes() -> Bytes {
+        // NOTE: Laid out by hand from the molecule spec for `ETHSPVProof` in `schemas/eth_spv.mol`.
+        hex::decode([
+            // Total size, then the offset of each of the 14 fields.
+            "ce000000", "3c000000", "44000000", "49000000", "51000000", "56000000",
+            "5a000000", "67000000", "7b000000", "8b000000", "9b000000", "a1000000",
+            "c1000000", "c6000000", "ca000000",
+            "0100000000000000",                                                 // log_index
+            "01000000c0",                                                       // log_entry_data
+            "0200000000000000",                                                 // receipt_index
+            "01000000c1",                                                       // receipt_data
+            "00000000",                                                         // header_data
+            "0d0000000800000001000000c0",                                       // proof
+            "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",                         // token
+            "e8030000000000000000000000000000",                                 // lock_amount
+            "01000000000000000000000000000000",                                 // bridge_fee
+            "02000000636b",                                                     // ckb_recipient
+            "1111111111111111111111111111111111111111111111111111111111111111", // block_hash
+            "0100000049",                                                       // recipient_lockscript
+            "00000000",                                                         // replay_resist_outpoint
+            "00000000",                                                         // sudt_extra_data
+        ].concat()).unwrap()
+    }
+
+    #[test]
+    fn should_encode_eth_spv_proof_to_golden_bytes() {
+        let eth_spv_proof = EthSpvProof {
+            log_index: 1,
+            log_entry_data: "c0".to_string(),
+            receipt_index: 2,
+            receipt_data: "c1".to_string(),
+            header_data: String::new(),
+            proof: "c1c0".to_string(),
+            token: H160::from_slice(&[0xaa; 20]),
+            lock_amount: 1_000,
+            bridge_fee: 1,
+            ckb_recipient: "ck".to_string(),
+            block_hash: H256::from_slice(&[0x11; 32]),
+            recipient_lockscript: vec![0x49],
+            replay_resist_outpoint: vec![],
+            sudt_extra_data: vec![],
+        };
+        let expected_result = get_golden_eth_spv_proof_bytes();
+        let result = encode_eth_spv_proof(&eth_spv_proof).unwrap();
+        assert!(result == expected_result);
+        assert!(decode_eth_spv_proof(&expected_result).unwrap().proof == eth_spv_proof.proof);
+    }
+
+    #[test]
+    fn should_err_when_decoding_log_index_too_large_for_i32() {
+        let mut encoded = get_golden_eth_spv_proof_bytes();
+        encoded[60..68].copy_from_slice(&encode_uint64(1 << 31));
+        match decode_eth_spv_proof(&encoded) {
+            Err(AppError::Custom(e)) => assert!(e.contains("does not fit in an i32")),
+            _ => panic!("Log index too large for an i32 should not decode!"),
+        }
+    }
+
+    #[test]
+    fn should_err_when_encoding_proof_without_log() {
+        let mut eth_spv_proof = get_sample_eth_spv_proof();
+        eth_spv_proof.log_index = -1;
+        match encode_eth_spv_proof(&eth_spv_proof) {
+            Err(AppError::Custom(e)) => assert!(e.contains("log index -1")),
+            _ => panic!("Proof without a log should not be encoded!"),
+        }
+    }
+}
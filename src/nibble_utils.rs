@@ -1,5 +1,4 @@
-use std::fmt;
-use crate::errors::AppError;
+use receipt_proof_verifier::nibble_utils;
 use crate::types::{
     Byte,
     Bytes,
@@ -12,41 +11,14 @@ use crate::constants::{
     NUM_BITS_IN_NIBBLE,
     NUM_NIBBLES_IN_BYTE,
 };
-
-#[derive(Clone, Eq)]
-pub struct Nibbles {
-    pub data: Bytes,
-    pub offset: usize,
-}
-
-impl PartialEq for Nibbles {
-    fn eq(&self, other: &Self) -> bool {
-        self.data == other.data &&
-        self.offset == other.offset
-    }
-}
-
-impl fmt::Debug for Nibbles {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self == &EMPTY_NIBBLES {
-            true => write!(f, "Nibble array is empty!")?,
-            false => for i in 0..get_length_in_nibbles(&self) {
-                write!(
-                    f,
-                    "0x{:01x} ",
-                    get_nibble_at_index(&self, i).unwrap()
-                )?;
-            }
-        };
-        Ok(())
-    }
-}
-
-impl Nibbles {
-    pub fn len(&self) -> usize {
-        get_length_in_nibbles(&self)
-    }
-}
+pub use receipt_proof_verifier::nibble_utils::{
+    Nibbles,
+    get_nibbles_from_bytes,
+    get_length_in_nibbles,
+    set_nibble_offset_to_one,
+    set_nibble_offset_to_zero,
+    get_nibbles_from_offset_bytes,
+};
 
 pub fn get_common_prefix_nibbles(
     nibbles_a: Nibbles,
@@ -220,22 +192,6 @@ fn remove_last_byte_from_nibbles(nibbles: Nibbles) -> Nibbles {
     }
 }
 
-pub fn set_nibble_offset_to_zero(nibbles: Nibbles) -> Nibbles {
-    Nibbles { data: nibbles.data, offset: 0 }
-}
-
-pub fn set_nibble_offset_to_one(nibbles: Nibbles) -> Nibbles {
-    Nibbles { data: nibbles.data, offset: 1 }
-}
-
-pub fn get_nibbles_from_bytes(nibbles: Bytes) -> Nibbles {
-    Nibbles { data: nibbles, offset: 0 }
-}
-
-pub fn get_nibbles_from_offset_bytes(nibbles: Bytes) -> Nibbles {
-    Nibbles { data: nibbles, offset: 1 }
-}
-
 pub fn replace_nibble_in_nibbles_at_nibble_index(
     nibbles: Nibbles,
     replacement_nibble: Nibbles,
@@ -316,10 +272,6 @@ fn merge_nibbles_from_bytes(
     high_nibble_byte ^ ((high_nibble_byte ^ low_nibble_byte) & HIGH_NIBBLE_MASK)
 }
 
-pub fn get_length_in_nibbles(nibbles: &Nibbles) -> usize {
-    nibbles.data.len() * 2 - nibbles.offset
-}
-
 pub fn split_at_first_nibble(nibbles: &Nibbles) -> Result<(Nibbles, Nibbles)> {
     match get_length_in_nibbles(&nibbles) > 0 {
         false => Ok((EMPTY_NIBBLES, EMPTY_NIBBLES)),
@@ -337,24 +289,7 @@ pub fn get_nibble_at_index(
     nibbles: &Nibbles,
     nibble_index: usize
 ) -> Result<Byte> {
-    match nibble_index > get_length_in_nibbles(&nibbles) {
-        true => Err(AppError::Custom(
-            format!(
-                "✘ Index {} is out-of-bounds in nibble vector!",
-                nibble_index
-            )
-        )),
-        _ => match nibbles.offset {
-            0 => match nibble_index % 2 {
-                0 => get_high_nibble_from_byte(&nibbles, &nibble_index),
-                _ => get_low_nibble_from_byte(&nibbles, &nibble_index),
-            }
-            _ => match nibble_index % 2 {
-                0 => get_low_nibble_from_byte(&nibbles, &nibble_index),
-                _ => get_high_nibble_from_byte(&nibbles, &(nibble_index + 1)),
-            }
-        }
-    }
+    Ok(nibble_utils::get_nibble_at_index(nibbles, nibble_index)?)
 }
 
 fn get_byte_containing_nibble_at_nibble_index(
@@ -364,10 +299,6 @@ fn get_byte_containing_nibble_at_nibble_index(
     Ok(nibbles.data[convert_nibble_index_to_byte_index(nibbles, nibble_index)])
 }
 
-fn mask_higher_nibble(byte: Byte) -> Byte {
-    byte & HIGH_NIBBLE_MASK
-}
-
 fn shift_nibble_right(byte: Byte) -> Byte {
     byte >> NUM_BITS_IN_NIBBLE
 }
@@ -376,16 +307,6 @@ fn shift_nibble_left(byte: Byte) -> Byte {
     byte << NUM_BITS_IN_NIBBLE
 }
 
-fn get_low_nibble_from_byte(nibbles: &Nibbles, nibble_index: &usize) -> Result<Byte> {
-    get_byte_containing_nibble_at_nibble_index(nibbles, nibble_index)
-        .map(mask_higher_nibble)
-}
-
-fn get_high_nibble_from_byte(nibbles: &Nibbles, nibble_index: &usize) -> Result<Byte> {
-    get_byte_containing_nibble_at_nibble_index(nibbles, nibble_index)
-        .map(shift_nibble_right)
-}
-
 pub fn prefix_nibbles_with_byte(
     nibbles: Nibbles,
     mut vec_including_prefix_byte: Vec<u8>
@@ -449,6 +370,7 @@ pub fn convert_nibble_to_usize(nibbles: Nibbles) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::errors::AppError;
     const EXPECTED_NIBBLES: [u8; 14] = [
         0x01u8, 0x02u8, 0x03u8, 0x04u8, 0x05u8, 0x06u8, 0x07u8,
         0x08u8, 0x09u8, 0x0au8, 0x0bu8, 0x0cu8, 0x0du8, 0x0eu8,
@@ -584,31 +506,27 @@ mod tests {
     fn should_mask_higher_nibble_correctly() {
         let test_byte = 174u8;      // [10101110]
         let expected_result = 14u8; // [00001110]
-        let result = mask_higher_nibble(test_byte);
+        let nibbles = get_nibbles_from_offset_bytes(vec![test_byte]);
+        let result = get_nibble_at_index(&nibbles, 0)
+            .unwrap();
         assert!(result == expected_result);
     }
 
     #[test]
     fn should_get_low_nibble_from_byte_correctly() {
-        let index_of_byte = 0;
         let nibbles = get_nibbles_from_bytes(vec![174u8]); // [10101110]
         let expected_result = 14u8;  // [00001110]
-        let result = get_low_nibble_from_byte(
-            &nibbles,
-            &index_of_byte
-        ).unwrap();
+        let result = get_nibble_at_index(&nibbles, 1)
+            .unwrap();
         assert!(result == expected_result);
     }
 
     #[test]
     fn should_get_high_nibble_from_byte_correctly() {
-        let index_of_byte = 0;
         let nibbles = get_nibbles_from_bytes(vec![174u8]); // [10101110]
         let expected_result = 10u8;  // [00001010]
-        let result = get_high_nibble_from_byte(
-            &nibbles,
-            &index_of_byte
-        ).unwrap();
+        let result = get_nibble_at_index(&nibbles, 0)
+            .unwrap();
         assert!(result == expected_result);
     }
 
@@ -13,9 +13,58 @@ use crate::utils::convert_hex_to_h256;
 #[allow(non_snake_case)]
 #[derive(Debug, Deserialize)]
 pub struct CliArgs {
+    pub cmd_prove: bool,
+    pub cmd_prove_batch: bool,
+    pub cmd_verify: bool,
+    pub cmd_verify_bundle: bool,
+    pub cmd_header: bool,
+    pub cmd_decode: bool,
+    pub cmd_root: bool,
+    pub cmd_serve: bool,
+    pub cmd_watch: bool,
     pub flag_verbose: bool,
     pub arg_txhash: String,
+    pub arg_proof: String,
+    pub arg_block: String,
+    pub arg_bundle: String,
+    pub arg_file: Option<String>,
     pub flag_disableLogs: bool,
+    pub flag_endpoint: Option<String>,
+    pub flag_config: Option<String>,
+    pub flag_output: Option<String>,
+    pub flag_log_index: Option<usize>,
+    pub flag_signature: Option<String>,
+    pub flag_cache_dir: Option<String>,
+    pub flag_cache_size: Option<u64>,
+    pub flag_block_tag: Option<String>,
+    pub flag_confirmations: Option<u64>,
+    pub flag_root: Option<String>,
+    pub flag_block_hash: Option<String>,
+    pub flag_tx_index: Option<usize>,
+    pub flag_address: Option<String>,
+    pub flag_max_requests: Option<usize>,
+    pub flag_contract: Option<String>,
+    pub flag_topic: Option<String>,
+    pub flag_from_block: Option<u64>,
+    pub flag_out_dir: Option<String>,
+    pub flag_poll_interval: Option<u64>,
+    pub flag_from_files: Option<String>,
+    pub flag_receipts: Option<String>,
+}
+
+impl CliArgs {
+    pub fn is_prove_command(&self) -> bool {
+        self.cmd_prove || !(
+            self.cmd_prove_batch ||
+            self.cmd_verify ||
+            self.cmd_verify_bundle ||
+            self.cmd_header ||
+            self.cmd_decode ||
+            self.cmd_root ||
+            self.cmd_serve ||
+            self.cmd_watch
+        )
+    }
 }
 
 pub fn parse_cli_args() -> Result<CliArgs> {
@@ -58,12 +107,186 @@ pub fn parse_cli_args() -> Result<CliArgs> {
                 }
                 info!("✔ CLI Args parsed successfully!");
                 info!("✔ Verbose mode: {}", args.flag_verbose);
-                info!(
-                    "✔ Transaction hash: {}",
-                    convert_hex_to_h256(args.arg_txhash.clone())?
-                );
+                if args.is_prove_command() {
+                    info!(
+                        "✔ Transaction hash: {}",
+                        convert_hex_to_h256(args.arg_txhash.clone())?
+                    );
+                }
+                info!("✔ Cache directory: {:?}", args.flag_cache_dir);
                 Ok(args)
             },
             Err(e) => Err(AppError::Custom(e.to_string()))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_cli_args_from_argv(argv: Vec<&str>) -> Result<CliArgs> {
+        Docopt::new(USAGE_INFO)
+            .and_then(|d| d.argv(argv).deserialize())
+            .map_err(|e| AppError::Custom(e.to_string()))
+    }
+
+    #[test]
+    fn should_parse_bare_tx_hash_as_prove_command() {
+        let argv = vec!["rusty-receipt-proof-maker", "0x1234", "--output=json"];
+        let result = get_cli_args_from_argv(argv)
+            .unwrap();
+        assert!(result.is_prove_command());
+        assert!(!result.cmd_prove);
+        assert!(result.arg_txhash == "0x1234");
+        assert!(result.flag_output == Some("json".to_string()));
+    }
+
+    #[test]
+    fn should_parse_prove_command() {
+        let argv = vec!["rusty-receipt-proof-maker", "prove", "0x1234", "-v"];
+        let result = get_cli_args_from_argv(argv)
+            .unwrap();
+        assert!(result.cmd_prove);
+        assert!(result.is_prove_command());
+        assert!(result.arg_txhash == "0x1234");
+        assert!(result.flag_verbose);
+    }
+
+    #[test]
+    fn should_parse_abi_signature() {
+        let argv = vec![
+            "rusty-receipt-proof-maker",
+            "prove",
+            "0x1234",
+            "--output=abi",
+            "--signature=(bytes receipt, bytes[] proof)",
+        ];
+        let result = get_cli_args_from_argv(argv)
+            .unwrap();
+        assert!(result.flag_output == Some("abi".to_string()));
+        assert!(result.flag_signature == Some("(bytes receipt, bytes[] proof)".to_string()));
+    }
+
+    #[test]
+    fn should_parse_from_files_flags() {
+        let argv = vec![
+            "rusty-receipt-proof-maker",
+            "prove",
+            "0x1234",
+            "--from-files=block.json",
+            "--receipts=receipts",
+        ];
+        let result = get_cli_args_from_argv(argv)
+            .unwrap();
+        assert!(result.is_prove_command());
+        assert!(result.flag_from_files == Some("block.json".to_string()));
+        assert!(result.flag_receipts == Some("receipts".to_string()));
+    }
+
+    #[test]
+    fn should_parse_serve_command() {
+        let argv = vec![
+            "rusty-receipt-proof-maker",
+            "serve",
+            "--address=0.0.0.0:8000",
+            "--max-requests=4",
+            "--cache-dir=cache",
+        ];
+        let result = get_cli_args_from_argv(argv)
+            .unwrap();
+        assert!(result.cmd_serve);
+        assert!(!result.is_prove_command());
+        assert!(result.flag_address == Some("0.0.0.0:8000".to_string()));
+        assert!(result.flag_max_requests == Some(4));
+        assert!(result.flag_cache_dir == Some("cache".to_string()));
+    }
+
+    #[test]
+    fn should_parse_watch_command() {
+        let argv = vec![
+            "rusty-receipt-proof-maker",
+            "watch",
+            "--contract=0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+            "--from-block=8503804",
+            "--out-dir=proofs",
+            "--poll-interval=5",
+            "--confirmations=12",
+        ];
+        let result = get_cli_args_from_argv(argv)
+            .unwrap();
+        assert!(result.cmd_watch);
+        assert!(!result.is_prove_command());
+        assert!(result.flag_contract == Some("0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2".to_string()));
+        assert!(result.flag_topic.is_none());
+        assert!(result.flag_from_block == Some(8503804));
+        assert!(result.flag_out_dir == Some("proofs".to_string()));
+        assert!(result.flag_poll_interval == Some(5));
+        assert!(result.flag_confirmations == Some(12));
+    }
+
+    #[test]
+    fn should_parse_prove_batch_command() {
+        let argv = vec!["rusty-receipt-proof-maker", "prove-batch", "--confirmations=3"];
+        let result = get_cli_args_from_argv(argv)
+            .unwrap();
+        assert!(result.cmd_prove_batch);
+        assert!(!result.is_prove_command());
+        assert!(result.arg_file.is_none());
+        assert!(result.flag_confirmations == Some(3));
+    }
+
+    #[test]
+    fn should_parse_verify_command() {
+        let argv = vec![
+            "rusty-receipt-proof-maker",
+            "verify",
+            "proof.json",
+            "--root=0x1234",
+            "--tx-index=3",
+        ];
+        let result = get_cli_args_from_argv(argv)
+            .unwrap();
+        assert!(result.cmd_verify);
+        assert!(!result.is_prove_command());
+        assert!(result.arg_proof == "proof.json");
+        assert!(result.flag_root == Some("0x1234".to_string()));
+        assert!(result.flag_tx_index == Some(3));
+    }
+
+    #[test]
+    fn should_parse_verify_bundle_command() {
+        let argv = vec!["rusty-receipt-proof-maker", "verify-bundle", "bundle.json"];
+        let result = get_cli_args_from_argv(argv)
+            .unwrap();
+        assert!(result.cmd_verify_bundle);
+        assert!(!result.cmd_verify);
+        assert!(!result.is_prove_command());
+        assert!(result.arg_bundle == "bundle.json");
+    }
+
+    #[test]
+    fn should_parse_block_commands() {
+        ["header", "root"]
+            .iter()
+            .for_each(|command| {
+                let argv = vec!["rusty-receipt-proof-maker", command, "latest"];
+                let result = get_cli_args_from_argv(argv)
+                    .unwrap();
+                assert!(result.cmd_header == (*command == "header"));
+                assert!(result.cmd_root == (*command == "root"));
+                assert!(result.arg_block == "latest");
+            });
+    }
+
+    #[test]
+    fn should_not_parse_verify_command_with_root_and_block_hash() {
+        let argv = vec![
+            "rusty-receipt-proof-maker",
+            "verify",
+            "proof.json",
+            "--root=0x1234",
+            "--block-hash=0x5678",
+        ];
+        assert!(get_cli_args_from_argv(argv).is_err());
+    }
+}
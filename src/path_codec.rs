@@ -1,34 +1,24 @@
-use crate::errors::AppError;
+use receipt_proof_verifier::path_codec;
 use crate::types::{
     Bytes,
     Result,
 };
 use crate::nibble_utils::{
     Nibbles,
-    get_zero_nibble,
-    get_nibble_at_index,
     get_length_in_nibbles,
-    get_nibbles_from_bytes,
     convert_nibble_to_bytes,
     prefix_nibbles_with_byte,
-    set_nibble_offset_to_one,
     set_nibble_offset_to_zero,
-    slice_nibbles_at_nibble_index,
     get_nibbles_from_offset_bytes,
     replace_nibble_in_nibbles_at_nibble_index,
 };
-use crate::constants::{
-    LEAF_NODE_STRING,
-    EXTENSION_NODE_STRING,
+use receipt_proof_verifier::path_codec::{
+    EVEN_LENGTH_LEAF_PREFIX_BYTE,
+    ODD_LENGTH_LEAF_PREFIX_NIBBLE,
+    EVEN_LENGTH_EXTENSION_PREFIX_BYTE,
+    ODD_LENGTH_EXTENSION_PREFIX_NIBBLE,
 };
 
-const ODD_LENGTH_LEAF_PREFIX_NIBBLE: u8 = 3u8;       // [00000011]
-const EVEN_LENGTH_LEAF_PREFIX_BYTE: u8 = 32u8;       // [00100000]
-const EVEN_LENGTH_LEAF_PREFIX_NIBBLE: u8 = 2u8;      // [00000010]
-const EVEN_LENGTH_EXTENSION_PREFIX_BYTE: u8 = 0u8;   // [00000000]
-const ODD_LENGTH_EXTENSION_PREFIX_NIBBLE: u8 = 1u8;  // [00000001]
-const EVEN_LENGTH_EXTENSION_PREFIX_NIBBLE: u8 = 0u8; // [00000000]
-
 fn get_leaf_prefix_nibble() -> Nibbles {
     get_nibbles_from_offset_bytes(vec![ODD_LENGTH_LEAF_PREFIX_NIBBLE])
 }
@@ -73,36 +63,10 @@ fn encode_odd_length_leaf_path_from_nibbles(
     encode_odd_length_path_from_nibbles(nibbles, get_leaf_prefix_nibble())
 }
 
-fn decode_odd_length_nibbles(nibbles: Nibbles) -> Result<Nibbles> {
-    replace_nibble_in_nibbles_at_nibble_index(nibbles, get_zero_nibble(), 0)
-        .map(set_nibble_offset_to_one)
-}
-
 pub fn decode_path_to_nibbles_and_node_type(
     path: Bytes
 ) -> Result<(Nibbles, &'static str)> {
-    let nibbles = get_nibbles_from_bytes(path);
-    match get_nibble_at_index(&nibbles, 0)? {
-        EVEN_LENGTH_LEAF_PREFIX_NIBBLE => Ok((
-            slice_nibbles_at_nibble_index(nibbles, 2)?,
-            LEAF_NODE_STRING
-        )),
-        EVEN_LENGTH_EXTENSION_PREFIX_NIBBLE => Ok((
-            slice_nibbles_at_nibble_index(nibbles, 2)?,
-            EXTENSION_NODE_STRING
-        )),
-        ODD_LENGTH_LEAF_PREFIX_NIBBLE => Ok((
-            decode_odd_length_nibbles(nibbles)?,
-            LEAF_NODE_STRING
-        )),
-        ODD_LENGTH_EXTENSION_PREFIX_NIBBLE => Ok((
-            decode_odd_length_nibbles(nibbles)?,
-            EXTENSION_NODE_STRING
-        )),
-        _ => Err(AppError::Custom(
-            "✘ Malformed path - cannot determine node type!".to_string()
-        ))
-    }
+    Ok(path_codec::decode_path_to_nibbles_and_node_type(path)?)
 }
 
 pub fn encode_extension_path_from_nibbles(
@@ -127,6 +91,7 @@ pub fn encode_leaf_path_from_nibbles(
 mod tests {
     use super::*;
     use hex;
+    use crate::errors::AppError;
     use crate::nibble_utils::{
         get_nibbles_from_bytes,
         get_nibbles_from_offset_bytes,
@@ -287,8 +252,7 @@ mod tests {
     #[test]
     fn should_decode_odd_length_leaf_path_to_nibbles_correctly() {
         let (expected_nibbles, path) = get_odd_leaf_path_sample();
-        let encoded_nibbles = get_nibbles_from_bytes(path);
-        let result = decode_odd_length_nibbles(encoded_nibbles)
+        let (result, _) = decode_path_to_nibbles_and_node_type(path)
             .unwrap();
         assert!(result.data == expected_nibbles.data);
     }
@@ -296,8 +260,7 @@ mod tests {
     #[test]
     fn should_decode_odd_length_extension_path_to_nibbles_correctly() {
         let (expected_nibbles, path) = get_odd_extension_path_sample();
-        let encoded_nibbles = get_nibbles_from_bytes(path);
-        let result = decode_odd_length_nibbles(encoded_nibbles)
+        let (result, _) = decode_path_to_nibbles_and_node_type(path)
             .unwrap();
         assert!(result.data == expected_nibbles.data);
     }
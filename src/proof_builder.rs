@@ -0,0 +1,369 @@
+use ethereum_types::H256;
+use ethabi::{
+    Event,
+    RawLog,
+};
+use crate::patricia_trie::Trie;
+use crate::state::State;
+use crate::errors::AppError;
+use crate::transport::Transport;
+use crate::proof_bundle::ProofBundle;
+use crate::validate_tx_hash::validate_tx_hash;
+use crate::get_branch_from_trie::find_branch_in_trie;
+use crate::check_receipts_root::warn_on_receipts_root_mismatch;
+use crate::get_output_from_state::get_log_entry_data;
+use crate::get_tx_index::get_tx_index_from_transactions;
+use crate::get_hex_proof_from_branch::get_hex_proof_from_branch;
+use crate::get_receipts_trie::get_receipts_trie_from_receipts;
+use crate::constants::DEFAULT_MIN_CONFIRMATIONS;
+use crate::utils::{
+    convert_hex_to_h256,
+    convert_bytes_to_hex,
+};
+use crate::types::{
+    Log,
+    Block,
+    Result,
+    Receipt,
+    HexProof,
+    NodeStack,
+    ReceiptProof,
+    FinalityRequirement,
+};
+
+#[derive(Clone, Debug)]
+pub struct Proof {
+    pub tx_hash: H256,
+    pub tx_index: usize,
+    pub block: Block,
+    pub receipts: Vec<Receipt>,
+    pub trie: Trie,
+    pub branch: NodeStack,
+    pub event: Option<Event>,
+    pub log_index: Option<usize>,
+}
+
+impl Proof {
+    pub fn get_receipt(&self) -> &Receipt {
+        &self.receipts[self.tx_index]
+    }
+
+    pub fn get_hex_proof(&self) -> Result<HexProof> {
+        get_hex_proof_from_branch(&self.branch)
+    }
+
+    pub fn get_receipt_proof(&self) -> Result<ReceiptProof> {
+        self.get_hex_proof()
+            .map(|proof|
+                ReceiptProof {
+                    proof,
+                    tx_hash: self.tx_hash,
+                    tx_index: self.tx_index,
+                    block_hash: self.block.hash,
+                    block_number: self.block.number.as_u64(),
+                    receipts_root: self.block.receipts_root,
+                    receipt_data: convert_bytes_to_hex(rlp::encode(self.get_receipt())),
+                }
+            )
+    }
+
+    pub fn get_proof_bundle(&self) -> Result<ProofBundle> {
+        ProofBundle::new(&self.block, &self.receipts, &self.branch, self.tx_index)
+    }
+
+    fn get_log_index(&self) -> Result<usize> {
+        self.log_index
+            .ok_or_else(|| AppError::Custom(
+                format!("✘ No log of the given event in receipt of tx {:?}!", self.tx_hash)
+            ))
+    }
+
+    pub fn get_log(&self) -> Result<&Log> {
+        self.get_log_index()
+            .map(|log_index| &self.get_receipt().logs[log_index])
+    }
+
+    pub fn get_log_entry_data(&self) -> Result<String> {
+        self.get_log_index()
+            .and_then(|log_index| get_log_entry_data(self.get_receipt(), log_index))
+    }
+
+    pub fn get_decoded_log(&self) -> Result<ethabi::Log> {
+        let log = self.get_log()?;
+        match &self.event {
+            Some(event) => event
+                .parse_log(RawLog { topics: log.topics.clone(), data: log.data.clone() })
+                .map_err(|e| AppError::Custom(format!("✘ Could not decode log: {}", e))),
+            None => Err(AppError::Custom("✘ No event was given to decode the log with!".to_string())),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ProofBuilder {
+    transport: Transport,
+    maybe_tx_hash: Option<String>,
+    maybe_event: Option<Event>,
+    maybe_block: Option<Block>,
+    maybe_receipts: Option<Vec<Receipt>>,
+    finality_requirement: FinalityRequirement,
+}
+
+impl ProofBuilder {
+    pub fn new(transport: Transport) -> ProofBuilder {
+        ProofBuilder {
+            transport,
+            maybe_tx_hash: None,
+            maybe_event: None,
+            maybe_block: None,
+            maybe_receipts: None,
+            finality_requirement: FinalityRequirement::Confirmations(DEFAULT_MIN_CONFIRMATIONS),
+        }
+    }
+
+    pub fn tx<S: Into<String>>(mut self, tx_hash: S) -> ProofBuilder {
+        self.maybe_tx_hash = Some(tx_hash.into());
+        self
+    }
+
+    pub fn event(mut self, event: Event) -> ProofBuilder {
+        self.maybe_event = Some(event);
+        self
+    }
+
+    pub fn confirmations(mut self, min_confirmations: u64) -> ProofBuilder {
+        self.finality_requirement = FinalityRequirement::Confirmations(min_confirmations);
+        self
+    }
+
+    pub fn block_tag<S: Into<String>>(mut self, tag: S) -> ProofBuilder {
+        self.finality_requirement = FinalityRequirement::BlockTag(tag.into());
+        self
+    }
+
+    pub fn finality(mut self, finality_requirement: FinalityRequirement) -> ProofBuilder {
+        self.finality_requirement = finality_requirement;
+        self
+    }
+
+    pub fn block(mut self, block: Block) -> ProofBuilder {
+        self.maybe_block = Some(block);
+        self
+    }
+
+    pub fn receipts(mut self, receipts: Vec<Receipt>) -> ProofBuilder {
+        self.maybe_receipts = Some(receipts);
+        self
+    }
+
+    fn get_tx_hash(&self) -> Result<H256> {
+        match &self.maybe_tx_hash {
+            Some(tx_hash) => validate_tx_hash(tx_hash.clone())
+                .and_then(|_| convert_hex_to_h256(tx_hash.clone())),
+            None => Err(AppError::Custom(
+                "✘ A transaction hash is needed to build a proof!".to_string()
+            ))
+        }
+    }
+
+    fn get_block(&self, tx_hash: &H256) -> Result<Block> {
+        match &self.maybe_block {
+            Some(block) => Ok(block.clone()),
+            None => {
+                info!("✔ Getting block from transaction hash: {:?}", tx_hash);
+                self.transport
+                    .get_receipt(tx_hash)
+                    .and_then(|receipt| self.transport.get_block(receipt.block_hash))
+            }
+        }
+    }
+
+    fn get_receipts(&self, block: &Block) -> Result<Vec<Receipt>> {
+        match &self.maybe_receipts {
+            Some(receipts) => Ok(receipts.clone()),
+            None => {
+                info!("✔ Getting all receipts from block...");
+                self.transport.get_receipts(&block.transactions)
+            }
+        }
+    }
+
+    fn get_log_index(&self, receipt: &Receipt) -> Option<usize> {
+        self.maybe_event
+            .as_ref()
+            .and_then(|event| {
+                let signature = event.signature();
+                receipt.logs
+                    .iter()
+                    .position(|log| log.topics.first() == Some(&signature))
+            })
+    }
+
+    pub fn build(self) -> Result<Proof> {
+        let tx_hash = self.get_tx_hash()?;
+        let block = self.get_block(&tx_hash)?;
+        self.transport.check_block_finality(&block, &self.finality_requirement)?;
+        let receipts = self.get_receipts(&block)?;
+        let tx_index = get_tx_index_from_transactions(&tx_hash, &block.transactions)?;
+        let log_index = match receipts.get(tx_index) {
+            Some(receipt) => self.get_log_index(receipt),
+            None => return Err(AppError::Custom(
+                format!("✘ No receipt at index {} of block {}!", tx_index, block.number)
+            )),
+        };
+        info!("✔ Building merkle-patricia trie from receipts...");
+        let trie = get_receipts_trie_from_receipts(&receipts)?;
+        // NOTE: Only warns until the typed receipts encoding is checked against a real post-London block.
+        warn_on_receipts_root_mismatch(&block, trie.root);
+        let (trie, branch) = find_branch_in_trie(trie, tx_index)?;
+        Ok(
+            Proof {
+                tx_hash,
+                tx_index,
+                block,
+                receipts,
+                trie,
+                branch,
+                log_index,
+                event: self.maybe_event,
+            }
+        )
+    }
+}
+
+pub fn build_proof_from_state(state: &State) -> Result<Proof> {
+    ProofBuilder::new(State::get_transport_from_state(state)?)
+        .tx(state.tx_hash_string.clone())
+        .finality(State::get_finality_requirement_from_state(state)?.clone())
+        .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethabi::{
+        EventParam,
+        ParamType,
+    };
+    use crate::get_branch_from_trie::get_branch_from_trie;
+    use crate::test_utils::{
+        PROOF_1_INDEX,
+        get_sample_receipts,
+        get_expected_block_1,
+        get_sample_mock_node,
+        get_sample_tx_hashes_1,
+        SAMPLE_RECEIPT_JSONS_1_PATH,
+    };
+
+    fn get_transfer_event() -> Event {
+        let get_param = |name: &str, kind: ParamType, indexed: bool|
+            EventParam { name: name.to_string(), kind, indexed };
+        Event {
+            name: "Transfer".to_string(),
+            inputs: vec![
+                get_param("from", ParamType::Address, true),
+                get_param("to", ParamType::Address, true),
+                get_param("value", ParamType::Uint(256), false),
+            ],
+            anonymous: false,
+        }
+    }
+
+    fn get_sample_tx_hash() -> String {
+        get_sample_tx_hashes_1()[PROOF_1_INDEX].clone()
+    }
+
+    #[test]
+    fn should_build_proof_via_mock_node() {
+        let mock_node = get_sample_mock_node();
+        let result = ProofBuilder::new(Transport::new(mock_node.get_endpoint()))
+            .tx(get_sample_tx_hash())
+            .confirmations(0)
+            .build()
+            .unwrap();
+        let expected_branch = get_branch_from_trie(result.trie.clone(), PROOF_1_INDEX)
+            .unwrap();
+        assert!(result.tx_index == PROOF_1_INDEX);
+        assert!(result.branch == expected_branch);
+        assert!(result.block.hash == get_expected_block_1().hash);
+        assert!(result.get_receipt().transaction_hash == result.tx_hash);
+        assert!(result.log_index.is_none());
+    }
+
+    #[test]
+    fn should_build_proof_from_given_block_and_receipts() {
+        let mock_node = get_sample_mock_node();
+        let receipts = get_sample_receipts(
+            SAMPLE_RECEIPT_JSONS_1_PATH.to_string(),
+            get_sample_tx_hashes_1(),
+        );
+        let result = ProofBuilder::new(Transport::new(mock_node.get_endpoint()))
+            .tx(get_sample_tx_hash())
+            .block(get_expected_block_1())
+            .receipts(receipts.clone())
+            .confirmations(0)
+            .build()
+            .unwrap();
+        assert!(result.trie.root == get_receipts_trie_from_receipts(&receipts).unwrap().root);
+        assert!(result.get_receipt_proof().unwrap().tx_index == PROOF_1_INDEX);
+    }
+
+    #[test]
+    fn should_not_err_when_receipts_root_does_not_match_header() {
+        let mock_node = get_sample_mock_node();
+        let mut block = get_expected_block_1();
+        block.receipts_root = H256::zero();
+        let result = ProofBuilder::new(Transport::new(mock_node.get_endpoint()))
+            .tx(get_sample_tx_hash())
+            .block(block)
+            .confirmations(0)
+            .build()
+            .unwrap();
+        assert!(result.trie.root != result.block.receipts_root);
+    }
+
+    #[test]
+    fn should_find_and_decode_log_of_event() {
+        let mock_node = get_sample_mock_node();
+        let event = get_transfer_event();
+        let receipts = get_sample_receipts(
+            SAMPLE_RECEIPT_JSONS_1_PATH.to_string(),
+            get_sample_tx_hashes_1(),
+        );
+        let tx_index = receipts
+            .iter()
+            .position(|receipt| receipt.logs
+                .iter()
+                .any(|log| log.topics.first() == Some(&event.signature()))
+            )
+            .unwrap();
+        let result = ProofBuilder::new(Transport::new(mock_node.get_endpoint()))
+            .tx(get_sample_tx_hashes_1()[tx_index].clone())
+            .event(event)
+            .confirmations(0)
+            .build()
+            .unwrap();
+        let decoded_log = result.get_decoded_log()
+            .unwrap();
+        assert!(result.log_index.is_some());
+        assert!(decoded_log.params.len() == 3);
+        assert!(result.get_log_entry_data().unwrap() == get_log_entry_data(result.get_receipt(), result.log_index.unwrap()).unwrap());
+    }
+
+    #[test]
+    fn should_err_without_tx_hash() {
+        match ProofBuilder::new(Transport::new("http://localhost:8545")).build() {
+            Err(AppError::Custom(e)) => assert!(e.contains("transaction hash is needed")),
+            _ => panic!("Builder without tx hash should not build!"),
+        }
+    }
+
+    #[test]
+    fn should_err_when_block_is_not_final() {
+        let mock_node = get_sample_mock_node();
+        match ProofBuilder::new(Transport::new(mock_node.get_endpoint())).tx(get_sample_tx_hash()).build() {
+            Err(AppError::NotYetFinal(_)) => assert!(true),
+            _ => panic!("Proof of tx in non-final block should not build!"),
+        }
+    }
+}
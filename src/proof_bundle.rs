@@ -0,0 +1,356 @@
+use std::fs;
+use ethereum_types::H256;
+use rlp::{
+    Rlp,
+    RlpStream,
+};
+use serde::{
+    de,
+    ser,
+    Serialize,
+    Serializer,
+    Deserialize,
+    Deserializer,
+};
+use crate::errors::AppError;
+use crate::verify_proof::verify_receipt_proof;
+use crate::get_keccak_hash::keccak_hash_bytes;
+use crate::get_header_chain::get_verified_rlp_encoded_header;
+use crate::get_receipts_trie::get_receipts_trie_from_encoded_receipts;
+use crate::utils::{
+    get_bytes_from_field,
+    convert_bytes_to_prefixed_hex,
+};
+use crate::types::{
+    Block,
+    Bytes,
+    Result,
+    Receipt,
+    NodeStack,
+};
+
+pub const PROOF_BUNDLE_VERSION: u32 = 1;
+
+const HEADER_RECEIPTS_ROOT_INDEX: usize = 5;
+const HEADER_NUMBER_INDEX: usize = 8;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProofBundle {
+    // NOTE: Not covered by `verify`, since the bundle holds no transactions trie to prove the
+    // tx at `tx_index` has this hash. Only the checksum, which anyone can recompute, covers it.
+    pub tx_hash: H256,
+    pub tx_index: usize,
+    pub block_hash: H256,
+    pub block_number: u64,
+    pub header: Bytes,
+    pub receipts: Vec<Bytes>,
+    pub receipts_root: H256,
+    pub branch: Vec<Bytes>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ProofBundleJson {
+    version: u32,
+    tx_hash: H256,
+    tx_index: usize,
+    block_hash: H256,
+    block_number: u64,
+    header: String,
+    receipts: Vec<String>,
+    receipts_root: H256,
+    branch: Vec<String>,
+    checksum: H256,
+}
+
+fn get_bytes_list_from_field(name: &str, values: &[String]) -> Result<Vec<Bytes>> {
+    values
+        .iter()
+        .map(|value| get_bytes_from_field(name, value))
+        .collect()
+}
+
+fn get_header_field<T: rlp::Decodable>(header: &Bytes, index: usize) -> Result<T> {
+    Rlp::new(header)
+        .val_at(index)
+        .map_err(|e| AppError::Custom(
+            format!("✘ Cannot decode field {} of bundled header: {}", index, e)
+        ))
+}
+
+fn check_hash(name: &str, expected: H256, actual: H256) -> Result<()> {
+    match expected == actual {
+        true => Ok(()),
+        false => Err(AppError::Custom(
+            format!("✘ Bundled {} is {:?}, but {:?} was expected!", name, actual, expected)
+        ))
+    }
+}
+
+impl ProofBundle {
+    pub fn new(
+        block: &Block,
+        receipts: &[Receipt],
+        branch: &NodeStack,
+        tx_index: usize,
+    ) -> Result<ProofBundle> {
+        ProofBundle::from_encoded_receipts(
+            block,
+            receipts.iter().map(rlp::encode).collect(),
+            branch,
+            tx_index,
+        )
+    }
+
+    pub fn from_encoded_receipts(
+        block: &Block,
+        encoded_receipts: Vec<Bytes>,
+        branch: &NodeStack,
+        tx_index: usize,
+    ) -> Result<ProofBundle> {
+        info!("✔ Bundling proof of receipt at index {}...", tx_index);
+        let tx_hash = match block.transactions.get(tx_index) {
+            Some(tx_hash) => *tx_hash,
+            None => return Err(AppError::Custom(
+                format!(
+                    "✘ Tx index {} is out of range for a block with {} transactions!",
+                    tx_index,
+                    block.transactions.len(),
+                )
+            ))
+        };
+        let receipts_root = get_receipts_trie_from_encoded_receipts(&encoded_receipts)?.root;
+        check_hash("receipts root", block.receipts_root, receipts_root)?;
+        Ok(
+            ProofBundle {
+                tx_hash,
+                tx_index,
+                block_hash: block.hash,
+                block_number: block.number.as_u64(),
+                header: get_verified_rlp_encoded_header(block)?,
+                receipts: encoded_receipts,
+                receipts_root,
+                branch: branch
+                    .iter()
+                    .map(|node| node.get_rlp_encoding())
+                    .collect::<Result<Vec<Bytes>>>()?,
+            }
+        )
+    }
+
+    pub fn get_checksum(&self) -> Result<H256> {
+        let mut stream = RlpStream::new_list(9);
+        stream
+            .append(&PROOF_BUNDLE_VERSION)
+            .append(&self.tx_hash)
+            .append(&(self.tx_index as u64))
+            .append(&self.block_hash)
+            .append(&self.block_number)
+            .append(&self.header);
+        stream.begin_list(self.receipts.len());
+        self.receipts.iter().for_each(|receipt| { stream.append(receipt); });
+        stream.append(&self.receipts_root);
+        stream.begin_list(self.branch.len());
+        self.branch.iter().for_each(|node| { stream.append(node); });
+        keccak_hash_bytes(&stream.out())
+    }
+
+    /// Re-checks everything in the bundle against itself: the header against the block
+    /// hash, the receipts against the header's receipts root & the branch against the
+    /// receipt at the tx index. Returns that receipt. The bundled tx hash is not checked, so
+    /// the receipt is only proven to be the one at the tx index.
+    pub fn verify(&self) -> Result<Bytes> {
+        info!("✔ Verifying proof bundle for receipt {} of block {:?}...", self.tx_index, self.block_hash);
+        check_hash("header hash", self.block_hash, keccak_hash_bytes(&self.header)?)?;
+        let block_number: u64 = get_header_field(&self.header, HEADER_NUMBER_INDEX)?;
+        if block_number != self.block_number {
+            return Err(AppError::Custom(
+                format!(
+                    "✘ Bundled header is for block {}, not {}!",
+                    block_number,
+                    self.block_number,
+                )
+            ))
+        }
+        check_hash(
+            "receipts root",
+            get_header_field(&self.header, HEADER_RECEIPTS_ROOT_INDEX)?,
+            self.receipts_root,
+        )?;
+        check_hash(
+            "receipts' root",
+            self.receipts_root,
+            get_receipts_trie_from_encoded_receipts(&self.receipts)?.root,
+        )?;
+        let receipt = verify_receipt_proof(&self.branch, &self.receipts_root, self.tx_index)?;
+        match self.receipts.get(self.tx_index) == Some(&receipt) {
+            true => Ok(receipt),
+            false => Err(AppError::Custom(
+                format!("✘ Bundled branch does not prove receipt {}!", self.tx_index)
+            ))
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    pub fn from_json(json: &str) -> Result<ProofBundle> {
+        Ok(serde_json::from_str(json)?)
+    }
+}
+
+impl Serialize for ProofBundle {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        ProofBundleJson {
+            version: PROOF_BUNDLE_VERSION,
+            tx_hash: self.tx_hash,
+            tx_index: self.tx_index,
+            block_hash: self.block_hash,
+            block_number: self.block_number,
+            header: convert_bytes_to_prefixed_hex(&self.header),
+            receipts: self.receipts.iter().map(|receipt| convert_bytes_to_prefixed_hex(receipt)).collect(),
+            receipts_root: self.receipts_root,
+            branch: self.branch.iter().map(|node| convert_bytes_to_prefixed_hex(node)).collect(),
+            checksum: self.get_checksum().map_err(|e| ser::Error::custom(e.to_string()))?,
+        }
+            .serialize(serializer)
+    }
+}
+
+fn get_proof_bundle_from_json(json: ProofBundleJson) -> Result<ProofBundle> {
+    if json.version != PROOF_BUNDLE_VERSION {
+        return Err(AppError::Custom(
+            format!(
+                "✘ Unsupported proof bundle version {}, expected {}!",
+                json.version,
+                PROOF_BUNDLE_VERSION,
+            )
+        ))
+    }
+    let bundle = ProofBundle {
+        tx_hash: json.tx_hash,
+        tx_index: json.tx_index,
+        block_hash: json.block_hash,
+        block_number: json.block_number,
+        header: get_bytes_from_field("header", &json.header)?,
+        receipts: get_bytes_list_from_field("receipts", &json.receipts)?,
+        receipts_root: json.receipts_root,
+        branch: get_bytes_list_from_field("branch", &json.branch)?,
+    };
+    check_hash("checksum", bundle.get_checksum()?, json.checksum)
+        .map(|_| bundle)
+}
+
+impl<'de> Deserialize<'de> for ProofBundle {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        ProofBundleJson::deserialize(deserializer)
+            .and_then(|json| get_proof_bundle_from_json(json).map_err(|e| de::Error::custom(e.to_string())))
+    }
+}
+
+pub fn read_proof_bundle_file(path: &str) -> Result<ProofBundle> {
+    info!("✔ Reading proof bundle at {}...", path);
+    fs::read_to_string(path)
+        .map_err(|e| AppError::Custom(
+            format!("✘ Cannot read proof bundle at {}!\n✘ {}", path, e)
+        ))
+        .and_then(|json| ProofBundle::from_json(&json))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value as Json;
+    use crate::get_branch_from_trie::get_branch_from_trie;
+    use crate::get_receipts_trie::get_receipts_trie_from_receipts;
+    use crate::test_utils::{
+        get_sample_receipts,
+        get_sample_tx_hashes_1,
+        get_sample_proof_bundle,
+        SAMPLE_RECEIPT_JSONS_1_PATH,
+        get_sample_block_with_receipts_root,
+    };
+
+    const TX_INDEX: usize = 4;
+
+    fn get_sample_receipts_1() -> Vec<Receipt> {
+        get_sample_receipts(SAMPLE_RECEIPT_JSONS_1_PATH.to_string(), get_sample_tx_hashes_1())
+    }
+
+    fn get_sample_proof_bundle_json() -> Json {
+        serde_json::from_str(&get_sample_proof_bundle(TX_INDEX).to_json().unwrap())
+            .unwrap()
+    }
+
+    #[test]
+    fn should_verify_proof_bundle() {
+        let bundle = get_sample_proof_bundle(TX_INDEX);
+        let result = bundle.verify()
+            .unwrap();
+        assert!(result == rlp::encode(&get_sample_receipts_1()[TX_INDEX]));
+        assert!(bundle.receipts.len() == get_sample_tx_hashes_1().len());
+        assert!(format!("{:?}", bundle.tx_hash) == get_sample_tx_hashes_1()[TX_INDEX]);
+    }
+
+    #[test]
+    fn should_round_trip_proof_bundle_through_json() {
+        let bundle = get_sample_proof_bundle(TX_INDEX);
+        let result = ProofBundle::from_json(&bundle.to_json().unwrap())
+            .unwrap();
+        assert!(result == bundle);
+        assert!(result.verify().is_ok());
+    }
+
+    #[test]
+    fn should_serialize_proof_bundle_to_versioned_schema() {
+        let json = get_sample_proof_bundle_json();
+        assert!(json["version"] == PROOF_BUNDLE_VERSION);
+        assert!(json["tx_index"] == TX_INDEX);
+        assert!(json["header"].as_str().unwrap().starts_with("0x"));
+        assert!(json["checksum"] == format!("{:?}", get_sample_proof_bundle(TX_INDEX).get_checksum().unwrap()));
+    }
+
+    #[test]
+    fn should_err_on_proof_bundle_checksum_mismatch() {
+        let mut json = get_sample_proof_bundle_json();
+        json["receipts"][0] = json!("0xc0");
+        match ProofBundle::from_json(&json.to_string()) {
+            Err(AppError::SerdeJsonError(e)) => assert!(e.to_string().contains("checksum")),
+            _ => panic!("Tampered bundle should not be accepted!"),
+        }
+    }
+
+    #[test]
+    fn should_err_on_unsupported_proof_bundle_version() {
+        let mut json = get_sample_proof_bundle_json();
+        json["version"] = json!(PROOF_BUNDLE_VERSION + 1);
+        match ProofBundle::from_json(&json.to_string()) {
+            Err(AppError::SerdeJsonError(e)) =>
+                assert!(e.to_string().contains("Unsupported proof bundle version")),
+            _ => panic!("Unknown bundle version should not be accepted!"),
+        }
+    }
+
+    #[test]
+    fn should_err_on_verifying_bundle_with_wrong_receipts() {
+        let mut bundle = get_sample_proof_bundle(TX_INDEX);
+        bundle.receipts[0] = vec![0xc0];
+        match bundle.verify() {
+            Err(AppError::Custom(e)) => assert!(e.contains("receipts' root")),
+            _ => panic!("Bundle with wrong receipts should not verify!"),
+        }
+    }
+
+    #[test]
+    fn should_err_on_bundling_receipts_not_matching_block() {
+        let receipts = get_sample_receipts_1();
+        let trie = get_receipts_trie_from_receipts(&receipts).unwrap();
+        let block = get_sample_block_with_receipts_root(H256::zero());
+        let branch = get_branch_from_trie(trie, TX_INDEX).unwrap();
+        match ProofBundle::new(&block, &receipts, &branch, TX_INDEX) {
+            Err(AppError::Custom(e)) => assert!(e.contains("receipts root")),
+            _ => panic!("Receipts not matching the block should not be bundled!"),
+        }
+    }
+}
@@ -0,0 +1,674 @@
+use std::io;
+use std::thread;
+use std::time::{
+    Instant,
+    Duration,
+};
+use std::collections::{
+    HashMap,
+    VecDeque,
+};
+use std::io::{
+    Read,
+    Write,
+    BufRead,
+    BufReader,
+};
+use std::net::{
+    SocketAddr,
+    TcpStream,
+    TcpListener,
+};
+use std::sync::{
+    mpsc,
+    Arc,
+    Mutex,
+};
+use std::sync::atomic::{
+    Ordering,
+    AtomicBool,
+};
+use ethereum_types::H256;
+use serde_json::Value as Json;
+use crate::errors::AppError;
+use crate::rpc_cache::RpcCache;
+use crate::shutdown_signal::{
+    is_shutdown_signalled,
+    listen_for_shutdown_signals,
+};
+use crate::get_block::get_block_by_blockhash;
+use crate::verify_proof::verify_receipt_proof;
+use crate::get_output_from_state::get_log_entry_data;
+use crate::get_header_chain::get_verified_rlp_encoded_header;
+use crate::types::{
+    Bytes,
+    Result,
+    ReceiptProof,
+    FinalityRequirement,
+};
+use crate::utils::{
+    convert_hex_to_h256,
+    convert_bytes_to_hex,
+    convert_hex_to_bytes,
+};
+use crate::decode_proof::get_proof_nodes;
+use crate::block_proof_context::BlockProofContext;
+use crate::generate_batch_proofs::get_block_hash_of_tx;
+
+pub const DEFAULT_SERVER_ADDRESS: &str = "127.0.0.1:8547";
+pub const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 8;
+const MAX_BLOCKS_IN_MEMORY: usize = 32;
+const MAX_REQUEST_BODY_SIZE: usize = 1024 * 1024;
+const MAX_REQUEST_HEADER_SIZE: u64 = 8 * 1024;
+const MAX_REQUEST_HEADERS: usize = 64;
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+const ACCEPT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const SERVER_ERROR: i64 = -32000;
+
+type RpcError = (i64, String);
+type RpcResult = std::result::Result<Json, RpcError>;
+
+pub struct BlockCache {
+    max_blocks: usize,
+    blocks: Mutex<(HashMap<H256, Arc<BlockProofContext>>, VecDeque<H256>)>,
+}
+
+impl BlockCache {
+    pub fn new(max_blocks: usize) -> BlockCache {
+        BlockCache { max_blocks, blocks: Mutex::new((HashMap::new(), VecDeque::new())) }
+    }
+
+    pub fn get(&self, block_hash: &H256) -> Option<Arc<BlockProofContext>> {
+        self.blocks
+            .lock()
+            .ok()
+            .and_then(|blocks| blocks.0.get(block_hash).cloned())
+    }
+
+    pub fn insert(&self, block_hash: H256, block_proof_context: Arc<BlockProofContext>) {
+        if let Ok(mut blocks) = self.blocks.lock() {
+            if blocks.0.insert(block_hash, block_proof_context).is_none() {
+                blocks.1.push_back(block_hash);
+            }
+            while blocks.1.len() > self.max_blocks {
+                if let Some(oldest_hash) = blocks.1.pop_front() {
+                    blocks.0.remove(&oldest_hash);
+                }
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.blocks
+            .lock()
+            .map(|blocks| blocks.0.len())
+            .unwrap_or(0)
+    }
+}
+
+#[derive(Clone)]
+pub struct ServerContext {
+    pub endpoint: String,
+    pub maybe_cache: Option<RpcCache>,
+    pub block_cache: Arc<BlockCache>,
+    pub finality_requirement: FinalityRequirement,
+}
+
+impl ServerContext {
+    pub fn new(
+        endpoint: String,
+        finality_requirement: FinalityRequirement,
+        maybe_cache: Option<RpcCache>,
+    ) -> ServerContext {
+        ServerContext {
+            endpoint,
+            maybe_cache,
+            finality_requirement,
+            block_cache: Arc::new(BlockCache::new(MAX_BLOCKS_IN_MEMORY)),
+        }
+    }
+
+    fn get_block_proof_context(&self, block_hash: H256) -> Result<Arc<BlockProofContext>> {
+        match self.block_cache.get(&block_hash) {
+            Some(block_proof_context) => Ok(block_proof_context),
+            None => BlockProofContext::from_block_hash(
+                &self.endpoint,
+                block_hash,
+                &self.finality_requirement,
+                self.maybe_cache.as_ref(),
+            )
+                .map(Arc::new)
+                .map(|block_proof_context| {
+                    self.block_cache.insert(block_hash, block_proof_context.clone());
+                    block_proof_context
+                })
+        }
+    }
+
+    fn get_receipt_proof(&self, tx_hash: &str) -> Result<(ReceiptProof, Arc<BlockProofContext>)> {
+        get_block_hash_of_tx(&self.endpoint, tx_hash, self.maybe_cache.as_ref())
+            .and_then(|(tx_hash, block_hash)|
+                self.get_block_proof_context(block_hash)
+                    .and_then(|block_proof_context|
+                        block_proof_context
+                            .get_receipt_proof(&tx_hash)
+                            .map(|receipt_proof| (receipt_proof, block_proof_context))
+                    )
+            )
+    }
+}
+
+struct DeadlineReader<'a> {
+    stream: &'a TcpStream,
+    deadline: Instant,
+}
+
+impl<'a> Read for DeadlineReader<'a> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        // NOTE: A socket read timeout only bounds each read, so it's re-armed with whatever is
+        // left until the deadline, lest a slow client keep a worker busy by trickling bytes.
+        let now = Instant::now();
+        if now >= self.deadline {
+            return Err(io::Error::new(io::ErrorKind::TimedOut, "request deadline exceeded"))
+        }
+        self.stream.set_read_timeout(Some(self.deadline - now))?;
+        self.stream.read(buf)
+    }
+}
+
+fn read_http_header_line<R: BufRead>(reader: &mut R) -> Result<String> {
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    match line.ends_with('\n') {
+        true => Ok(line),
+        false => Err(AppError::Custom(
+            format!("✘ Request headers are incomplete or exceed {} bytes!", MAX_REQUEST_HEADER_SIZE)
+        ))
+    }
+}
+
+pub fn read_http_request(stream: &TcpStream, timeout: Duration) -> Result<(String, String)> {
+    let deadline_reader = DeadlineReader { stream, deadline: Instant::now() + timeout };
+    let mut reader = BufReader::new(deadline_reader.take(MAX_REQUEST_HEADER_SIZE));
+    let request_line = read_http_header_line(&mut reader)?;
+    let mut content_length = 0;
+    let mut num_headers = 0;
+    loop {
+        let line = read_http_header_line(&mut reader)?;
+        let header = line.trim().to_lowercase();
+        if header.is_empty() {
+            break;
+        }
+        num_headers += 1;
+        if num_headers > MAX_REQUEST_HEADERS {
+            return Err(AppError::Custom(
+                format!("✘ Request has more than {} headers!", MAX_REQUEST_HEADERS)
+            ))
+        }
+        if header.starts_with("content-length:") {
+            content_length = header["content-length:".len()..]
+                .trim()
+                .parse::<usize>()
+                .unwrap_or(0);
+        }
+    }
+    if content_length > MAX_REQUEST_BODY_SIZE {
+        return Err(AppError::Custom(
+            format!("✘ Request body of {} bytes is too large!", content_length)
+        ))
+    }
+    let mut body = vec![0u8; content_length];
+    reader.get_mut().set_limit(content_length as u64);
+    reader.read_exact(&mut body)?;
+    Ok((request_line.trim().to_string(), String::from_utf8_lossy(&body).to_string()))
+}
+
+pub fn write_http_response(mut stream: &TcpStream, status: &str, body: &str) -> Result<()> {
+    let response = format!(
+        "HTTP/1.1 {}\r\n{}\r\n{}\r\n{}\r\n\r\n{}",
+        status,
+        "Content-Type: application/json",
+        format!("Content-Length: {}", body.len()),
+        "Connection: close",
+        body,
+    );
+    stream.write_all(response.as_bytes())?;
+    Ok(stream.flush()?)
+}
+
+fn get_string_param(params: &Json, index: usize, name: &str) -> std::result::Result<String, RpcError> {
+    match params[index].as_str() {
+        Some(string) => Ok(string.to_string()),
+        None => Err((INVALID_PARAMS, format!("Param {} `{}` must be a string", index, name))),
+    }
+}
+
+fn get_usize_param(params: &Json, index: usize, name: &str) -> std::result::Result<usize, RpcError> {
+    match params[index].as_u64() {
+        Some(number) => Ok(number as usize),
+        None => Err((INVALID_PARAMS, format!("Param {} `{}` must be a number", index, name))),
+    }
+}
+
+fn to_rpc_result(result: Result<Json>) -> RpcResult {
+    result.map_err(|e| (SERVER_ERROR, e.to_string()))
+}
+
+fn get_receipt_proof_rpc(context: &ServerContext, params: &Json) -> RpcResult {
+    let tx_hash = get_string_param(params, 0, "txHash")?;
+    to_rpc_result(
+        context
+            .get_receipt_proof(&tx_hash)
+            .and_then(|(receipt_proof, _)| Ok(serde_json::to_value(receipt_proof)?))
+    )
+}
+
+fn get_log_proof_rpc(context: &ServerContext, params: &Json) -> RpcResult {
+    let tx_hash = get_string_param(params, 0, "txHash")?;
+    let log_index = get_usize_param(params, 1, "logIndex")?;
+    to_rpc_result(
+        context
+            .get_receipt_proof(&tx_hash)
+            .and_then(|(receipt_proof, block_proof_context)| {
+                let log_entry_data = get_log_entry_data(
+                    block_proof_context.get_receipt(receipt_proof.tx_index)?,
+                    log_index,
+                )?;
+                let mut json = serde_json::to_value(receipt_proof)?;
+                json["log_index"] = json!(log_index);
+                json["log_entry_data"] = json!(log_entry_data);
+                Ok(json)
+            })
+    )
+}
+
+fn get_header_rpc(context: &ServerContext, params: &Json) -> RpcResult {
+    let block_hash = get_string_param(params, 0, "blockHash")?;
+    to_rpc_result(
+        convert_hex_to_h256(block_hash)
+            .and_then(|block_hash|
+                get_block_by_blockhash(&context.endpoint, block_hash, context.maybe_cache.as_ref())
+            )
+            .and_then(|block|
+                get_verified_rlp_encoded_header(&block)
+                    .map(|header|
+                        json!({
+                            "block_hash": format!("{:?}", block.hash),
+                            "block_number": block.number.as_u64(),
+                            "header": convert_bytes_to_hex(header),
+                        })
+                    )
+            )
+    )
+}
+
+fn verify_proof_rpc(params: &Json) -> RpcResult {
+    let proof = get_string_param(params, 0, "proof")?;
+    let tx_index = get_usize_param(params, 1, "txIndex")?;
+    let receipts_root = get_string_param(params, 2, "receiptsRoot")?;
+    to_rpc_result(
+        convert_hex_to_h256(receipts_root)
+            .and_then(|receipts_root|
+                convert_hex_to_bytes(proof)
+                    .and_then(|proof| get_proof_nodes(&proof))
+                    .and_then(|proof_nodes|
+                        verify_receipt_proof(&proof_nodes, &receipts_root, tx_index)
+                    )
+            )
+            .map(|receipt| json!({ "receipt": convert_bytes_to_hex(receipt) }))
+    )
+}
+
+fn call_rpc_method(context: &ServerContext, method: &str, params: &Json) -> RpcResult {
+    match method {
+        "getReceiptProof" => get_receipt_proof_rpc(context, params),
+        "getLogProof" => get_log_proof_rpc(context, params),
+        "getHeader" => get_header_rpc(context, params),
+        "verifyProof" => verify_proof_rpc(params),
+        _ => Err((METHOD_NOT_FOUND, format!("Method {} not found", method))),
+    }
+}
+
+pub fn get_json_rpc_response(context: &ServerContext, request_body: &str) -> Json {
+    let (id, result) = match serde_json::from_str::<Json>(request_body) {
+        Err(e) => (Json::Null, Err((PARSE_ERROR, format!("Parse error: {}", e)))),
+        Ok(request) => {
+            info!("✔ Handling `{}` request...", request["method"]);
+            let result = match request["method"].as_str() {
+                Some(method) => call_rpc_method(context, method, &request["params"]),
+                None => Err((METHOD_NOT_FOUND, "Request has no method".to_string())),
+            };
+            (request["id"].clone(), result)
+        },
+    };
+    match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err((code, message)) => json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": { "code": code, "message": message },
+        }),
+    }
+}
+
+fn handle_connection(stream: TcpStream, context: &ServerContext) -> Result<()> {
+    stream.set_nonblocking(false)?;
+    match read_http_request(&stream, REQUEST_TIMEOUT) {
+        Ok((ref request_line, ref request_body)) if request_line.starts_with("POST ") =>
+            write_http_response(
+                &stream,
+                "200 OK",
+                &get_json_rpc_response(context, request_body).to_string(),
+            ),
+        Ok(_) => write_http_response(
+            &stream,
+            "405 Method Not Allowed",
+            &json!({ "error": "Only POST requests are supported" }).to_string(),
+        ),
+        Err(e) => write_http_response(
+            &stream,
+            "400 Bad Request",
+            &json!({ "error": e.to_string() }).to_string(),
+        ),
+    }
+}
+
+fn start_worker(
+    context: ServerContext,
+    receiver: Arc<Mutex<mpsc::Receiver<TcpStream>>>,
+) -> thread::JoinHandle<()> {
+    thread::spawn(move || loop {
+        let maybe_stream = match receiver.lock() {
+            Ok(receiver) => receiver.recv().ok(),
+            Err(_) => None,
+        };
+        match maybe_stream {
+            Some(stream) => if let Err(e) = handle_connection(stream, &context) {
+                error!("✘ Error handling request: {}", e);
+            },
+            None => break,
+        }
+    })
+}
+
+fn run_accept_loop(
+    listener: TcpListener,
+    context: ServerContext,
+    max_concurrent_requests: usize,
+    is_shutting_down: Arc<AtomicBool>,
+) {
+    let (sender, receiver) = mpsc::channel();
+    let receiver = Arc::new(Mutex::new(receiver));
+    let workers: Vec<thread::JoinHandle<()>> = (0..max_concurrent_requests)
+        .map(|_| start_worker(context.clone(), receiver.clone()))
+        .collect();
+    while !is_shutting_down.load(Ordering::SeqCst) {
+        match listener.accept() {
+            Ok((stream, _)) => if sender.send(stream).is_err() {
+                break;
+            },
+            Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock =>
+                thread::sleep(ACCEPT_POLL_INTERVAL),
+            Err(e) => error!("✘ Error accepting connection: {}", e),
+        }
+    }
+    info!("✔ Finishing in-flight requests...");
+    drop(sender);
+    workers
+        .into_iter()
+        .for_each(|worker| { let _ = worker.join(); });
+}
+
+pub struct ProofServer {
+    address: SocketAddr,
+    is_shutting_down: Arc<AtomicBool>,
+    accept_loop: Option<thread::JoinHandle<()>>,
+}
+
+impl ProofServer {
+    pub fn start(
+        address: &str,
+        context: ServerContext,
+        max_concurrent_requests: usize,
+    ) -> Result<ProofServer> {
+        if max_concurrent_requests == 0 {
+            return Err(AppError::Custom(
+                "✘ Server needs to allow at least one concurrent request!".to_string()
+            ))
+        }
+        let listener = TcpListener::bind(address)
+            .map_err(|e| AppError::Custom(format!("✘ Cannot listen on {}!\n✘ {}", address, e)))?;
+        listener.set_nonblocking(true)?;
+        let address = listener.local_addr()?;
+        let is_shutting_down = Arc::new(AtomicBool::new(false));
+        let shutdown_flag = is_shutting_down.clone();
+        let accept_loop = thread::spawn(move ||
+            run_accept_loop(listener, context, max_concurrent_requests, shutdown_flag)
+        );
+        info!("✔ Serving proofs at http://{}", address);
+        Ok(ProofServer { address, is_shutting_down, accept_loop: Some(accept_loop) })
+    }
+
+    pub fn get_address(&self) -> SocketAddr {
+        self.address
+    }
+
+    pub fn shutdown(mut self) -> Result<()> {
+        info!("✔ Shutting down proof server...");
+        self.is_shutting_down.store(true, Ordering::SeqCst);
+        match self.accept_loop.take() {
+            Some(accept_loop) => accept_loop
+                .join()
+                .map_err(|_| AppError::Custom("✘ Proof server thread panicked!".to_string())),
+            None => Ok(()),
+        }
+    }
+
+    pub fn run_until_signalled(self) -> Result<()> {
+        listen_for_shutdown_signals();
+        while !is_shutdown_signalled() {
+            thread::sleep(ACCEPT_POLL_INTERVAL);
+        }
+        self.shutdown()
+    }
+}
+
+impl Drop for ProofServer {
+    fn drop(&mut self) {
+        self.is_shutting_down.store(true, Ordering::SeqCst);
+    }
+}
+
+pub fn run_proof_server(
+    address: &str,
+    context: ServerContext,
+    max_concurrent_requests: usize,
+) -> Result<Bytes> {
+    ProofServer::start(address, context, max_concurrent_requests)
+        .and_then(ProofServer::run_until_signalled)
+        .map(|_| "✔ Proof server shut down\n".as_bytes().to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock_node::MockNode;
+    use crate::test_utils::{
+        PROOF_1_INDEX,
+        RECEIPTS_ROOT_1,
+        get_expected_block,
+        get_sample_proof_1,
+        get_sample_mock_node,
+        get_sample_tx_hashes_1,
+    };
+
+    fn start_server(mock_node: &MockNode) -> ProofServer {
+        let context = ServerContext::new(
+            mock_node.get_endpoint(),
+            FinalityRequirement::Confirmations(0),
+            None,
+        );
+        ProofServer::start("127.0.0.1:0", context, 2)
+            .unwrap()
+    }
+
+    fn call_server(server: &ProofServer, method: &str, params: Json) -> Json {
+        reqwest::Client::new()
+            .post(&format!("http://{}", server.get_address()))
+            .json(&json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params }))
+            .send()
+            .and_then(|mut response| response.json())
+            .unwrap()
+    }
+
+    #[test]
+    fn should_serve_receipt_and_log_proofs() {
+        let mock_node = get_sample_mock_node();
+        let server = start_server(&mock_node);
+        let tx_hash = get_sample_tx_hashes_1()[PROOF_1_INDEX].clone();
+        let receipt_proof = call_server(&server, "getReceiptProof", json!([tx_hash]));
+        let log_proof = call_server(&server, "getLogProof", json!([tx_hash, 0]));
+        assert!(receipt_proof["id"] == 1);
+        assert!(receipt_proof["result"]["tx_index"] == PROOF_1_INDEX);
+        assert!(log_proof["result"]["proof"] == receipt_proof["result"]["proof"]);
+        assert!(log_proof["result"]["log_entry_data"].is_string());
+        server.shutdown().unwrap();
+    }
+
+    #[test]
+    fn should_share_block_cache_between_requests() {
+        let mock_node = get_sample_mock_node();
+        let context = ServerContext::new(
+            mock_node.get_endpoint(),
+            FinalityRequirement::Confirmations(0),
+            None,
+        );
+        get_sample_tx_hashes_1()[..3]
+            .iter()
+            .for_each(|tx_hash| assert!(context.get_receipt_proof(tx_hash).is_ok()));
+        assert!(context.block_cache.len() == 1);
+    }
+
+    #[test]
+    fn should_serve_header() {
+        let mock_node = get_sample_mock_node();
+        let server = start_server(&mock_node);
+        let block = get_expected_block();
+        let result = call_server(&server, "getHeader", json!([format!("{:?}", block.hash)]));
+        let expected_header = get_verified_rlp_encoded_header(&block)
+            .unwrap();
+        assert!(result["result"]["header"] == convert_bytes_to_hex(expected_header));
+        assert!(result["result"]["block_number"] == block.number.as_u64());
+        server.shutdown().unwrap();
+    }
+
+    #[test]
+    fn should_verify_proof() {
+        let mock_node = get_sample_mock_node();
+        let server = start_server(&mock_node);
+        let valid = call_server(
+            &server,
+            "verifyProof",
+            json!([get_sample_proof_1(), PROOF_1_INDEX, RECEIPTS_ROOT_1]),
+        );
+        let invalid = call_server(
+            &server,
+            "verifyProof",
+            json!([get_sample_proof_1(), 0x30, RECEIPTS_ROOT_1]),
+        );
+        assert!(valid["result"]["receipt"].is_string());
+        assert!(invalid["error"]["code"] == SERVER_ERROR);
+        assert!(invalid["error"]["message"].as_str().unwrap().contains("does not contain"));
+        server.shutdown().unwrap();
+    }
+
+    #[test]
+    fn should_return_json_rpc_errors() {
+        let mock_node = get_sample_mock_node();
+        let server = start_server(&mock_node);
+        let unknown_method = call_server(&server, "getBalance", json!([]));
+        let bad_params = call_server(&server, "getLogProof", json!(["0x1234"]));
+        assert!(unknown_method["error"]["code"] == METHOD_NOT_FOUND);
+        assert!(bad_params["error"]["code"] == INVALID_PARAMS);
+        assert!(bad_params["error"]["message"].as_str().unwrap().contains("logIndex"));
+        server.shutdown().unwrap();
+    }
+
+    fn read_request_sent_in_chunks(chunks: Vec<String>, timeout: Duration) -> Result<(String, String)> {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let client = thread::spawn(move || {
+            let mut stream = TcpStream::connect(address).unwrap();
+            chunks.iter().for_each(|chunk| {
+                let _ = stream.write_all(chunk.as_bytes());
+                thread::sleep(Duration::from_millis(100));
+            });
+        });
+        let (stream, _) = listener.accept().unwrap();
+        let result = read_http_request(&stream, timeout);
+        drop(stream);
+        client.join().unwrap();
+        result
+    }
+
+    #[test]
+    fn should_read_http_request() {
+        let chunks = vec!["POST / HTTP/1.1\r\nContent-Length: 2\r\n\r\n{}".to_string()];
+        let result = read_request_sent_in_chunks(chunks, REQUEST_TIMEOUT)
+            .unwrap();
+        assert!(result == ("POST / HTTP/1.1".to_string(), "{}".to_string()));
+    }
+
+    #[test]
+    fn should_err_when_request_headers_exceed_max_size() {
+        let chunks = vec![
+            format!("POST / HTTP/1.1\r\nX-Big: {}\r\n\r\n", "x".repeat(MAX_REQUEST_HEADER_SIZE as usize)),
+        ];
+        match read_request_sent_in_chunks(chunks, REQUEST_TIMEOUT) {
+            Err(AppError::Custom(e)) => assert!(e.contains("exceed")),
+            _ => panic!("Should err when request headers exceed max size!"),
+        }
+    }
+
+    #[test]
+    fn should_err_when_request_has_too_many_headers() {
+        let headers = (0..MAX_REQUEST_HEADERS + 1)
+            .map(|i| format!("X-Header-{}: {}\r\n", i, i))
+            .collect::<String>();
+        let chunks = vec![format!("POST / HTTP/1.1\r\n{}\r\n", headers)];
+        match read_request_sent_in_chunks(chunks, REQUEST_TIMEOUT) {
+            Err(AppError::Custom(e)) => assert!(e.contains("headers")),
+            _ => panic!("Should err when request has too many headers!"),
+        }
+    }
+
+    #[test]
+    fn should_err_when_slow_request_misses_deadline() {
+        let chunks = (0..10)
+            .map(|i| format!("X-Header-{}: {}\r\n", i, i))
+            .collect::<Vec<String>>();
+        let result = read_request_sent_in_chunks(
+            [vec!["POST / HTTP/1.1\r\n".to_string()], chunks].concat(),
+            Duration::from_millis(500),
+        );
+        match result {
+            Err(AppError::IOError(e)) => assert!(
+                e.kind() == io::ErrorKind::TimedOut || e.kind() == io::ErrorKind::WouldBlock
+            ),
+            _ => panic!("Should err when slow request misses deadline!"),
+        }
+    }
+
+    #[test]
+    fn should_stop_accepting_connections_after_shutdown() {
+        let mock_node = get_sample_mock_node();
+        let server = start_server(&mock_node);
+        let address = server.get_address();
+        server.shutdown().unwrap();
+        assert!(TcpStream::connect(address).is_err());
+    }
+}
@@ -0,0 +1,412 @@
+use hex;
+use std::fs;
+use std::cell::Cell;
+use std::path::PathBuf;
+use std::time::SystemTime;
+use serde_json::Value as Json;
+use crate::utils::convert_hex_to_u256;
+use crate::get_block::get_block_by_number;
+use crate::constants::CACHE_FINALITY_CONFIRMATIONS;
+use ethereum_types::{
+    H256,
+    U256,
+};
+use crate::make_rpc_call::{
+    make_rpc_call,
+    get_response_text,
+};
+use crate::types::{
+    Result,
+    FinalityRequirement,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CacheKind {
+    Block,
+    Receipt,
+}
+
+impl CacheKind {
+    fn get_dir_name(&self) -> &'static str {
+        match self {
+            CacheKind::Block => "blocks",
+            CacheKind::Receipt => "receipts",
+        }
+    }
+
+    fn get_hash_field(&self) -> &'static str {
+        match self {
+            CacheKind::Block => "hash",
+            CacheKind::Receipt => "transactionHash",
+        }
+    }
+
+    fn get_block_number_field(&self) -> &'static str {
+        match self {
+            CacheKind::Block => "number",
+            CacheKind::Receipt => "blockNumber",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RpcCache {
+    pub cache_dir: PathBuf,
+    pub max_size_in_bytes: u64,
+    pub finality_requirement: FinalityRequirement,
+    final_block_number: Cell<Option<U256>>,
+    size_in_bytes: Cell<Option<u64>>,
+}
+
+impl RpcCache {
+    pub fn new(cache_dir: &str, max_size_in_bytes: u64) -> RpcCache {
+        RpcCache {
+            max_size_in_bytes,
+            cache_dir: PathBuf::from(cache_dir),
+            final_block_number: Cell::new(None),
+            size_in_bytes: Cell::new(None),
+            finality_requirement: FinalityRequirement::Confirmations(CACHE_FINALITY_CONFIRMATIONS),
+        }
+    }
+
+    pub fn finality(mut self, finality_requirement: FinalityRequirement) -> RpcCache {
+        self.finality_requirement = finality_requirement;
+        self.final_block_number = Cell::new(None);
+        self
+    }
+
+    fn get_entry_path(&self, kind: CacheKind, key: &H256) -> PathBuf {
+        self.cache_dir
+            .join(kind.get_dir_name())
+            .join(format!("{}.json", hex::encode(key)))
+    }
+
+    pub fn get_response(&self, kind: CacheKind, key: &H256) -> Option<String> {
+        fs::read_to_string(self.get_entry_path(kind, key))
+            .ok()
+            .filter(|response| response_matches_key(response, kind, key))
+    }
+
+    fn get_final_block_number(&self, endpoint: &str) -> Result<Option<U256>> {
+        match &self.finality_requirement {
+            FinalityRequirement::Confirmations(min_confirmations) =>
+                get_block_by_number(endpoint, "latest")
+                    .map(|block| block.number.checked_sub(U256::from(*min_confirmations))),
+            FinalityRequirement::BlockTag(tag) => get_block_by_number(endpoint, tag)
+                .map(|block| Some(block.number)),
+        }
+    }
+
+    fn is_block_final(&self, endpoint: &str, block_number: U256) -> Result<bool> {
+        // NOTE: The final block number only grows, so it's only fetched again for blocks above it.
+        match self.final_block_number.get() {
+            Some(final_block_number) if block_number <= final_block_number => Ok(true),
+            _ => self.get_final_block_number(endpoint)
+                .map(|maybe_final_block_number| {
+                    if maybe_final_block_number.is_some() {
+                        self.final_block_number.set(maybe_final_block_number);
+                    }
+                    maybe_final_block_number
+                        .map(|final_block_number| block_number <= final_block_number)
+                        .unwrap_or(false)
+                }),
+        }
+    }
+
+    fn put_response(
+        &self,
+        kind: CacheKind,
+        key: &H256,
+        response: &str,
+    ) -> Result<()> {
+        let path = self.get_entry_path(kind, key);
+        let temp_path = path.with_extension("tmp");
+        let replaced_size = fs::metadata(&path).map(|metadata| metadata.len()).unwrap_or(0);
+        fs::create_dir_all(self.cache_dir.join(kind.get_dir_name()))?;
+        fs::write(&temp_path, response)?;
+        fs::rename(temp_path, path)?;
+        if let Some(size_in_bytes) = self.size_in_bytes.get() {
+            self.size_in_bytes.set(Some(size_in_bytes + response.len() as u64 - replaced_size));
+        }
+        Ok(())
+    }
+
+    pub fn maybe_put_response(
+        &self,
+        endpoint: &str,
+        kind: CacheKind,
+        key: &H256,
+        response: &str,
+    ) -> Result<()> {
+        match get_block_number_from_response(response, kind) {
+            None => Ok(()),
+            Some(block_number) => match self.is_block_final(endpoint, block_number)? {
+                false => {
+                    info!("✔ Block {} not final - not caching!", block_number);
+                    Ok(())
+                },
+                true => self.put_response(kind, key, response)
+                    .and_then(|_| self.maybe_prune_to_max_size()),
+            }
+        }
+    }
+
+    fn get_entries(&self) -> Result<Vec<(SystemTime, u64, PathBuf)>> {
+        [CacheKind::Block, CacheKind::Receipt]
+            .iter()
+            .map(|kind| self.cache_dir.join(kind.get_dir_name()))
+            .filter(|dir| dir.is_dir())
+            .map(|dir| -> Result<Vec<(SystemTime, u64, PathBuf)>> {
+                fs::read_dir(dir)?
+                    .map(|entry| {
+                        let entry = entry?;
+                        let metadata = entry.metadata()?;
+                        Ok((metadata.modified()?, metadata.len(), entry.path()))
+                    })
+                    .collect()
+            })
+            .collect::<Result<Vec<_>>>()
+            .map(|entries| entries.concat())
+    }
+
+    pub fn get_size_in_bytes(&self) -> Result<u64> {
+        self.get_entries()
+            .map(|entries| entries.iter().map(|(_, size, _)| size).sum())
+    }
+
+    fn get_tracked_size_in_bytes(&self) -> Result<u64> {
+        // NOTE: The directory is only scanned once, after which puts & prunes keep the size.
+        match self.size_in_bytes.get() {
+            Some(size_in_bytes) => Ok(size_in_bytes),
+            None => self.get_size_in_bytes()
+                .inspect(|size_in_bytes| self.size_in_bytes.set(Some(*size_in_bytes))),
+        }
+    }
+
+    fn maybe_prune_to_max_size(&self) -> Result<()> {
+        match self.get_tracked_size_in_bytes()? > self.max_size_in_bytes {
+            true => self.prune_to_max_size(),
+            false => Ok(()),
+        }
+    }
+
+    pub fn prune_to_max_size(&self) -> Result<()> {
+        let mut entries = self.get_entries()?;
+        let mut size_in_bytes: u64 = entries.iter().map(|(_, size, _)| size).sum();
+        entries.sort();
+        for (_, entry_size, path) in entries {
+            if size_in_bytes <= self.max_size_in_bytes {
+                break;
+            }
+            info!("✔ Evicting from cache: {}", path.display());
+            fs::remove_file(path)?;
+            size_in_bytes -= entry_size;
+        }
+        self.size_in_bytes.set(Some(size_in_bytes));
+        Ok(())
+    }
+}
+
+fn response_matches_key(response: &str, kind: CacheKind, key: &H256) -> bool {
+    match serde_json::from_str::<Json>(response) {
+        Ok(json) => match json["result"][kind.get_hash_field()].as_str() {
+            Some(hash) => hash.to_lowercase() == format!("0x{}", hex::encode(key)),
+            None => false,
+        },
+        Err(_) => false,
+    }
+}
+
+fn get_block_number_from_response(
+    response: &str,
+    kind: CacheKind,
+) -> Option<U256> {
+    serde_json::from_str::<Json>(response)
+        .ok()
+        .and_then(|json|
+            json["result"][kind.get_block_number_field()]
+                .as_str()
+                .map(|hex| hex.to_string())
+        )
+        .and_then(|hex| convert_hex_to_u256(hex).ok())
+}
+
+pub fn get_response_text_via_cache(
+    endpoint: &str,
+    rpc_json: Json,
+    maybe_cache: Option<&RpcCache>,
+    kind: CacheKind,
+    key: H256,
+) -> Result<String> {
+    match maybe_cache.and_then(|cache| cache.get_response(kind, &key)) {
+        Some(response) => {
+            info!("✔ Cache hit for {:?} with hash: {:?}", kind, key);
+            Ok(response)
+        },
+        None => make_rpc_call(endpoint, rpc_json)
+            .and_then(get_response_text)
+            .map(|response| {
+                if let Some(cache) = maybe_cache {
+                    if let Err(e) = cache.maybe_put_response(
+                        endpoint,
+                        kind,
+                        &key,
+                        &response,
+                    ) {
+                        warn!("✘ Could not write to cache: {}", e);
+                    }
+                };
+                response
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::get_rpc_call_jsons::get_block_by_block_hash_json;
+    use crate::test_utils::{
+        SAMPLE_TX_HASH,
+        SAMPLE_BLOCK_HASH,
+        get_temp_cache_dir,
+        get_sample_mock_node,
+        get_sample_tx_hashes_1,
+        get_sample_mock_node_fixtures,
+        get_valid_tx_hash_h256,
+        get_valid_block_hash_h256,
+    };
+    use crate::utils::convert_hex_to_h256;
+    use crate::get_rpc_call_jsons::get_transaction_receipt_json;
+    use crate::mock_node::MockNode;
+
+    fn get_block_response_via_cache(endpoint: &str, cache: &RpcCache) -> Result<String> {
+        get_response_text_via_cache(
+            endpoint,
+            get_block_by_block_hash_json(SAMPLE_BLOCK_HASH.to_string())?,
+            Some(cache),
+            CacheKind::Block,
+            get_valid_block_hash_h256()?,
+        )
+    }
+
+    #[test]
+    fn should_cache_final_block_and_serve_it_without_node() {
+        let cache = RpcCache::new(&get_temp_cache_dir("final_block"), 1_000_000);
+        let mock_node = get_sample_mock_node();
+        let expected_result = get_block_response_via_cache(
+            &mock_node.get_endpoint(),
+            &cache
+        ).unwrap();
+        drop(mock_node);
+        let block_hash = get_valid_block_hash_h256().unwrap();
+        assert!(cache.get_response(CacheKind::Block, &block_hash).is_some());
+        let result = get_block_response_via_cache("non-working-endpoint", &cache)
+            .unwrap();
+        assert!(result == expected_result);
+    }
+
+    #[test]
+    fn should_cache_receipt_from_final_block() {
+        let cache = RpcCache::new(&get_temp_cache_dir("final_receipt"), 1_000_000);
+        let mock_node = get_sample_mock_node();
+        let tx_hash = get_valid_tx_hash_h256().unwrap();
+        get_response_text_via_cache(
+            &mock_node.get_endpoint(),
+            get_transaction_receipt_json(SAMPLE_TX_HASH).unwrap(),
+            Some(&cache),
+            CacheKind::Receipt,
+            tx_hash,
+        ).unwrap();
+        assert!(cache.get_response(CacheKind::Receipt, &tx_hash).is_some());
+    }
+
+    #[test]
+    fn should_not_cache_receipt_from_non_final_block() {
+        let cache = RpcCache::new(&get_temp_cache_dir("non_final"), 1_000_000);
+        let mock_node = get_sample_mock_node();
+        let tx_hash_hex = get_sample_tx_hashes_1()[0].clone();
+        let tx_hash = convert_hex_to_h256(tx_hash_hex.clone()).unwrap();
+        get_response_text_via_cache(
+            &mock_node.get_endpoint(),
+            get_transaction_receipt_json(&tx_hash_hex).unwrap(),
+            Some(&cache),
+            CacheKind::Receipt,
+            tx_hash,
+        ).unwrap();
+        assert!(cache.get_response(CacheKind::Receipt, &tx_hash).is_none());
+    }
+
+    #[test]
+    fn should_refresh_stale_final_block_number() {
+        let cache = RpcCache::new(&get_temp_cache_dir("stale_final"), 1_000_000);
+        let mock_node = get_sample_mock_node();
+        cache.final_block_number.set(Some(U256::from(1)));
+        let result = cache.is_block_final(&mock_node.get_endpoint(), U256::from(8233333))
+            .unwrap();
+        assert!(result);
+        assert!(cache.final_block_number.get() == Some(U256::from(8503804 - CACHE_FINALITY_CONFIRMATIONS)));
+    }
+
+    #[test]
+    fn should_use_block_tag_finality_requirement() {
+        let cache = RpcCache::new(&get_temp_cache_dir("block_tag"), 1_000_000)
+            .finality(FinalityRequirement::BlockTag("finalized".to_string()));
+        let mock_node = get_sample_mock_node_fixtures()
+            .map(|fixtures| fixtures.add_block_tag("finalized", 8233333))
+            .and_then(MockNode::start)
+            .unwrap();
+        let endpoint = mock_node.get_endpoint();
+        assert!(cache.is_block_final(&endpoint, U256::from(8233333)).unwrap());
+        assert!(!cache.is_block_final(&endpoint, U256::from(8503804)).unwrap());
+    }
+
+    #[test]
+    fn should_not_serve_entry_whose_hash_does_not_match_key() {
+        let cache = RpcCache::new(&get_temp_cache_dir("mismatch"), 1_000_000);
+        let mock_node = get_sample_mock_node();
+        let response = get_block_response_via_cache(
+            &mock_node.get_endpoint(),
+            &cache
+        ).unwrap();
+        let wrong_key = H256::zero();
+        cache.put_response(CacheKind::Block, &wrong_key, &response).unwrap();
+        assert!(cache.get_response(CacheKind::Block, &wrong_key).is_none());
+    }
+
+    #[test]
+    fn should_prune_oldest_entries_when_over_max_size() {
+        let entry = "x".repeat(100);
+        let cache = RpcCache::new(&get_temp_cache_dir("prune"), 250);
+        let keys: Vec<H256> = (1..4).map(H256::from_low_u64_be).collect();
+        keys.iter().for_each(|key| {
+            cache.put_response(CacheKind::Block, key, &entry).unwrap();
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        });
+        assert!(cache.get_size_in_bytes().unwrap() == 300);
+        cache.prune_to_max_size().unwrap();
+        assert!(cache.get_size_in_bytes().unwrap() == 200);
+        assert!(!cache.get_entry_path(CacheKind::Block, &keys[0]).exists());
+        assert!(cache.get_entry_path(CacheKind::Block, &keys[2]).exists());
+    }
+
+    #[test]
+    fn should_track_size_across_puts_and_only_prune_when_over_max_size() {
+        let entry = "x".repeat(100);
+        let cache = RpcCache::new(&get_temp_cache_dir("tracked_size"), 250);
+        let keys: Vec<H256> = (1..4).map(H256::from_low_u64_be).collect();
+        let put_entry = |key: &H256| {
+            cache.put_response(CacheKind::Block, key, &entry).unwrap();
+            cache.maybe_prune_to_max_size().unwrap();
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        };
+        put_entry(&keys[0]);
+        put_entry(&keys[1]);
+        put_entry(&keys[1]);
+        assert!(cache.size_in_bytes.get() == Some(200));
+        assert!(cache.get_entry_path(CacheKind::Block, &keys[0]).exists());
+        put_entry(&keys[2]);
+        assert!(cache.size_in_bytes.get() == Some(200));
+        assert!(cache.get_size_in_bytes().unwrap() == 200);
+        assert!(!cache.get_entry_path(CacheKind::Block, &keys[0]).exists());
+    }
+}
@@ -0,0 +1,498 @@
+use std::fs;
+use std::io::Read;
+use ethereum_types::H256;
+use crate::errors::AppError;
+use crate::get_config::get_config;
+use crate::parse_cli_args::CliArgs;
+use crate::get_endpoint::get_endpoint;
+use crate::connect_to_node::connect_to_node;
+use crate::verify_proof::verify_receipt_proof;
+use crate::proof_bundle::read_proof_bundle_file;
+use crate::check_receipts_root::check_receipts_root;
+use crate::proof_builder::build_proof_from_state;
+use crate::get_output_from_state::get_output_from_proof;
+use crate::get_header_chain::get_verified_rlp_encoded_header;
+use crate::initialize_state_from_cli_args::{
+    get_maybe_cache,
+    get_output_format,
+    initialize_state_from_cli_args,
+    get_finality_requirement_from_cli_args,
+};
+use crate::types::{
+    Bytes,
+    Result,
+    OutputFormat,
+};
+use crate::utils::{
+    convert_hex_to_h256,
+    convert_bytes_to_hex,
+};
+use crate::decode_proof::{
+    ProofFile,
+    get_proof_nodes,
+    read_proof_file,
+    get_decoded_proof_string,
+};
+use crate::generate_batch_proofs::{
+    get_batch_output,
+    generate_receipt_proofs,
+    get_tx_hashes_from_string,
+};
+use crate::proof_server::{
+    ServerContext,
+    run_proof_server,
+    DEFAULT_SERVER_ADDRESS,
+    DEFAULT_MAX_CONCURRENT_REQUESTS,
+};
+use crate::shutdown_signal::{
+    is_shutdown_signalled,
+    listen_for_shutdown_signals,
+};
+use crate::watch_blocks::{
+    WatchOutput,
+    BlockWatcher,
+    get_log_filter,
+    write_watch_events,
+    DEFAULT_POLL_INTERVAL_IN_SECS,
+};
+use crate::get_block::{
+    get_block_by_block_id,
+    get_block_by_blockhash,
+};
+use crate::generate_proof_from_data::{
+    get_output_from_offline_proof,
+    generate_offline_proof_from_files,
+};
+
+fn get_endpoint_from_cli_args(cli_args: &CliArgs) -> Result<String> {
+    get_config(cli_args.flag_config.clone())
+        .and_then(|config| get_endpoint(cli_args.flag_endpoint.clone(), config.endpoint))
+}
+
+fn run_prove_from_files_command(cli_args: CliArgs, block_path: &str) -> Result<Bytes> {
+    let receipts_dir = match &cli_args.flag_receipts {
+        Some(receipts_dir) => receipts_dir,
+        None => return Err(AppError::Custom(
+            "✘ Need a `--receipts` directory to generate a proof `--from-files`!".to_string()
+        ))
+    };
+    if cli_args.flag_log_index.is_some() {
+        return Err(AppError::Custom(
+            "✘ A log index cannot be used with `--from-files`!".to_string()
+        ))
+    }
+    let config = get_config(cli_args.flag_config.clone())?;
+    let output_format = get_output_format(cli_args.flag_output.clone().or(config.output))?;
+    if cli_args.flag_signature.is_some() && output_format != OutputFormat::Abi {
+        return Err(AppError::Custom(
+            "✘ A signature can only be used with `abi` output!".to_string()
+        ))
+    }
+    generate_offline_proof_from_files(block_path, receipts_dir, &cli_args.arg_txhash)
+        .and_then(|offline_proof|
+            get_output_from_offline_proof(
+                &offline_proof,
+                output_format,
+                cli_args.flag_signature.clone(),
+            )
+        )
+}
+
+fn run_prove_command(cli_args: CliArgs) -> Result<Bytes> {
+    if let Some(block_path) = cli_args.flag_from_files.clone() {
+        return run_prove_from_files_command(cli_args, &block_path)
+    }
+    initialize_state_from_cli_args(cli_args)
+        .and_then(connect_to_node)
+        .and_then(|state|
+            build_proof_from_state(&state)
+                .and_then(|proof| get_output_from_proof(&proof, &state))
+        )
+}
+
+fn read_tx_hashes_file(maybe_path: &Option<String>) -> Result<String> {
+    match maybe_path.as_ref().map(String::as_str) {
+        None | Some("-") => {
+            info!("✔ Reading transaction hashes from stdin...");
+            let mut contents = String::new();
+            std::io::stdin().read_to_string(&mut contents)?;
+            Ok(contents)
+        },
+        Some(path) => {
+            info!("✔ Reading transaction hashes from {}...", path);
+            fs::read_to_string(path)
+                .map_err(|e| AppError::Custom(
+                    format!("✘ Cannot read transaction hashes file at {}!\n✘ {}", path, e)
+                ))
+        }
+    }
+}
+
+fn run_prove_batch_command(cli_args: CliArgs) -> Result<Bytes> {
+    let config = get_config(cli_args.flag_config.clone())?;
+    let finality_requirement = get_finality_requirement_from_cli_args(&cli_args, &config)?;
+    let endpoint = get_endpoint(cli_args.flag_endpoint.clone(), config.endpoint)?;
+    let maybe_cache = get_maybe_cache(
+        cli_args.flag_cache_dir.clone(),
+        cli_args.flag_cache_size,
+        &finality_requirement,
+    );
+    read_tx_hashes_file(&cli_args.arg_file)
+        .map(|contents| get_tx_hashes_from_string(&contents))
+        .map(|tx_hashes|
+            generate_receipt_proofs(
+                &endpoint,
+                &tx_hashes,
+                &finality_requirement,
+                maybe_cache.as_ref(),
+            )
+        )
+        .and_then(get_batch_output)
+        .map(String::into_bytes)
+}
+
+fn get_receipts_root_from_block_hash(
+    cli_args: &CliArgs,
+    block_hash: H256,
+) -> Result<H256> {
+    info!("✔ Getting receipts root from header of block {:?}...", block_hash);
+    get_endpoint_from_cli_args(cli_args)
+        .and_then(|endpoint| get_block_by_blockhash(&endpoint, block_hash, None))
+        .and_then(|block|
+            get_verified_rlp_encoded_header(&block)
+                .map(|_| block.receipts_root)
+        )
+}
+
+fn get_receipts_root_to_verify_against(
+    cli_args: &CliArgs,
+    proof_file: &ProofFile,
+) -> Result<H256> {
+    match (&cli_args.flag_root, &cli_args.flag_block_hash, proof_file.block_hash) {
+        (Some(root), _, _) => convert_hex_to_h256(root.clone()),
+        (None, Some(block_hash), _) => get_receipts_root_from_block_hash(
+            cli_args,
+            convert_hex_to_h256(block_hash.clone())?,
+        ),
+        (None, None, Some(block_hash)) =>
+            get_receipts_root_from_block_hash(cli_args, block_hash),
+        (None, None, None) => Err(AppError::Custom(
+            "✘ Need a `--root` or `--block-hash` to verify the proof against!"
+                .to_string()
+        ))
+    }
+}
+
+fn run_verify_command(cli_args: CliArgs) -> Result<Bytes> {
+    let proof_file = read_proof_file(&cli_args.arg_proof)?;
+    let receipts_root = get_receipts_root_to_verify_against(&cli_args, &proof_file)?;
+    let tx_index = match cli_args.flag_tx_index.or(proof_file.tx_index) {
+        Some(tx_index) => tx_index,
+        None => return Err(AppError::Custom(
+            "✘ Need a `--tx-index` to verify a proof file without one!".to_string()
+        ))
+    };
+    get_proof_nodes(&proof_file.proof)
+        .and_then(|proof_nodes|
+            verify_receipt_proof(&proof_nodes, &receipts_root, tx_index)
+        )
+        .map(|receipt| {
+            info!("✔ Proof is valid! Receipt:\n");
+            format!("{}\n", convert_bytes_to_hex(receipt)).into_bytes()
+        })
+}
+
+fn run_verify_bundle_command(cli_args: CliArgs) -> Result<Bytes> {
+    read_proof_bundle_file(&cli_args.arg_bundle)
+        .and_then(|bundle| bundle.verify())
+        .map(|receipt| {
+            info!("✔ Proof bundle is valid! Receipt:\n");
+            format!("{}\n", convert_bytes_to_hex(receipt)).into_bytes()
+        })
+}
+
+fn run_header_command(cli_args: CliArgs) -> Result<Bytes> {
+    info!("✔ Getting header of block {}...", cli_args.arg_block);
+    get_endpoint_from_cli_args(&cli_args)
+        .and_then(|endpoint| get_block_by_block_id(&endpoint, &cli_args.arg_block))
+        .and_then(|block| get_verified_rlp_encoded_header(&block))
+        .map(|header| format!("{}\n", convert_bytes_to_hex(header)).into_bytes())
+}
+
+fn run_decode_command(cli_args: CliArgs) -> Result<Bytes> {
+    read_proof_file(&cli_args.arg_proof)
+        .and_then(|proof_file| get_decoded_proof_string(&proof_file.proof))
+        .map(String::into_bytes)
+}
+
+fn run_root_command(cli_args: CliArgs) -> Result<Bytes> {
+    let endpoint = get_endpoint_from_cli_args(&cli_args)?;
+    get_block_by_block_id(&endpoint, &cli_args.arg_block)
+        .and_then(|block| check_receipts_root(&endpoint, &block))
+        .map(|receipts_root| format!("{:?}\n", receipts_root).into_bytes())
+}
+
+fn run_serve_command(cli_args: CliArgs) -> Result<Bytes> {
+    let config = get_config(cli_args.flag_config.clone())?;
+    let finality_requirement = get_finality_requirement_from_cli_args(&cli_args, &config)?;
+    let endpoint = get_endpoint(cli_args.flag_endpoint.clone(), config.endpoint)?;
+    let maybe_cache = get_maybe_cache(
+        cli_args.flag_cache_dir.clone(),
+        cli_args.flag_cache_size,
+        &finality_requirement,
+    );
+    run_proof_server(
+        cli_args.flag_address.as_ref().map(String::as_str).unwrap_or(DEFAULT_SERVER_ADDRESS),
+        ServerContext::new(endpoint, finality_requirement, maybe_cache),
+        cli_args.flag_max_requests.unwrap_or(DEFAULT_MAX_CONCURRENT_REQUESTS),
+    )
+}
+
+fn sleep_unless_shutdown_signalled(secs: u64) {
+    let step = std::time::Duration::from_millis(100);
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(secs);
+    while !is_shutdown_signalled() && std::time::Instant::now() < deadline {
+        std::thread::sleep(step);
+    }
+}
+
+fn run_watch_command(cli_args: CliArgs) -> Result<Bytes> {
+    let filter = get_log_filter(cli_args.flag_contract.clone(), cli_args.flag_topic.clone())?;
+    let config = get_config(cli_args.flag_config.clone())?;
+    let finality_requirement = get_finality_requirement_from_cli_args(&cli_args, &config)?;
+    let endpoint = get_endpoint(cli_args.flag_endpoint.clone(), config.endpoint)?;
+    let maybe_cache = get_maybe_cache(
+        cli_args.flag_cache_dir.clone(),
+        cli_args.flag_cache_size,
+        &finality_requirement,
+    );
+    let output = match &cli_args.flag_out_dir {
+        Some(dir) => WatchOutput::Dir(dir.into()),
+        None => WatchOutput::Stdout,
+    };
+    let poll_interval = cli_args.flag_poll_interval.unwrap_or(DEFAULT_POLL_INTERVAL_IN_SECS);
+    listen_for_shutdown_signals();
+    let mut watcher = BlockWatcher::new(
+        endpoint,
+        filter,
+        finality_requirement,
+        maybe_cache,
+        cli_args.flag_from_block,
+    )?;
+    while !is_shutdown_signalled() {
+        if let Err(e) = watcher.poll_with(|events| write_watch_events(&output, &events)) {
+            error!("✘ Error watching block {}: {}", watcher.next_block_number, e);
+        }
+        sleep_unless_shutdown_signalled(poll_interval);
+    }
+    info!("✔ Stopped watching at block {}", watcher.next_block_number);
+    Ok(vec![])
+}
+
+pub fn run_cli_command(cli_args: CliArgs) -> Result<Bytes> {
+    if cli_args.cmd_prove_batch {
+        run_prove_batch_command(cli_args)
+    } else if cli_args.cmd_verify {
+        run_verify_command(cli_args)
+    } else if cli_args.cmd_verify_bundle {
+        run_verify_bundle_command(cli_args)
+    } else if cli_args.cmd_header {
+        run_header_command(cli_args)
+    } else if cli_args.cmd_decode {
+        run_decode_command(cli_args)
+    } else if cli_args.cmd_root {
+        run_root_command(cli_args)
+    } else if cli_args.cmd_serve {
+        run_serve_command(cli_args)
+    } else if cli_args.cmd_watch {
+        run_watch_command(cli_args)
+    } else {
+        run_prove_command(cli_args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use super::*;
+    use serde_json::Value as Json;
+    use crate::get_keccak_hash::keccak_hash_bytes;
+    use crate::get_log::get_logs_bloom_from_receipts;
+    use crate::get_header_chain::get_rlp_encoded_header;
+    use crate::get_receipts_trie::get_receipts_trie_from_receipts;
+    use crate::get_block::deserialize_block_json_to_block_struct;
+    use crate::make_rpc_call::deserialize_to_block_rpc_response;
+    use crate::mock_node::MockNode;
+    use crate::test_utils::{
+        PROOF_1_INDEX,
+        RECEIPTS_ROOT_1,
+        get_temp_cache_dir,
+        get_expected_block,
+        get_sample_cli_args,
+        get_sample_proof_1,
+        get_sample_receipts,
+        get_sample_proof_bundle,
+        get_sample_mock_node,
+        get_sample_tx_hashes_1,
+        SAMPLE_BLOCK_JSON_PATH,
+        SAMPLE_RECEIPT_JSONS_1_PATH,
+        get_sample_mock_node_fixtures,
+    };
+
+    fn get_mock_node_with_block(receipts_root: H256) -> (MockNode, H256) {
+        // NOTE: The sample block, re-hashed to commit to the given receipts root & to the
+        // logs bloom of the sample receipts.
+        let mut json: Json = fs::read_to_string(SAMPLE_BLOCK_JSON_PATH)
+            .map(|string| serde_json::from_str(&string).unwrap())
+            .unwrap();
+        let logs_bloom = get_logs_bloom_from_receipts(&get_sample_receipts(
+            SAMPLE_RECEIPT_JSONS_1_PATH.to_string(),
+            get_sample_tx_hashes_1(),
+        ));
+        json["result"]["receiptsRoot"] = json!(format!("{:?}", receipts_root));
+        json["result"]["logsBloom"] = json!(format!("{:?}", logs_bloom));
+        json["result"]["transactions"] = json!(get_sample_tx_hashes_1());
+        let block = deserialize_to_block_rpc_response(json.to_string())
+            .and_then(|res| deserialize_block_json_to_block_struct(res.result))
+            .unwrap();
+        let hash = keccak_hash_bytes(&get_rlp_encoded_header(&block)).unwrap();
+        json["result"]["hash"] = json!(format!("{:?}", hash));
+        let mock_node = get_sample_mock_node_fixtures()
+            .and_then(|fixtures| fixtures.add_block_response(&json.to_string()))
+            .and_then(MockNode::start)
+            .unwrap();
+        (mock_node, hash)
+    }
+
+    fn write_proof_file(name: &str, contents: &str) -> String {
+        let dir = get_temp_cache_dir(name);
+        fs::create_dir_all(&dir).unwrap();
+        let path = format!("{}/proof", dir);
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    fn get_cli_args_with_endpoint(endpoint: String) -> CliArgs {
+        let mut cli_args = get_sample_cli_args();
+        cli_args.flag_endpoint = Some(endpoint);
+        cli_args
+    }
+
+    #[test]
+    fn should_run_prove_batch_command() {
+        let mock_node = get_sample_mock_node();
+        let tx_hashes = get_sample_tx_hashes_1();
+        let mut cli_args = get_cli_args_with_endpoint(mock_node.get_endpoint());
+        cli_args.cmd_prove_batch = true;
+        cli_args.flag_confirmations = Some(0);
+        cli_args.arg_file = Some(
+            write_proof_file("prove-batch", &tx_hashes[..3].join("\n"))
+        );
+        let result = String::from_utf8(run_cli_command(cli_args).unwrap())
+            .unwrap();
+        assert!(result.lines().count() == 3);
+        assert!(result.lines().all(|line| line.contains("\"proof\"")));
+    }
+
+    #[test]
+    fn should_run_verify_command_against_root() {
+        let mut cli_args = get_sample_cli_args();
+        cli_args.cmd_verify = true;
+        cli_args.arg_proof = write_proof_file("verify-root", &get_sample_proof_1());
+        cli_args.flag_root = Some(RECEIPTS_ROOT_1.to_string());
+        cli_args.flag_tx_index = Some(PROOF_1_INDEX);
+        let result = run_cli_command(cli_args)
+            .unwrap();
+        assert!(result.ends_with(b"\n"));
+    }
+
+    #[test]
+    fn should_run_verify_command_against_block_hash_in_json_proof() {
+        let (mock_node, block_hash) = get_mock_node_with_block(
+            convert_hex_to_h256(RECEIPTS_ROOT_1.to_string()).unwrap()
+        );
+        let proof_json = json!({
+            "proof": get_sample_proof_1(),
+            "tx_index": PROOF_1_INDEX,
+            "block_hash": format!("{:?}", block_hash),
+        });
+        let mut cli_args = get_cli_args_with_endpoint(mock_node.get_endpoint());
+        cli_args.cmd_verify = true;
+        cli_args.arg_proof = write_proof_file("verify-json", &proof_json.to_string());
+        assert!(run_cli_command(cli_args).is_ok());
+    }
+
+    #[test]
+    fn should_err_on_verify_command_without_root() {
+        let mut cli_args = get_sample_cli_args();
+        cli_args.cmd_verify = true;
+        cli_args.arg_proof = write_proof_file("verify-no-root", &get_sample_proof_1());
+        cli_args.flag_tx_index = Some(PROOF_1_INDEX);
+        match run_cli_command(cli_args) {
+            Err(AppError::Custom(e)) => assert!(e.contains("`--root`")),
+            _ => panic!("Proof without root should not be verified!"),
+        }
+    }
+
+    #[test]
+    fn should_err_on_prove_from_files_without_receipts_dir() {
+        let mut cli_args = get_sample_cli_args();
+        cli_args.flag_from_files = Some(SAMPLE_BLOCK_JSON_PATH.to_string());
+        match run_cli_command(cli_args) {
+            Err(AppError::Custom(e)) => assert!(e.contains("`--receipts`")),
+            _ => panic!("Proof should not be generated from files without a receipts dir!"),
+        }
+    }
+
+    #[test]
+    fn should_run_verify_bundle_command() {
+        let bundle = get_sample_proof_bundle(PROOF_1_INDEX);
+        let mut cli_args = get_sample_cli_args();
+        cli_args.cmd_verify_bundle = true;
+        cli_args.arg_bundle = write_proof_file("verify-bundle", &bundle.to_json().unwrap());
+        let result = run_cli_command(cli_args)
+            .unwrap();
+        assert!(result == format!("{}\n", convert_bytes_to_hex(bundle.receipts[PROOF_1_INDEX].clone())).into_bytes());
+    }
+
+    #[test]
+    fn should_run_header_command() {
+        let mock_node = get_sample_mock_node();
+        let block = get_expected_block();
+        let mut cli_args = get_cli_args_with_endpoint(mock_node.get_endpoint());
+        cli_args.cmd_header = true;
+        cli_args.arg_block = block.number.to_string();
+        let result = run_cli_command(cli_args)
+            .unwrap();
+        let expected_header = get_verified_rlp_encoded_header(&block).unwrap();
+        assert!(result == format!("{}\n", convert_bytes_to_hex(expected_header)).into_bytes());
+    }
+
+    #[test]
+    fn should_run_decode_command() {
+        let mut cli_args = get_sample_cli_args();
+        cli_args.cmd_decode = true;
+        cli_args.arg_proof = write_proof_file("decode", &get_sample_proof_1());
+        let result = String::from_utf8(run_cli_command(cli_args).unwrap())
+            .unwrap();
+        assert!(result.contains("Node 2: leaf"));
+    }
+
+    #[test]
+    fn should_run_root_command() {
+        let receipts = get_sample_receipts(
+            SAMPLE_RECEIPT_JSONS_1_PATH.to_string(),
+            get_sample_tx_hashes_1(),
+        );
+        let receipts_root = get_receipts_trie_from_receipts(&receipts)
+            .unwrap()
+            .root;
+        let (mock_node, block_hash) = get_mock_node_with_block(receipts_root);
+        let mut cli_args = get_cli_args_with_endpoint(mock_node.get_endpoint());
+        cli_args.cmd_root = true;
+        cli_args.arg_block = format!("{:?}", block_hash);
+        let result = run_cli_command(cli_args)
+            .unwrap();
+        assert!(result == format!("{:?}\n", receipts_root).into_bytes());
+    }
+}
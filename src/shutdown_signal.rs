@@ -0,0 +1,27 @@
+use std::sync::atomic::{
+    Ordering,
+    AtomicBool,
+};
+
+static SHUTDOWN_SIGNALLED: AtomicBool = AtomicBool::new(false);
+
+#[cfg(unix)]
+extern "C" fn handle_shutdown_signal(_: libc::c_int) {
+    SHUTDOWN_SIGNALLED.store(true, Ordering::SeqCst);
+}
+
+#[cfg(unix)]
+pub fn listen_for_shutdown_signals() {
+    let handler = handle_shutdown_signal as extern "C" fn(libc::c_int) as libc::sighandler_t;
+    unsafe {
+        libc::signal(libc::SIGINT, handler);
+        libc::signal(libc::SIGTERM, handler);
+    }
+}
+
+#[cfg(not(unix))]
+pub fn listen_for_shutdown_signals() {}
+
+pub fn is_shutdown_signalled() -> bool {
+    SHUTDOWN_SIGNALLED.load(Ordering::SeqCst)
+}
@@ -0,0 +1,293 @@
+use crate::errors::AppError;
+use crate::types::{
+    Bytes,
+    Result,
+};
+use sha2::{
+    Digest,
+    Sha256,
+};
+use ethereum_types::{
+    H256,
+    U256,
+};
+
+pub const BYTES_PER_CHUNK: usize = 32;
+const BITS_PER_CHUNK: usize = 256;
+const BYTES_PER_U64: usize = 8;
+
+pub fn sha256_pair(left: &H256, right: &H256) -> H256 {
+    let mut hasher = Sha256::new();
+    hasher.input(left.as_bytes());
+    hasher.input(right.as_bytes());
+    H256::from_slice(&hasher.result())
+}
+
+pub fn get_zero_hashes(depth: usize) -> Vec<H256> {
+    let mut zero_hashes = vec![H256::zero()];
+    for i in 0..depth {
+        let next = sha256_pair(&zero_hashes[i], &zero_hashes[i]);
+        zero_hashes.push(next);
+    }
+    zero_hashes
+}
+
+pub fn get_depth(num_leaves: usize) -> usize {
+    let mut depth = 0;
+    while (1usize << depth) < num_leaves {
+        depth += 1;
+    }
+    depth
+}
+
+fn get_tree_levels(chunks: &[H256], depth: usize) -> Vec<Vec<H256>> {
+    // NOTE: Only the non-zero part of each level is kept; the rest is virtual.
+    let zero_hashes = get_zero_hashes(depth);
+    let mut levels = vec![chunks.to_vec()];
+    for d in 0..depth {
+        let mut level = levels[d].clone();
+        if level.len() % 2 == 1 {
+            level.push(zero_hashes[d]);
+        }
+        levels.push(
+            level
+                .chunks(2)
+                .map(|pair| sha256_pair(&pair[0], &pair[1]))
+                .collect()
+        );
+    }
+    levels
+}
+
+pub fn merkleize(chunks: &[H256], maybe_limit: Option<usize>) -> Result<H256> {
+    let limit = maybe_limit.unwrap_or(chunks.len());
+    match chunks.len() <= limit {
+        false => Err(AppError::Custom(
+            format!("✘ Cannot merkleize {} chunks with limit {}!", chunks.len(), limit)
+        )),
+        true => {
+            let depth = get_depth(limit);
+            match chunks.is_empty() {
+                true => Ok(get_zero_hashes(depth)[depth]),
+                false => Ok(get_tree_levels(chunks, depth)[depth][0]),
+            }
+        }
+    }
+}
+
+pub fn get_merkle_branch(chunks: &[H256], index: usize, depth: usize) -> Vec<H256> {
+    let zero_hashes = get_zero_hashes(depth);
+    get_tree_levels(chunks, depth)[..depth]
+        .iter()
+        .enumerate()
+        .map(|(d, level)|
+            level
+                .get((index >> d) ^ 1)
+                .cloned()
+                .unwrap_or(zero_hashes[d])
+        )
+        .collect()
+}
+
+pub fn is_valid_merkle_branch(
+    leaf: &H256,
+    branch: &[H256],
+    index: u64,
+    root: &H256,
+) -> bool {
+    let computed_root = branch
+        .iter()
+        .enumerate()
+        .fold(*leaf, |node, (d, sibling)|
+            match (index >> d) & 1 {
+                1 => sha256_pair(sibling, &node),
+                _ => sha256_pair(&node, sibling),
+            }
+        );
+    &computed_root == root
+}
+
+pub fn mix_in_length(root: &H256, length: usize) -> H256 {
+    let mut length_chunk = [0u8; BYTES_PER_CHUNK];
+    length_chunk[..BYTES_PER_U64].copy_from_slice(&(length as u64).to_le_bytes());
+    sha256_pair(root, &H256::from(length_chunk))
+}
+
+pub fn pack_bytes(bytes: &[u8]) -> Vec<H256> {
+    bytes
+        .chunks(BYTES_PER_CHUNK)
+        .map(|bytes_chunk| {
+            let mut chunk = [0u8; BYTES_PER_CHUNK];
+            chunk[..bytes_chunk.len()].copy_from_slice(bytes_chunk);
+            H256::from(chunk)
+        })
+        .collect()
+}
+
+pub fn hash_tree_root_u64(value: u64) -> H256 {
+    pack_bytes(&value.to_le_bytes())[0]
+}
+
+pub fn hash_tree_root_u256(value: &U256) -> H256 {
+    let mut bytes = [0u8; BYTES_PER_CHUNK];
+    value.to_little_endian(&mut bytes);
+    H256::from(bytes)
+}
+
+pub fn hash_tree_root_byte_vector(bytes: &[u8]) -> Result<H256> {
+    merkleize(&pack_bytes(bytes), None)
+}
+
+pub fn hash_tree_root_byte_list(bytes: &[u8], max_length: usize) -> Result<H256> {
+    merkleize(&pack_bytes(bytes), Some((max_length + 31) / BYTES_PER_CHUNK))
+        .map(|root| mix_in_length(&root, bytes.len()))
+}
+
+pub fn hash_tree_root_u64_list(values: &[u64], max_length: usize) -> Result<H256> {
+    let bytes: Bytes = values
+        .iter()
+        .flat_map(|value| value.to_le_bytes().to_vec())
+        .collect();
+    merkleize(
+        &pack_bytes(&bytes),
+        Some((max_length * BYTES_PER_U64 + 31) / BYTES_PER_CHUNK)
+    )
+        .map(|root| mix_in_length(&root, values.len()))
+}
+
+pub fn hash_tree_root_bitvector(bytes: &[u8], num_bits: usize) -> Result<H256> {
+    merkleize(&pack_bytes(bytes), Some((num_bits + 255) / BITS_PER_CHUNK))
+}
+
+pub fn hash_tree_root_bitlist(bytes: &[u8], max_bits: usize) -> Result<H256> {
+    // NOTE: The highest set bit of an SSZ bitlist's last byte marks its length.
+    match bytes.last() {
+        None | Some(0) => Err(AppError::Custom(
+            "✘ Bitlist has no length delimiting bit!".to_string()
+        )),
+        Some(last_byte) => {
+            let delimiter_index = 7 - last_byte.leading_zeros() as usize;
+            let num_bits = (bytes.len() - 1) * 8 + delimiter_index;
+            let mut bits = bytes.to_vec();
+            bits[bytes.len() - 1] ^= 1 << delimiter_index;
+            bits.truncate((num_bits + 7) / 8);
+            merkleize(&pack_bytes(&bits), Some((max_bits + 255) / BITS_PER_CHUNK))
+                .map(|root| mix_in_length(&root, num_bits))
+        }
+    }
+}
+
+pub fn hash_tree_root_vector_of_roots(roots: &[H256]) -> Result<H256> {
+    merkleize(roots, None)
+}
+
+pub fn hash_tree_root_list_of_roots(
+    roots: &[H256],
+    max_length: usize,
+) -> Result<H256> {
+    merkleize(roots, Some(max_length))
+        .map(|root| mix_in_length(&root, roots.len()))
+}
+
+pub fn hash_tree_root_container(field_roots: &[H256]) -> Result<H256> {
+    merkleize(field_roots, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::convert_hex_to_h256;
+
+    fn get_chunks(num_chunks: u64) -> Vec<H256> {
+        (1..=num_chunks).map(H256::from_low_u64_be).collect()
+    }
+
+    #[test]
+    fn should_get_zero_hashes() {
+        let expected_result = convert_hex_to_h256(
+            "0xf5a5fd42d16a20302798ef6ed309979b43003d2320d9f0e8ea9831a92759fb4b"
+                .to_string()
+        ).unwrap();
+        let result = get_zero_hashes(1);
+        assert!(result[0] == H256::zero());
+        assert!(result[1] == expected_result);
+    }
+
+    #[test]
+    fn should_get_depth() {
+        assert!(get_depth(0) == 0);
+        assert!(get_depth(1) == 0);
+        assert!(get_depth(5) == 3);
+        assert!(get_depth(16) == 4);
+        assert!(get_depth(17) == 5);
+    }
+
+    #[test]
+    fn should_merkleize_with_virtual_zero_padding() {
+        let chunks = get_chunks(3);
+        let mut padded_chunks = chunks.clone();
+        padded_chunks.extend(vec![H256::zero(); 5]);
+        let expected_result = merkleize(&padded_chunks, None).unwrap();
+        let result = merkleize(&chunks, Some(8)).unwrap();
+        assert!(result == expected_result);
+    }
+
+    #[test]
+    fn should_err_when_merkleizing_more_chunks_than_limit() {
+        match merkleize(&get_chunks(3), Some(2)) {
+            Err(AppError::Custom(e)) => assert!(e.contains("limit 2")),
+            _ => panic!("Should not merkleize more chunks than limit!"),
+        }
+    }
+
+    #[test]
+    fn should_get_valid_merkle_branches() {
+        let chunks = get_chunks(5);
+        let depth = 4;
+        let root = merkleize(&chunks, Some(1 << depth)).unwrap();
+        chunks
+            .iter()
+            .enumerate()
+            .for_each(|(index, chunk)| {
+                let branch = get_merkle_branch(&chunks, index, depth);
+                assert!(branch.len() == depth);
+                assert!(is_valid_merkle_branch(chunk, &branch, index as u64, &root));
+                assert!(!is_valid_merkle_branch(chunk, &branch, index as u64 ^ 1, &root));
+            });
+    }
+
+    #[test]
+    fn should_hash_tree_root_u64_little_endian() {
+        let result = hash_tree_root_u64(0x0102);
+        assert!(result.as_bytes()[0] == 0x02);
+        assert!(result.as_bytes()[1] == 0x01);
+        assert!(result.as_bytes()[2..].iter().all(|byte| *byte == 0));
+    }
+
+    #[test]
+    fn bitlist_root_should_ignore_delimiter_bit() {
+        let bits = vec![0b0000_0101u8, 0b0000_0011];
+        let expected_result = mix_in_length(
+            &merkleize(&pack_bytes(&[0b0000_0101, 0b0000_0001]), Some(8)).unwrap(),
+            9,
+        );
+        let result = hash_tree_root_bitlist(&bits, 2048).unwrap();
+        assert!(result == expected_result);
+    }
+
+    #[test]
+    fn should_err_on_bitlist_without_delimiter() {
+        match hash_tree_root_bitlist(&[0b0000_0001, 0], 2048) {
+            Err(AppError::Custom(e)) => assert!(e.contains("delimiting")),
+            _ => panic!("Bitlist without delimiter should not be accepted!"),
+        }
+    }
+
+    #[test]
+    fn empty_list_root_should_depend_on_limit() {
+        let result_1 = hash_tree_root_list_of_roots(&[], 16).unwrap();
+        let result_2 = hash_tree_root_list_of_roots(&[], 128).unwrap();
+        assert!(result_1 == mix_in_length(&get_zero_hashes(4)[4], 0));
+        assert!(result_1 != result_2);
+    }
+}
@@ -1,4 +1,5 @@
-use crate::trie::Trie;
+use crate::rpc_cache::RpcCache;
+use crate::transport::Transport;
 use ethereum_types::H256;
 use crate::errors::AppError;
 use crate::utils::{
@@ -6,25 +7,21 @@ use crate::utils::{
     get_no_overwrite_state_err,
 };
 use crate::types::{
-    Block,
     Result,
-    Receipt,
-    Database,
-    NodeStack,
+    OutputFormat,
+    FinalityRequirement,
 };
-use crate::constants;
 
 #[derive(Debug)]
 pub struct State {
     pub tx_hash: H256,
-    pub database: Database,
-    pub block: Option<Block>,
-    pub index: Option<usize>,
     pub tx_hash_string: String,
     pub endpoint: Option<String>,
-    pub branch: Option<NodeStack>,
-    pub receipts_trie: Option<Trie>,
-    pub receipts: Option<Vec<Receipt>>,
+    pub cache: Option<RpcCache>,
+    pub finality_requirement: Option<FinalityRequirement>,
+    pub output_format: Option<OutputFormat>,
+    pub log_index: Option<usize>,
+    pub abi_signature: Option<String>,
 }
 
 impl State {
@@ -36,98 +33,91 @@ impl State {
         Ok(
             State {
                 tx_hash,
-                block: None,
-                index: None,
-                branch: None,
+                cache: None,
                 endpoint,
-                receipts: None,
                 tx_hash_string,
-                receipts_trie: None,
-                database: std::collections::HashMap::new(),
+                log_index: None,
+                abi_signature: None,
+                output_format: None,
+                finality_requirement: None,
             }
         )
     }
 
-    pub fn set_block_in_state(mut self, block: Block) -> Result<State> {
-        match self.block {
+    pub fn set_endpoint_in_state(mut self, endpoint: String) -> Result<State> {
+        match self.endpoint {
             Some(_) =>
-                Err(AppError::Custom(get_no_overwrite_state_err("block"))),
+                Err(AppError::Custom(get_no_overwrite_state_err("endpoint"))),
             None => {
-                self.block = Some(block);
+                self.endpoint = Some(endpoint);
                 Ok(self)
             }
         }
     }
 
-    pub fn set_index_in_state(mut self, index: usize) -> Result<State> {
-        match self.index {
+    pub fn set_cache_in_state(mut self, cache: RpcCache) -> Result<State> {
+        match self.cache {
             Some(_) =>
-                Err(AppError::Custom(get_no_overwrite_state_err("index"))),
+                Err(AppError::Custom(get_no_overwrite_state_err("cache"))),
             None => {
-                self.index = Some(index);
+                self.cache = Some(cache);
                 Ok(self)
             }
         }
     }
 
-    pub fn set_endpoint_in_state(mut self, endpoint: String) -> Result<State> {
-        match self.endpoint {
-            Some(_) =>
-                Err(AppError::Custom(get_no_overwrite_state_err("endpoint"))),
+    pub fn set_finality_requirement_in_state(
+        mut self,
+        finality_requirement: FinalityRequirement,
+    ) -> Result<State> {
+        match self.finality_requirement {
+            Some(_) => Err(AppError::Custom(
+                get_no_overwrite_state_err("finality_requirement")
+            )),
             None => {
-                self.endpoint = Some(endpoint);
+                self.finality_requirement = Some(finality_requirement);
                 Ok(self)
             }
         }
     }
 
-    pub fn set_receipts_in_state(mut self, receipts: Vec<Receipt>) -> Result<State> {
-        match self.receipts {
-            Some(_) =>
-                Err(AppError::Custom(get_no_overwrite_state_err("receipts"))),
+    pub fn set_output_format_in_state(
+        mut self,
+        output_format: OutputFormat,
+    ) -> Result<State> {
+        match self.output_format {
+            Some(_) => Err(AppError::Custom(
+                get_no_overwrite_state_err("output_format")
+            )),
             None => {
-                self.receipts= Some(receipts);
+                self.output_format = Some(output_format);
                 Ok(self)
             }
         }
     }
 
-    pub fn set_branch_in_state(mut self, branch: NodeStack) -> Result<State> {
-        match self.branch {
+    pub fn set_log_index_in_state(mut self, log_index: usize) -> Result<State> {
+        match self.log_index {
             Some(_) =>
-                Err(AppError::Custom(get_no_overwrite_state_err("branch"))),
+                Err(AppError::Custom(get_no_overwrite_state_err("log_index"))),
             None => {
-                self.branch = Some(branch);
+                self.log_index = Some(log_index);
                 Ok(self)
             }
         }
     }
 
-    pub fn set_receipts_trie_in_state(mut self, receipts_trie: Trie) -> Result<State> {
-        match self.receipts_trie {
+    pub fn set_abi_signature_in_state(mut self, abi_signature: String) -> Result<State> {
+        match self.abi_signature {
             Some(_) =>
-                Err(AppError::Custom(get_no_overwrite_state_err("receipts_trie"))),
+                Err(AppError::Custom(get_no_overwrite_state_err("abi_signature"))),
             None => {
-                self.receipts_trie = Some(receipts_trie);
+                self.abi_signature = Some(abi_signature);
                 Ok(self)
             }
         }
     }
 
-    pub fn get_block_from_state(&self) -> Result<&Block> {
-        match &self.block {
-            Some(block) => Ok(&block),
-            None => Err(AppError::Custom(get_not_in_state_err("block")))
-        }
-    }
-
-    pub fn get_branch_from_state(&self) -> Result<&NodeStack> {
-        match &self.branch {
-            Some(branch) => Ok(&branch),
-            None => Err(AppError::Custom(get_not_in_state_err("branch")))
-        }
-    }
-
     pub fn get_endpoint_from_state(&self) -> Result<&str> {
         match &self.endpoint {
             Some(endpoint) => Ok(endpoint),
@@ -135,24 +125,36 @@ impl State {
         }
     }
 
-    pub fn get_receipts_from_state(&self) -> Result<&Vec<Receipt>> {
-        match &self.receipts {
-            Some(receipts) => Ok(receipts),
-            None => Err(AppError::Custom(get_not_in_state_err("receipts")))
+    pub fn get_transport_from_state(&self) -> Result<Transport> {
+        self.get_endpoint_from_state()
+            .map(|endpoint| match &self.cache {
+                Some(cache) => Transport::new(endpoint).cache(cache.clone()),
+                None => Transport::new(endpoint),
+            })
+    }
+
+    pub fn get_finality_requirement_from_state(
+        &self
+    ) -> Result<&FinalityRequirement> {
+        match &self.finality_requirement {
+            Some(finality_requirement) => Ok(finality_requirement),
+            None => Err(AppError::Custom(
+                get_not_in_state_err("finality_requirement")
+            ))
         }
     }
 
-    pub fn get_index_from_state(&self) -> Result<&usize> {
-        match &self.index {
-            Some(index) => Ok(index),
-            None => Err(AppError::Custom(get_not_in_state_err("index")))
+    pub fn get_output_format_from_state(&self) -> Result<&OutputFormat> {
+        match &self.output_format {
+            Some(output_format) => Ok(output_format),
+            None => Err(AppError::Custom(get_not_in_state_err("output_format")))
         }
     }
 
-    pub fn get_receipts_trie_from_state(&self) -> Result<&Trie> {
-        match &self.receipts_trie{
-            Some(receipts_trie) => Ok(receipts_trie),
-            None => Err(AppError::Custom(get_not_in_state_err("receipts_trie")))
+    pub fn get_abi_signature_from_state(&self) -> Result<&String> {
+        match &self.abi_signature {
+            Some(abi_signature) => Ok(abi_signature),
+            None => Err(AppError::Custom(get_not_in_state_err("abi_signature")))
         }
     }
 }
@@ -161,12 +163,8 @@ impl State {
 mod tests {
     use super::*;
     use crate::test_utils::{
-        get_expected_block,
-        get_expected_receipt,
         get_valid_tx_hash_h256,
         get_valid_initial_state,
-        assert_block_is_correct,
-        assert_receipt_is_correct,
     };
 
     #[test]
@@ -178,17 +176,6 @@ mod tests {
         assert!(state.tx_hash == expected_tx_hash);
     }
 
-    #[test]
-    fn initial_state_should_have_no_block() {
-        let expected_err = get_not_in_state_err("block");
-        let state = get_valid_initial_state()
-            .unwrap();
-        match State::get_block_from_state(&state) {
-            Err(AppError::Custom(e)) => assert!(e == expected_err) ,
-            _ => panic!("Block should not be initialised in state!"),
-        }
-    }
-
     #[test]
     fn initial_state_should_have_no_endpoint() {
         let expected_err = get_not_in_state_err("endpoint");
@@ -200,28 +187,6 @@ mod tests {
         }
     }
 
-    #[test]
-    fn initial_state_should_have_no_receipts_trie() {
-        let expected_err = get_not_in_state_err("receipts_trie");
-        let state = get_valid_initial_state()
-            .unwrap();
-        match State::get_receipts_trie_from_state(&state) {
-            Err(AppError::Custom(e)) => assert!(e == expected_err),
-            _ => panic!("Receipts trie should not be initialised in state!"),
-        }
-    }
-
-    #[test]
-    fn initial_state_should_have_no_branch() {
-        let expected_err = get_not_in_state_err("branch");
-        let state = get_valid_initial_state()
-            .unwrap();
-        match State::get_branch_from_state(&state) {
-            Err(AppError::Custom(e)) => assert!(e == expected_err),
-            _ => panic!("Branch should not be initialised in state!"),
-        }
-    }
-
     #[test]
     fn initial_state_should_have_tx_hash_set_correctly() {
         let expected_tx_hash = get_valid_tx_hash_h256()
@@ -269,36 +234,38 @@ mod tests {
     }
 
     #[test]
-    fn should_set_receipts_trie_to_state() {
-        let trie = Trie::get_new_trie().unwrap();
-        let expected_root = trie.root;
+    fn should_get_transport_with_cache_from_state() {
         let state = get_valid_initial_state()
+            .and_then(|state| State::set_endpoint_in_state(state, "http://localhost:8545".to_string()))
+            .and_then(|state| State::set_cache_in_state(state, RpcCache::new("cache-dir", 1337)))
             .unwrap();
-        let new_state = State::set_receipts_trie_in_state(state, trie)
-            .unwrap();
-        let result = State::get_receipts_trie_from_state(&new_state)
+        let result = State::get_transport_from_state(&state)
             .unwrap();
-        assert!(result.root == expected_root);
+        assert!(result.endpoint == "http://localhost:8545");
+        assert!(result.maybe_cache.unwrap().max_size_in_bytes == 1337);
     }
 
     #[test]
-    fn should_err_when_attempting_to_overwrite_receipts_trie_in_state() {
-        let expected_err = "✘ Cannot overwrite receipts_trie in state!";
-        let trie = Trie::get_new_trie().unwrap();
-        let expected_root = trie.root;
-        let initial_state = get_valid_initial_state()
+    fn should_set_cache_in_state() {
+        let cache = RpcCache::new("cache-dir", 1337);
+        let state = get_valid_initial_state()
             .unwrap();
-        let state_with_trie = State::set_receipts_trie_in_state(
-            initial_state,
-            trie.clone()
-        ).unwrap();
-        let trie_from_state = State::get_receipts_trie_from_state(
-            &state_with_trie
+        assert!(state.cache.is_none());
+        let new_state = State::set_cache_in_state(state, cache)
+            .unwrap();
+        assert!(new_state.cache.unwrap().max_size_in_bytes == 1337);
+    }
+
+    #[test]
+    fn should_err_when_attempting_to_overwrite_cache_in_state() {
+        let expected_err = "✘ Cannot overwrite cache in state!";
+        let state_with_cache = State::set_cache_in_state(
+            get_valid_initial_state().unwrap(),
+            RpcCache::new("cache-dir", 1337),
         ).unwrap();
-        assert!(trie_from_state.root == expected_root);
-        match State::set_receipts_trie_in_state(
-            state_with_trie,
-            trie.clone()
+        match State::set_cache_in_state(
+            state_with_cache,
+            RpcCache::new("cache-dir", 1337),
         ) {
             Err(AppError::Custom(e)) => assert!(e == expected_err),
             _ => panic!("Overwriting state should not have succeeded!"),
@@ -306,126 +273,67 @@ mod tests {
     }
 
     #[test]
-    fn should_set_block_in_state() {
-        let expected_block = get_expected_block();
+    fn should_set_finality_requirement_in_state() {
+        let expected_result = FinalityRequirement::Confirmations(1337);
         let state = get_valid_initial_state()
             .unwrap();
-        let new_state = State::set_block_in_state(state, expected_block)
-            .unwrap();
-        let result = State::get_block_from_state(&new_state)
+        let new_state = State::set_finality_requirement_in_state(
+            state,
+            expected_result.clone(),
+        ).unwrap();
+        let result = State::get_finality_requirement_from_state(&new_state)
             .unwrap();
-        assert_block_is_correct(result.clone())
+        assert!(result == &expected_result);
     }
 
     #[test]
-    fn should_err_when_attempting_to_overwrite_block_in_state() {
-        let expected_err = "✘ Cannot overwrite block in state!";
-        let expected_block = get_expected_block();
-        let state = get_valid_initial_state()
-            .unwrap();
-        let state_with_block = State::set_block_in_state(
-            state,
-            expected_block.clone()
-        )
-            .unwrap();
-        match State::set_block_in_state(
-            state_with_block,
-            expected_block
+    fn should_err_when_attempting_to_overwrite_finality_requirement_in_state() {
+        let expected_err = "✘ Cannot overwrite finality_requirement in state!";
+        let requirement = FinalityRequirement::BlockTag("finalized".to_string());
+        let state_with_requirement = State::set_finality_requirement_in_state(
+            get_valid_initial_state().unwrap(),
+            requirement.clone(),
+        ).unwrap();
+        match State::set_finality_requirement_in_state(
+            state_with_requirement,
+            requirement,
         ) {
             Err(AppError::Custom(e)) => assert!(e == expected_err),
             _ => panic!("Overwriting state should not have succeeded!"),
         }
     }
 
-   #[test]
-   fn should_set_receipts_into_state() {
-       let receipt = get_expected_receipt();
-       let mut vec_of_receipts = Vec::new();
-       vec_of_receipts.push(receipt.clone());
-       vec_of_receipts.push(receipt);
-       let state = get_valid_initial_state()
-           .unwrap();
-       let state_with_receipts = State::set_receipts_in_state(
-           state,
-           vec_of_receipts
-       ).unwrap();
-       let receipts_from_state = State::get_receipts_from_state(
-           &state_with_receipts
-       ).unwrap();
-       let _result: Vec<_> = receipts_from_state
-            .iter()
-            .map(|receipt| assert_receipt_is_correct(receipt.clone()))
-            .collect();
-   }
-
-   #[test]
-   fn should_err_when_attempting_to_overwrite_receipts_in_state() {
-       let expected_err = "✘ Cannot overwrite receipts in state!";
-       let receipt = get_expected_receipt();
-       let mut vec_of_receipts = Vec::new();
-       vec_of_receipts.push(receipt.clone());
-       vec_of_receipts.push(receipt);
-       let state = get_valid_initial_state()
-           .unwrap();
-       let state_with_receipts = State::set_receipts_in_state(
-           state,
-           vec_of_receipts.clone(),
-       ).unwrap();
-       match State::set_receipts_in_state(
-           state_with_receipts,
-           vec_of_receipts,
-       ) {
-           Err(AppError::Custom(e)) => assert!(e == expected_err),
-           _ => panic!("Expected error not received!")
-       }
-   }
-
     #[test]
-    fn should_set_index_to_state() {
-        let expected_index: usize = 1337;
+    fn should_set_output_format_in_state() {
         let state = get_valid_initial_state()
             .unwrap();
-        let new_state = State::set_index_in_state(state, expected_index)
+        let new_state = State::set_output_format_in_state(state, OutputFormat::Json)
             .unwrap();
-        let result = State::get_index_from_state(&new_state)
+        let result = State::get_output_format_from_state(&new_state)
             .unwrap();
-        assert!(result == &expected_index);
+        assert!(result == &OutputFormat::Json);
     }
 
     #[test]
-    fn should_set_branch_in_state() {
-        let expected_branch: NodeStack = Vec::new();
-        let state = get_valid_initial_state()
-            .unwrap();
-        let new_state = State::set_branch_in_state(
-            state,
-            expected_branch.clone()
-        ).unwrap();
-        let result = State::get_branch_from_state(&new_state)
+    fn should_err_when_attempting_to_overwrite_log_index_in_state() {
+        let expected_err = "✘ Cannot overwrite log_index in state!";
+        let state_with_log_index = get_valid_initial_state()
+            .and_then(|state| State::set_log_index_in_state(state, 1))
             .unwrap();
-        assert!(result == &expected_branch);
+        match State::set_log_index_in_state(state_with_log_index, 2) {
+            Err(AppError::Custom(e)) => assert!(e == expected_err),
+            _ => panic!("Overwriting log index in state should not succeed!"),
+        }
     }
 
     #[test]
-    fn should_err_when_attempting_to_overwrite_index_in_state() {
-        let expected_index: usize = 1337;
-        let expected_err = "✘ Cannot overwrite index in state!";
-        let initial_state = get_valid_initial_state()
+    fn should_set_abi_signature_in_state() {
+        let signature = "(bytes receipt)".to_string();
+        let state = get_valid_initial_state()
+            .and_then(|state| State::set_abi_signature_in_state(state, signature.clone()))
             .unwrap();
-        let state_with_index = State::set_index_in_state(
-            initial_state,
-            expected_index,
-        ).unwrap();
-        let index_from_state = State::get_index_from_state(
-            &state_with_index
-        ).unwrap();
-        assert!(index_from_state == &expected_index);
-        match State::set_index_in_state(
-            state_with_index,
-            expected_index.clone()
-        ) {
-            Err(AppError::Custom(e)) => assert!(e == expected_err),
-            _ => panic!("Overwriting state should not have succeeded!"),
-        }
+        let result = State::get_abi_signature_from_state(&state)
+            .unwrap();
+        assert!(result == &signature);
     }
 }
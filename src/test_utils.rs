@@ -3,24 +3,32 @@
 
 use std::fs;
 use crate::state::State;
+use crate::errors::AppError;
 use ethereum_types::H256;
 use crate::trie_nodes::Node;
+use crate::parse_cli_args::CliArgs;
+use crate::proof_bundle::ProofBundle;
+use serde_json::Value as Json;
+use crate::get_keccak_hash::keccak_hash_bytes;
+use crate::get_header_chain::get_rlp_encoded_header;
 use crate::get_block::deserialize_block_json_to_block_struct;
 use crate::rlp_codec::get_rlp_encoded_receipts_and_nibble_tuples;
 use crate::get_receipts::deserialize_receipt_json_to_receipt_struct;
-use crate::get_branch_from_trie::get_branch_from_trie_and_put_in_state;
+use crate::get_receipts_trie::get_receipts_trie_from_receipts;
+use crate::get_branch_from_trie::get_branch_from_trie;
 use crate::nibble_utils::{
     Nibbles,
     get_nibbles_from_bytes,
     get_nibbles_from_offset_bytes,
 };
-use crate::trie::{
+use crate::patricia_trie::{
     Trie,
     put_in_trie_recursively
 };
 use crate::utils::{
     convert_hex_to_h256,
     convert_h256_to_prefixed_hex,
+    convert_hex_strings_to_h256s,
 };
 use crate::make_rpc_call::{
     deserialize_to_block_rpc_response,
@@ -29,10 +37,19 @@ use crate::make_rpc_call::{
 use crate::constants::{
     DOT_ENV_PATH,
     DEFAULT_ENDPOINT,
+    LOCK_EVENT_STRING,
+};
+use crate::get_log::{
+    get_logs_bloom_from_logs,
+    get_logs_from_receipt_json,
 };
 use crate::get_database::{
     put_thing_in_database,
 };
+use crate::mock_node::{
+    MockNode,
+    MockNodeFixtures,
+};
 use crate::types::{
     Log,
     Block,
@@ -40,6 +57,7 @@ use crate::types::{
     Result,
     Receipt,
     Database,
+    ReceiptJson,
 };
 
 pub const TX_INDEX: usize = 96;
@@ -47,9 +65,31 @@ pub const PROOF_1_INDEX: usize = 14;
 pub const PROOF_3_INDEX: usize = 134;
 pub const WORKING_ENDPOINT: &str = "http://localhost:8545";
 pub const SAMPLE_BLOCK_JSON_PATH: &str = "./test_utils/sample_block_json";
+// NOTE: Block 1's hash, number, transactions, gasUsed, receiptsRoot & logsBloom are mainnet's, &
+// match its sample receipts. Its other header fields are copied from the sample block, so its
+// header does not hash to its hash.
+pub const SAMPLE_BLOCK_JSON_1_PATH: &str = "./test_utils/sample_block_json_1";
+pub const SAMPLE_ETHASH_CACHE_SLICE_PATH: &str = "./test_utils/sample_ethash_cache_slice";
+pub const SAMPLE_ETHASH_DATASET_SLICE_PATH: &str = "./test_utils/sample_ethash_dataset_slice";
+// NOTE: A synthetic Deneb block wrapping the sample block 1's payload, so its root is only
+// checked against this crate's own SSZ merkleization. The mainnet genesis root test is the one
+// check against a published root until a real Capella/Deneb block & its root are vendored.
+pub const SAMPLE_BEACON_BLOCK_JSON_PATH: &str = "./test_utils/sample_beacon_block_json";
+// NOTE: Synthetic light client fixtures, signed by a made-up committee on a chain whose only
+// fork is at epoch 0, so they don't stand in for the consensus-spec `light_client/sync` vectors.
+pub const SAMPLE_LIGHT_CLIENT_UPDATE_JSON_PATH: &str =
+    "./test_utils/sample_light_client_update_json";
+pub const SAMPLE_LIGHT_CLIENT_BOOTSTRAP_JSON_PATH: &str =
+    "./test_utils/sample_light_client_bootstrap_json";
+pub const SAMPLE_LIGHT_CLIENT_BOOTSTRAP_ROOT: &str =
+    "0xb4ddfe6629236cfe08eb82672a8300611efa608e4b1e41b03913e4b42b1867c2";
+pub const SAMPLE_GENESIS_VALIDATORS_ROOT: &str =
+    "0x7cf5e74189c54a62725023fac160b14a2e0c3e902a01480ac368f0494aac1b86";
 pub const SAMPLE_RECEIPT_JSON_PATH: &str = "./test_utils/sample_receipt_json";
 pub const SAMPLE_RECEIPT_JSON_PATH_2: &str = "./test_utils/sample_receipt_json_2";
 pub const SAMPLE_RECEIPT_JSON_PATH_3: &str = "./test_utils/sample_receipt_json_3";
+// NOTE: A copy of the receipt above tagged as an EIP-1559 (type 0x2) receipt.
+pub const SAMPLE_TYPED_RECEIPT_JSON_PATH: &str = "./test_utils/sample_typed_receipt_json";
 pub const SAMPLE_RECEIPT_JSONS_1_PATH: &str = "./test_utils/sample_receipt_jsons_1/";
 pub const SAMPLE_RECEIPT_JSONS_2_PATH: &str = "./test_utils/sample_receipt_jsons_2/";
 pub const SAMPLE_RECEIPT_JSONS_3_PATH: &str = "./test_utils/sample_receipt_jsons_3/";
@@ -870,40 +910,149 @@ pub fn get_valid_tx_hash_h256() -> Result<H256> {
      convert_hex_to_h256(get_valid_tx_hash_hex())
 }
 
+pub fn get_sample_cli_args() -> CliArgs {
+    CliArgs {
+        cmd_prove: false,
+        cmd_prove_batch: false,
+        cmd_verify: false,
+        cmd_verify_bundle: false,
+        cmd_header: false,
+        cmd_decode: false,
+        cmd_root: false,
+        cmd_serve: false,
+        cmd_watch: false,
+        arg_txhash: get_valid_tx_hash_hex(),
+        arg_proof: String::new(),
+        arg_block: String::new(),
+        arg_bundle: String::new(),
+        arg_file: None,
+        flag_verbose: false,
+        flag_disableLogs: true,
+        flag_cache_dir: None,
+        flag_cache_size: None,
+        flag_block_tag: None,
+        flag_confirmations: None,
+        flag_endpoint: Some(WORKING_ENDPOINT.to_string()),
+        flag_config: None,
+        flag_output: None,
+        flag_log_index: None,
+        flag_signature: None,
+        flag_root: None,
+        flag_block_hash: None,
+        flag_tx_index: None,
+        flag_address: None,
+        flag_max_requests: None,
+        flag_contract: None,
+        flag_topic: None,
+        flag_from_block: None,
+        flag_out_dir: None,
+        flag_poll_interval: None,
+        flag_from_files: None,
+        flag_receipts: None,
+    }
+}
+
 pub fn get_valid_initial_state() -> Result<State> {
     State::init(
         get_valid_tx_hash_h256()?,
         get_valid_tx_hash_hex(),
-        Some(String::from(DEFAULT_ENDPOINT))
+        None,
     )
 }
 
-pub fn get_valid_state_with_endpoint() -> Result<State> {
+pub fn get_valid_state_with_endpoint(endpoint: &str) -> Result<State> {
     get_valid_initial_state()
         .and_then(|state|
-            State::set_endpoint_in_state(state, WORKING_ENDPOINT.to_string())
+            State::set_endpoint_in_state(state, endpoint.to_string())
         )
 }
 
-pub fn get_valid_state_with_receipts_trie_and_index(
-    path: String,
-    tx_hashes: Vec<String>
-) -> Result<State> {
-    get_valid_initial_state()
-        .and_then(|state| state.set_index_in_state(14))
-        .and_then(|state|
-            state.set_receipts_trie_in_state(
-                get_sample_trie_with_sample_receipts(path, tx_hashes)
+pub fn get_sample_mock_node_fixtures() -> Result<MockNodeFixtures> {
+    MockNodeFixtures::new()
+        .add_block_from_file(SAMPLE_BLOCK_JSON_PATH)
+        .and_then(|fixtures| fixtures.add_block_from_file(SAMPLE_BLOCK_JSON_1_PATH))
+        .and_then(|fixtures| fixtures.add_receipt_from_file(SAMPLE_RECEIPT_JSON_PATH))
+        .and_then(|fixtures|
+            fixtures.add_receipts_from_dir(SAMPLE_RECEIPT_JSONS_1_PATH)
+        )
+        .and_then(|fixtures|
+            fixtures.add_beacon_block_from_file(SAMPLE_BEACON_BLOCK_JSON_PATH)
+        )
+}
+
+pub fn get_sample_mock_node() -> MockNode {
+    get_sample_mock_node_fixtures()
+        .and_then(MockNode::start)
+        .unwrap()
+}
+
+pub const SAMPLE_LOCKED_AMOUNT: u128 = 1_000_000;
+pub const SAMPLE_BRIDGE_FEE: u128 = 1_000;
+
+fn get_sample_lock_event_receipt_json_1() -> Json {
+    // NOTE: The receipt at `PROOF_1_INDEX` of block 1, its logs swapped for one `Locked` event.
+    let mut json: Json = fs::read_to_string(
+        format!("{}{}", SAMPLE_RECEIPT_JSONS_1_PATH, get_sample_tx_hashes_1()[PROOF_1_INDEX])
+    )
+        .map(|string| serde_json::from_str(&string).unwrap())
+        .unwrap();
+    let mut log = json["result"]["logs"][0].clone();
+    log["topics"] = json!([
+        format!("0x{}", LOCK_EVENT_STRING),
+        format!("0x{}{}", "00".repeat(12), "aa".repeat(20)),
+        format!("0x{}{}", "00".repeat(12), "bb".repeat(20)),
+    ]);
+    log["data"] = json!(format!("0x{}", hex::encode(ethabi::encode(&[
+        ethabi::Token::Uint(SAMPLE_LOCKED_AMOUNT.into()),
+        ethabi::Token::Uint(SAMPLE_BRIDGE_FEE.into()),
+        ethabi::Token::Bytes(vec![0x49, 0x00]),
+        ethabi::Token::Bytes(vec![0x01, 0x02, 0x03]),
+        ethabi::Token::Bytes(vec![]),
+    ]))));
+    json["result"]["logs"] = json!([log]);
+    let receipt_json: ReceiptJson = serde_json::from_value(json["result"].clone()).unwrap();
+    let logs_bloom = get_logs_from_receipt_json(&receipt_json)
+        .and_then(|logs| get_logs_bloom_from_logs(&logs))
+        .unwrap();
+    json["result"]["logsBloom"] = json!(format!("0x{}", hex::encode(logs_bloom)));
+    json
+}
+
+pub fn get_sample_lock_event_mock_node() -> MockNode {
+    // NOTE: Block 1 & its receipts, with the tx at `PROOF_1_INDEX` emitting a `Locked` event
+    // & the header committing to the receipts root computed here from those receipts.
+    let receipt_json = get_sample_lock_event_receipt_json_1();
+    let mut receipts = get_sample_receipts(
+        SAMPLE_RECEIPT_JSONS_1_PATH.to_string(),
+        get_sample_tx_hashes_1(),
+    );
+    receipts[PROOF_1_INDEX] = serde_json::from_value(receipt_json["result"].clone())
+        .map_err(AppError::from)
+        .and_then(deserialize_receipt_json_to_receipt_struct)
+        .unwrap();
+    let trie = get_receipts_trie_from_receipts(&receipts).unwrap();
+    let mut block_json: Json = fs::read_to_string(SAMPLE_BLOCK_JSON_1_PATH)
+        .map(|string| serde_json::from_str(&string).unwrap())
+        .unwrap();
+    block_json["result"]["receiptsRoot"] = json!(format!("{:?}", trie.root));
+    get_sample_mock_node_fixtures()
+        .and_then(|fixtures| fixtures.add_block_response(&block_json.to_string()))
+        .map(|fixtures|
+            fixtures.add_raw_receipt_response(
+                &get_sample_tx_hashes_1()[PROOF_1_INDEX],
+                &receipt_json.to_string(),
             )
         )
+        .and_then(MockNode::start)
+        .unwrap()
 }
 
-pub fn get_valid_state_with_receipts_trie_index_and_branch(
-    path: String,
-    tx_hashes: Vec<String>
-) -> Result<State> {
-    get_valid_state_with_receipts_trie_and_index(path, tx_hashes)
-        .and_then(get_branch_from_trie_and_put_in_state)
+pub fn get_temp_cache_dir(name: &str) -> String {
+    let path = std::env::temp_dir().join(
+        format!("rusty-receipt-proof-maker-{}-{}", name, std::process::id())
+    );
+    let _ = fs::remove_dir_all(&path);
+    path.to_string_lossy().to_string()
 }
 
 pub fn get_expected_block() -> Block {
@@ -912,6 +1061,36 @@ pub fn get_expected_block() -> Block {
     deserialize_block_json_to_block_struct(res.result).unwrap()
 }
 
+pub fn get_expected_block_1() -> Block {
+    let string = fs::read_to_string(SAMPLE_BLOCK_JSON_1_PATH).unwrap();
+    let res = deserialize_to_block_rpc_response(string).unwrap();
+    deserialize_block_json_to_block_struct(res.result).unwrap()
+}
+
+pub fn get_synthetic_block_chain(length: usize) -> Vec<String> {
+    // NOTE: Descendants of the sample block, re-hashed so each header is valid.
+    let mut json: Json = fs::read_to_string(SAMPLE_BLOCK_JSON_PATH)
+        .map(|string| serde_json::from_str(&string).unwrap())
+        .unwrap();
+    let mut chain = vec![json.to_string()];
+    for _ in 1..length {
+        let parent_hash = json["result"]["hash"].clone();
+        let block_number = deserialize_to_block_rpc_response(json.to_string())
+            .and_then(|res| deserialize_block_json_to_block_struct(res.result))
+            .unwrap()
+            .number;
+        json["result"]["parentHash"] = parent_hash;
+        json["result"]["number"] = json!(format!("{:#x}", block_number + 1));
+        let block = deserialize_to_block_rpc_response(json.to_string())
+            .and_then(|res| deserialize_block_json_to_block_struct(res.result))
+            .unwrap();
+        let hash = keccak_hash_bytes(&get_rlp_encoded_header(&block)).unwrap();
+        json["result"]["hash"] = json!(format!("{:?}", hash));
+        chain.push(json.to_string());
+    }
+    chain
+}
+
 pub fn get_expected_receipt() -> Receipt {
     let string = fs::read_to_string(SAMPLE_RECEIPT_JSON_PATH).unwrap();
     let res = deserialize_to_receipt_rpc_response(string).unwrap();
@@ -1109,11 +1288,6 @@ mod tests {
                 assert!(e == get_not_in_state_err("endpoint")),
             _ => panic!("Intial state should not have endpoint set!")
         }
-        match State::get_block_from_state(&result) {
-            Err(AppError::Custom(e)) =>
-                assert!(e == get_not_in_state_err("block")),
-            _ => panic!("Intial state should not have endpoint set!")
-        }
     }
 
     #[test]
@@ -1121,18 +1295,13 @@ mod tests {
         let expected_endpoint = WORKING_ENDPOINT;
         let expected_tx_hash = get_valid_tx_hash_h256()
             .unwrap();
-        let result = get_valid_state_with_endpoint()
+        let result = get_valid_state_with_endpoint(expected_endpoint)
             .unwrap();
         assert!(result.tx_hash == expected_tx_hash);
         match State::get_endpoint_from_state(&result) {
             Ok(endpoint) => assert!(endpoint == expected_endpoint),
             _ => panic!("Intial w/ endpoint should have endpoint set!")
         }
-        match State::get_block_from_state(&result) {
-            Err(AppError::Custom(e)) =>
-                assert!(e == get_not_in_state_err("block")),
-            _ => panic!("Intial state should not have endpoint set!")
-        }
     }
 
     #[test]
@@ -1254,3 +1423,24 @@ mod tests {
         assert!(result == expected_result);
     }
 }
+
+pub fn get_sample_block_with_receipts_root(receipts_root: H256) -> Block {
+    // NOTE: The sample block, re-hashed to commit to the given receipts root & to the
+    // sample tx hashes 1.
+    let mut block = get_expected_block();
+    block.receipts_root = receipts_root;
+    block.transactions = convert_hex_strings_to_h256s(get_sample_tx_hashes_1()).unwrap();
+    block.hash = keccak_hash_bytes(&get_rlp_encoded_header(&block)).unwrap();
+    block
+}
+
+pub fn get_sample_proof_bundle(tx_index: usize) -> ProofBundle {
+    let receipts = get_sample_receipts(
+        SAMPLE_RECEIPT_JSONS_1_PATH.to_string(),
+        get_sample_tx_hashes_1(),
+    );
+    let trie = get_receipts_trie_from_receipts(&receipts).unwrap();
+    let block = get_sample_block_with_receipts_root(trie.root);
+    let branch = get_branch_from_trie(trie, tx_index).unwrap();
+    ProofBundle::new(&block, &receipts, &branch, tx_index).unwrap()
+}
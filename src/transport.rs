@@ -0,0 +1,83 @@
+use ethereum_types::H256;
+use crate::rpc_cache::RpcCache;
+use crate::get_block::get_block_by_blockhash;
+use crate::utils::convert_h256_to_prefixed_hex;
+use crate::check_block_finality::check_block_finality;
+use crate::get_receipts::{
+    get_receipt_from_tx_hash,
+    get_receipts_from_tx_hashes,
+};
+use crate::types::{
+    Block,
+    Result,
+    Receipt,
+    FinalityRequirement,
+};
+
+#[derive(Clone, Debug)]
+pub struct Transport {
+    pub endpoint: String,
+    pub maybe_cache: Option<RpcCache>,
+}
+
+impl Transport {
+    pub fn new<S: Into<String>>(endpoint: S) -> Transport {
+        Transport {
+            endpoint: endpoint.into(),
+            maybe_cache: None,
+        }
+    }
+
+    pub fn cache(mut self, cache: RpcCache) -> Transport {
+        self.maybe_cache = Some(cache);
+        self
+    }
+
+    pub fn get_block(&self, block_hash: H256) -> Result<Block> {
+        get_block_by_blockhash(&self.endpoint, block_hash, self.maybe_cache.as_ref())
+    }
+
+    pub fn get_receipt(&self, tx_hash: &H256) -> Result<Receipt> {
+        convert_h256_to_prefixed_hex(*tx_hash)
+            .and_then(|tx_hash| get_receipt_from_tx_hash(&self.endpoint, &tx_hash, self.maybe_cache.as_ref()))
+    }
+
+    pub fn get_receipts(&self, tx_hashes: &Vec<H256>) -> Result<Vec<Receipt>> {
+        get_receipts_from_tx_hashes(&self.endpoint, tx_hashes, self.maybe_cache.as_ref())
+    }
+
+    pub fn check_block_finality(
+        &self,
+        block: &Block,
+        finality_requirement: &FinalityRequirement,
+    ) -> Result<()> {
+        check_block_finality(&self.endpoint, block, finality_requirement)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{
+        PROOF_1_INDEX,
+        get_temp_cache_dir,
+        get_sample_mock_node,
+        get_expected_block_1,
+        get_sample_tx_hashes_1,
+    };
+
+    #[test]
+    fn should_get_block_and_receipts_via_transport() {
+        let mock_node = get_sample_mock_node();
+        let transport = Transport::new(mock_node.get_endpoint())
+            .cache(RpcCache::new(&get_temp_cache_dir("transport"), 1_000_000));
+        let block = get_expected_block_1();
+        let receipt = transport.get_receipt(&block.transactions[PROOF_1_INDEX])
+            .unwrap();
+        let result = transport.get_block(receipt.block_hash)
+            .unwrap();
+        assert!(result.hash == block.hash);
+        assert!(transport.get_receipts(&result.transactions).unwrap().len() == get_sample_tx_hashes_1().len());
+        assert!(transport.check_block_finality(&result, &FinalityRequirement::Confirmations(0)).is_ok());
+    }
+}
@@ -3,14 +3,14 @@ use crate::errors::AppError;
 use crate::nibble_utils::Nibbles;
 use crate::get_database::get_thing_from_database;
 use crate::get_keccak_hash::keccak_hash_bytes;
-use rlp::{
-    Rlp,
-    RlpStream
-};
+use rlp::RlpStream;
 use crate::path_codec::{
     encode_leaf_path_from_nibbles,
     encode_extension_path_from_nibbles,
-    decode_path_to_nibbles_and_node_type,
+};
+use receipt_proof_verifier::trie_nodes::{
+    self,
+    DecodedNode,
 };
 use crate::types::{
     Bytes,
@@ -234,59 +234,18 @@ impl Node {
 }
 
 pub fn rlp_decode_node(rlp_data: Bytes) -> Result<Node> {
-    match Rlp::new(&rlp_data).as_list() {
-        Err(e) => Err(AppError::Custom(e.to_string())),
-        Ok(list) => {
-            match list.len() {
-                2 => {
-                    let path: &Bytes = &list[0];
-                    let value: &Bytes = &list[1];
-                    let (
-                        path_nibbles,
-                        node_type
-                    ) = decode_path_to_nibbles_and_node_type(path.clone())?;
-                    match node_type == LEAF_NODE_STRING {
-                        true => Node::get_new_leaf_node(
-                            path_nibbles,
-                            value.to_vec(),
-                        ),
-                        false => Node::get_new_extension_node(
-                            path_nibbles,
-                            value.to_vec(),
-                        )
-                    }
-                },
-                17 => {
-                    let value: &Bytes = &list[16];
-                    let mut branches = get_empty_child_nodes();
-                    for i in 0..16 {
-                        if list[i].len() > 0 {
-                            let value: &Bytes = &list[i];
-                            branches[i] = Some(value.to_vec())
-                        }
-                    }
-                    Ok(
-                        Node {
-                            leaf: None,
-                            extension: None,
-                            branch: Some(
-                                BranchNode {
-                                    branches,
-                                    value: if value.len() > 0 {
-                                        Some(value.to_vec())
-                                     } else {
-                                         None
-                                     }
-                                }
-                            )
-                        }
-                    )
-                },
-                _ => Err(AppError::Custom(
-                    "✘ Cannot decode node from rlp data!".to_string()
-                ))
+    match trie_nodes::rlp_decode_node(&rlp_data)? {
+        DecodedNode::Leaf { path_nibbles, value } =>
+            Node::get_new_leaf_node(path_nibbles, value),
+        DecodedNode::Extension { path_nibbles, value } =>
+            Node::get_new_extension_node(path_nibbles, value),
+        DecodedNode::Branch { branches, value } => Ok(
+            Node {
+                leaf: None,
+                extension: None,
+                branch: Some(BranchNode { branches: *branches, value }),
             }
-        }
+        ),
     }
 }
 
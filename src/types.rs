@@ -1,8 +1,8 @@
 use crate::errors::AppError;
 use crate::trie_nodes::Node;
-use ethereum_types::{Address, Bloom, H160, H256, U256};
+use ethereum_types::{Address, Bloom, H160, H256, H64, U256};
 use rlp::{Encodable, RlpStream};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::result;
 
@@ -14,6 +14,22 @@ pub type Database = HashMap<H256, Bytes>;
 pub type ChildNodes = [Option<Bytes>; 16];
 pub type Result<T> = result::Result<T, AppError>;
 
+#[derive(Clone, Debug, PartialEq)]
+pub enum FinalityRequirement {
+    Confirmations(u64),
+    BlockTag(String),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OutputFormat {
+    Hex,
+    Json,
+    RlpBin,
+    Molecule,
+    Abi,
+    Bundle,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct BlockRpcResponse {
     pub result: BlockJson,
@@ -24,6 +40,11 @@ pub struct ReceiptRpcResponse {
     pub result: ReceiptJson,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct LogsRpcResponse {
+    pub result: Vec<LogJson>,
+}
+
 #[derive(Clone, Debug, Deserialize)]
 pub struct Block {
     // pub author: Address,
@@ -44,10 +65,86 @@ pub struct Block {
     pub size: U256,
     pub state_root: H256,
     pub timestamp: U256,
-    pub total_difficulty: U256,
+    pub total_difficulty: Option<U256>,
     pub transactions: Vec<H256>,
     pub transactions_root: H256,
     pub uncles: Vec<H256>,
+    pub base_fee_per_gas: Option<U256>,
+    pub withdrawals_root: Option<H256>,
+    pub blob_gas_used: Option<U256>,
+    pub excess_blob_gas: Option<U256>,
+    pub parent_beacon_block_root: Option<H256>,
+    pub requests_hash: Option<H256>,
+}
+
+impl Block {
+    fn get_nonce_as_h64(&self) -> H64 {
+        let mut bytes = [0u8; 32];
+        self.nonce.to_big_endian(&mut bytes);
+        H64::from_slice(&bytes[24..])
+    }
+
+    fn get_num_optional_header_fields(&self) -> usize {
+        [
+            self.base_fee_per_gas.is_some(),
+            self.withdrawals_root.is_some(),
+            self.blob_gas_used.is_some(),
+            self.excess_blob_gas.is_some(),
+            self.parent_beacon_block_root.is_some(),
+            self.requests_hash.is_some(),
+        ]
+            .iter()
+            .filter(|is_some| **is_some)
+            .count()
+    }
+
+    pub fn rlp_append_header(&self, rlp_stream: &mut RlpStream, with_seal: bool) {
+        let num_seal_fields = if with_seal { 2 } else { 0 };
+        let rlp = rlp_stream.begin_list(
+            13 + num_seal_fields + self.get_num_optional_header_fields()
+        );
+        rlp.append(&self.parent_hash)
+            .append(&self.sha3_uncles)
+            .append(&self.miner)
+            .append(&self.state_root)
+            .append(&self.transactions_root)
+            .append(&self.receipts_root)
+            .append(&self.logs_bloom)
+            .append(&self.difficulty)
+            .append(&self.number)
+            .append(&self.gas_limit)
+            .append(&self.gas_used)
+            .append(&self.timestamp)
+            .append(&self.extra_data);
+        if with_seal {
+            rlp.append(&self.mix_hash)
+                .append(&self.get_nonce_as_h64());
+        }
+        if let Some(base_fee_per_gas) = &self.base_fee_per_gas {
+            rlp.append(base_fee_per_gas);
+        }
+        if let Some(withdrawals_root) = &self.withdrawals_root {
+            rlp.append(withdrawals_root);
+        }
+        if let Some(blob_gas_used) = &self.blob_gas_used {
+            rlp.append(blob_gas_used);
+        }
+        if let Some(excess_blob_gas) = &self.excess_blob_gas {
+            rlp.append(excess_blob_gas);
+        }
+        if let Some(parent_beacon_block_root) = &self.parent_beacon_block_root {
+            rlp.append(parent_beacon_block_root);
+        }
+        if let Some(requests_hash) = &self.requests_hash {
+            rlp.append(requests_hash);
+        }
+    }
+}
+
+impl Encodable for Block { // NOTE: Encodes the header only.
+    fn rlp_append(&self, rlp_stream: &mut RlpStream) {
+        self.rlp_append_header(rlp_stream, true)
+    }
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -65,10 +162,12 @@ pub struct Receipt {
     pub logs: Vec<Log>,
     // pub root: H256,
     pub logs_bloom: Bloom,
+    #[serde(default)]
+    pub tx_type: Option<u8>,
 }
 
-impl Encodable for Receipt {
-    fn rlp_append(&self, rlp_stream: &mut RlpStream) {
+impl Receipt {
+    fn rlp_append_receipt(&self, rlp_stream: &mut RlpStream) {
         let rlp = rlp_stream.begin_list(4);
         match &self.status {
             true => rlp.append(&self.status),
@@ -80,6 +179,22 @@ impl Encodable for Receipt {
     }
 }
 
+impl Encodable for Receipt {
+    fn rlp_append(&self, rlp_stream: &mut RlpStream) {
+        // NOTE: EIP-2718 typed receipts are `type || rlp(receipt)`, as committed to by the trie.
+        match self.tx_type {
+            None => self.rlp_append_receipt(rlp_stream),
+            Some(tx_type) => {
+                let mut receipt_stream = RlpStream::new();
+                self.rlp_append_receipt(&mut receipt_stream);
+                let mut typed_receipt = vec![tx_type];
+                typed_receipt.append(&mut receipt_stream.out());
+                rlp_stream.append_raw(&typed_receipt, 1);
+            },
+        }
+    }
+}
+
 #[derive(Clone, Debug, Deserialize)]
 pub struct Log {
     pub address: Address,
@@ -100,11 +215,10 @@ pub struct Log {
 impl Encodable for Log {
     fn rlp_append(&self, rlp_stream: &mut RlpStream) {
         rlp_stream
-            .begin_list(4)
+            .begin_list(3)
             .append(&self.address)
             .append_list(&self.topics)
-            .append(&self.data)
-            .append(&self.log_index);
+            .append(&self.data);
     }
 }
 
@@ -129,15 +243,22 @@ pub struct BlockJson {
     pub size: String,
     pub stateRoot: String,
     pub timestamp: String,
-    pub totalDifficulty: String,
+    pub totalDifficulty: Option<String>,
     pub transactions: Vec<String>,
     pub transactionsRoot: String,
     pub uncles: Vec<String>,
+    pub baseFeePerGas: Option<String>,
+    pub withdrawalsRoot: Option<String>,
+    pub blobGasUsed: Option<String>,
+    pub excessBlobGas: Option<String>,
+    pub parentBeaconBlockRoot: Option<String>,
+    pub requestsHash: Option<String>,
 }
 
 #[allow(non_snake_case)]
 #[derive(Debug, Deserialize)]
 pub struct ReceiptJson {
+    pub r#type: Option<String>,
     pub from: String,
     pub status: String,
     pub gasUsed: String,
@@ -168,6 +289,17 @@ pub struct LogJson {
     pub transactionIndex: String,
 }
 
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ReceiptProof {
+    pub tx_hash: H256,
+    pub tx_index: usize,
+    pub block_hash: H256,
+    pub block_number: u64,
+    pub receipts_root: H256,
+    pub receipt_data: String,
+    pub proof: HexProof,
+}
+
 #[derive(Clone, Debug, Deserialize, Default)]
 pub struct EthSpvProof {
     pub log_index: i32,
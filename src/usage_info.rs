@@ -6,22 +6,157 @@ pub static USAGE_INFO: &'static str = "
 
 ❍ Info ❍
 
-This tool generates a merkle receipt proof of the receipt pertaining to the given transaction hash.
+This tool generates a merkle receipt proof of the receipt pertaining to the given transaction hash, & helps debug such proofs.
 
 ***
 
 Usage:  rusty-receipt-proof-maker [--help]
-        rusty-receipt-proof-maker <txhash> [--verbose | -v] [--disableLogs | -d]
+        rusty-receipt-proof-maker prove <txhash> [--verbose | -v] [--disableLogs | -d] [--endpoint=<url>] [--config=<path>] [--output=<format>] [--log-index=<n>] [--signature=<sig>] [--from-files=<path> --receipts=<dir>] [--cache-dir=<path>] [--cache-size=<mb>] [--confirmations=<n> | --block-tag=<tag>]
+        rusty-receipt-proof-maker prove-batch [<file>] [--verbose | -v] [--disableLogs | -d] [--endpoint=<url>] [--config=<path>] [--cache-dir=<path>] [--cache-size=<mb>] [--confirmations=<n> | --block-tag=<tag>]
+        rusty-receipt-proof-maker verify <proof> [--root=<hash> | --block-hash=<hash>] [--tx-index=<n>] [--verbose | -v] [--disableLogs | -d] [--endpoint=<url>] [--config=<path>]
+        rusty-receipt-proof-maker verify-bundle <bundle> [--verbose | -v] [--disableLogs | -d]
+        rusty-receipt-proof-maker header <block> [--verbose | -v] [--disableLogs | -d] [--endpoint=<url>] [--config=<path>]
+        rusty-receipt-proof-maker decode <proof> [--verbose | -v] [--disableLogs | -d]
+        rusty-receipt-proof-maker root <block> [--verbose | -v] [--disableLogs | -d] [--endpoint=<url>] [--config=<path>]
+        rusty-receipt-proof-maker serve [--address=<addr>] [--max-requests=<n>] [--verbose | -v] [--disableLogs | -d] [--endpoint=<url>] [--config=<path>] [--cache-dir=<path>] [--cache-size=<mb>] [--confirmations=<n> | --block-tag=<tag>]
+        rusty-receipt-proof-maker watch [--contract=<addrs>] [--topic=<hashes>] [--from-block=<n>] [--out-dir=<path>] [--poll-interval=<s>] [--verbose | -v] [--disableLogs | -d] [--endpoint=<url>] [--config=<path>] [--cache-dir=<path>] [--cache-size=<mb>] [--confirmations=<n> | --block-tag=<tag>]
+        rusty-receipt-proof-maker <txhash> [--verbose | -v] [--disableLogs | -d] [--endpoint=<url>] [--config=<path>] [--output=<format>] [--log-index=<n>] [--signature=<sig>] [--from-files=<path> --receipts=<dir>] [--cache-dir=<path>] [--cache-size=<mb>] [--confirmations=<n> | --block-tag=<tag>]
+
+Commands:
+
+    prove                ❍ Generate a receipt proof for a transaction. This is also
+                           what runs when just a transaction hash is given.
+
+    prove-batch          ❍ Generate receipt proofs for many transactions, fetching
+                           each block's receipts only once. Writes one JSON line
+                           per transaction, holding either its proof or an error.
+
+    verify               ❍ Check a proof file against a receipts root, or against
+                           the receipts root in the header of the given block, &
+                           print the proven RLP-encoded receipt.
+
+    verify-bundle        ❍ Check a proof bundle written by `--output=bundle` without
+                           a node: its checksum, its header against its block
+                           hash, its receipts against the header's receipts root
+                           & its branch against the proven receipt, which is
+                           printed RLP-encoded.
+
+    header               ❍ Print the RLP-encoded header of a block, after checking
+                           it hashes to the block's hash.
+
+    decode               ❍ Pretty-print the trie nodes in a proof file.
+
+    root                 ❍ Recompute a block's receipts root & logs bloom from its
+                           receipts & check they match the block's header.
+
+    serve                ❍ Serve proofs over HTTP via the JSON-RPC methods
+                           `getReceiptProof`, `getLogProof`, `getHeader` &
+                           `verifyProof`, until stopped with SIGINT or SIGTERM.
+
+    watch                ❍ Follow new final blocks & write a proof for every log
+                           matching the `--contract` & `--topic` filters, until
+                           stopped with SIGINT or SIGTERM. Reorged blocks are
+                           re-processed.
 
 Options:
 
-    --help              ❍ Show this message.
+    --help               ❍ Show this message.
+
+    -d, --disableLogs    ❍  Disable logging to file.
+
+    -v, --verbose        ❍ Enable verbose mode for additional output.
+
+    --endpoint=<url>     ❍ RPC endpoint of the node to get blocks & receipts from.
+                           Takes precedence over the `ENDPOINT` env var, then the
+                           `ENDPOINT` in a `.env` file, then the config file.
+
+    --config=<path>      ❍ JSON config file with default `endpoint`, `output`,
+                           `block_tag` & `confirmations` values.
+                         ➔ Default: ./config.json
+
+    --output=<format>    ❍ Format of the proof written to stdout.
+                         ➔ Format: One of `hex`, `json`, `rlp-bin`, `molecule`,
+                           `abi` or `bundle`.
+                         ➔ Default: hex
+
+    --log-index=<n>      ❍ Include the RLP-encoded log at this index of the
+                           receipt in the `json` output.
+
+    --signature=<sig>    ❍ Tuple signature of the `abi` output. Fields can be a
+                           `header`, `receipt` or `proof` as `bytes`, the `proof`
+                           as `bytes[]` of nodes, the `index` or `block_number`
+                           as a `uint`, or the `tx_hash`, `block_hash` or
+                           `receipts_root` as `bytes32`.
+                         ➔ Default: (bytes header, bytes[] proof, uint256 index, bytes receipt)
+
+    --cache-dir=<path>   ❍ Cache fetched blocks & receipts from finalized blocks
+                           in this directory & reuse them on later runs.
+
+    --cache-size=<mb>    ❍ Maximum size of the cache directory in megabytes. The
+                           oldest entries are evicted when it's exceeded.
+                         ➔ Default: 256
+
+    --confirmations=<n>  ❍ Minimum number of blocks that must be mined on top of
+                           the transaction's block before a proof is generated.
+                         ➔ Default: 64
+
+    --block-tag=<tag>    ❍ Instead of counting confirmations, require the block to
+                           be at or below the node's `finalized` or `safe` block.
+                         ➔ Format: One of `finalized` or `safe`.
+
+    --from-files=<path>  ❍ Generate the proof offline from this block JSON file,
+                           as returned by `eth_getBlockByHash` (bare or wrapped
+                           in the RPC response), instead of querying a node.
+
+    --receipts=<dir>     ❍ Directory holding a file per transaction of the
+                           `--from-files` block, named by its hash. Each holds
+                           the receipt's JSON, its RLP as hex, or its raw RLP.
+
+    --root=<hash>        ❍ Receipts root to verify a proof against.
+
+    --block-hash=<hash>  ❍ Hash of the block whose receipts root to verify a proof
+                           against. Defaults to the `block_hash` of a `json` proof.
+
+    --tx-index=<n>       ❍ Index of the proven receipt in its block. Defaults to
+                           the `tx_index` of a `json` proof.
+
+    --address=<addr>     ❍ Address for the `serve` command to listen on.
+                         ➔ Default: 127.0.0.1:8547
+
+    --max-requests=<n>   ❍ Maximum number of requests the `serve` command handles
+                           at once. Further requests wait for a free slot.
+                         ➔ Default: 8
+
+    --contract=<addrs>   ❍ Comma-separated contract addresses whose logs `watch`
+                           proves.
+
+    --topic=<hashes>     ❍ Comma-separated topic hashes, any of which a log must
+                           have for `watch` to prove it.
+
+    --from-block=<n>     ❍ Block number for `watch` to start from. Defaults to the
+                           latest final block.
+
+    --out-dir=<path>     ❍ Directory for `watch` to write each proof to as its own
+                           JSON file, instead of JSON lines to stdout.
+
+    --poll-interval=<s>  ❍ Seconds `watch` waits between polls for new blocks.
+                         ➔ Default: 12
+
+    <txhash>             ❍ A transaction hash of an Ethereum transaction
+                         ➔ Format: A 32-byte long, prefixed hex string.
+
+    <file>               ❍ File of transaction hashes, one per line. Blank lines &
+                           lines starting with `#` are skipped.
+                         ➔ Default: stdin
 
-    -d, --disableLogs   ❍  Disable logging to file.
+    <proof>              ❍ Path to a proof file, as written by `prove` in any of
+                           its output formats.
 
-    -v, --verbose       ❍ Enable verbose mode for additional output.
+    <bundle>             ❍ Path to a proof bundle, as written by `prove` with
+                           `--output=bundle`.
 
-    <txhash>            ❍ A transaction hash of an Ethereum transaction
-                        ➔ Format: A 32-byte long, prefixed hex string.
+    <block>              ❍ A block hash, block number or block tag.
+                         ➔ Format: A prefixed hex hash, a decimal number, or one
+                           of `latest`, `safe` or `finalized`.
 
 ";
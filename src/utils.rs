@@ -127,6 +127,22 @@ pub fn convert_h256_to_bytes(hash: H256) -> Bytes {
         .to_vec()
 }
 
+pub fn convert_bytes_to_prefixed_hex(bytes: &[u8]) -> String {
+    format!("0x{}", convert_bytes_to_hex(bytes.to_vec()))
+}
+
+pub fn get_bytes_from_field(name: &str, value: &str) -> Result<Bytes> {
+    match value.starts_with("0x") {
+        true => convert_hex_to_bytes(value.to_string())
+            .map_err(|_| AppError::Custom(
+                format!("✘ Field `{}` is not valid hex: {}", name, value)
+            )),
+        false => Err(AppError::Custom(
+            format!("✘ Field `{}` must be a 0x-prefixed hex string!", name)
+        )),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -169,6 +185,28 @@ mod tests {
         assert!(result == expected_result);
     }
 
+    #[test]
+    fn should_convert_bytes_to_prefixed_hex_correctly() {
+        assert!(convert_bytes_to_prefixed_hex(&[192, 255, 238]) == "0xc0ffee");
+        assert!(convert_bytes_to_prefixed_hex(&[]) == "0x");
+    }
+
+    #[test]
+    fn should_get_bytes_from_prefixed_hex_field() {
+        let result = get_bytes_from_field("data", "0xc0ffee")
+            .unwrap();
+        assert!(result == [192, 255, 238]);
+        assert!(get_bytes_from_field("data", "0x").unwrap().is_empty());
+    }
+
+    #[test]
+    fn should_err_when_getting_bytes_from_unprefixed_hex_field() {
+        match get_bytes_from_field("data", "c0ffee") {
+            Err(AppError::Custom(e)) => assert!(e.contains("`data` must be a 0x-prefixed")),
+            _ => panic!("Unprefixed hex field should not be accepted!"),
+        }
+    }
+
     #[test]
     fn should_convert_unprefixed_hex_to_bytes_correctly() {
         let hex = "c0ffee".to_string();
@@ -0,0 +1,144 @@
+use ethereum_types::H256;
+use receipt_proof_verifier::verify_proof;
+use crate::types::{
+    Bytes,
+    Result,
+};
+
+pub fn verify_receipt_proof(
+    proof_nodes: &[Bytes],
+    receipts_root: &H256,
+    tx_index: usize,
+) -> Result<Bytes> {
+    info!(
+        "✔ Verifying proof of receipt at index {} against root {:?}...",
+        tx_index,
+        receipts_root,
+    );
+    Ok(verify_proof::verify_receipt_proof(proof_nodes, &receipts_root.0, tx_index)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::AppError;
+    use crate::trie_nodes::rlp_decode_node;
+    use crate::decode_proof::get_proof_nodes;
+    use crate::utils::{
+        convert_hex_to_h256,
+        convert_hex_to_bytes,
+    };
+    use receipt_proof_verifier::decode_receipt;
+    use crate::test_utils::{
+        PROOF_1_INDEX,
+        PROOF_3_INDEX,
+        RECEIPTS_ROOT_1,
+        RECEIPTS_ROOT_3,
+        get_sample_proof_1,
+        get_sample_proof_3,
+        get_sample_receipts,
+        get_sample_tx_hashes_1,
+        SAMPLE_RECEIPT_JSONS_1_PATH,
+    };
+
+    fn get_proof_nodes_from_hex(hex_proof: String) -> Vec<Bytes> {
+        get_proof_nodes(&convert_hex_to_bytes(hex_proof).unwrap())
+            .unwrap()
+    }
+
+    fn get_receipts_root_1() -> H256 {
+        convert_hex_to_h256(RECEIPTS_ROOT_1.to_string()).unwrap()
+    }
+
+    #[test]
+    fn should_verify_receipt_proof_1() {
+        let proof_nodes = get_proof_nodes_from_hex(get_sample_proof_1());
+        let expected_receipt = rlp_decode_node(proof_nodes[2].clone())
+            .unwrap()
+            .get_value()
+            .unwrap();
+        let result = verify_receipt_proof(
+            &proof_nodes,
+            &get_receipts_root_1(),
+            PROOF_1_INDEX,
+        ).unwrap();
+        assert!(result == expected_receipt);
+    }
+
+    #[test]
+    fn should_verify_receipt_proof_3() {
+        let result = verify_receipt_proof(
+            &get_proof_nodes_from_hex(get_sample_proof_3()),
+            &convert_hex_to_h256(RECEIPTS_ROOT_3.to_string()).unwrap(),
+            PROOF_3_INDEX,
+        );
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn should_err_on_wrong_receipts_root() {
+        let root = convert_hex_to_h256(RECEIPTS_ROOT_3.to_string()).unwrap();
+        match verify_receipt_proof(
+            &get_proof_nodes_from_hex(get_sample_proof_1()),
+            &root,
+            PROOF_1_INDEX,
+        ) {
+            Err(AppError::Custom(e)) => assert!(e.contains("hashes to")),
+            _ => panic!("Proof should not verify against wrong root!"),
+        }
+    }
+
+    #[test]
+    fn should_err_on_wrong_tx_index() {
+        match verify_receipt_proof(
+            &get_proof_nodes_from_hex(get_sample_proof_1()),
+            &get_receipts_root_1(),
+            0x30,
+        ) {
+            Err(AppError::Custom(e)) => assert!(e.contains("does not contain")),
+            _ => panic!("Proof should not verify for wrong index!"),
+        }
+    }
+
+    #[test]
+    fn should_err_on_tampered_leaf() {
+        let mut proof_nodes = get_proof_nodes_from_hex(get_sample_proof_1());
+        let last_byte = proof_nodes[2].len() - 1;
+        proof_nodes[2][last_byte] ^= 1;
+        match verify_receipt_proof(&proof_nodes, &get_receipts_root_1(), PROOF_1_INDEX) {
+            Err(AppError::Custom(e)) => assert!(e.contains("hashes to")),
+            _ => panic!("Tampered proof should not verify!"),
+        }
+    }
+
+    #[test]
+    fn should_err_on_truncated_proof() {
+        let proof_nodes = get_proof_nodes_from_hex(get_sample_proof_1());
+        match verify_receipt_proof(&proof_nodes[..2], &get_receipts_root_1(), PROOF_1_INDEX) {
+            Err(AppError::Custom(e)) => assert!(e.contains("ends before")),
+            _ => panic!("Truncated proof should not verify!"),
+        }
+    }
+
+    #[test]
+    fn should_decode_receipts_encoded_by_prover() {
+        get_sample_receipts(SAMPLE_RECEIPT_JSONS_1_PATH.to_string(), get_sample_tx_hashes_1())
+            .iter()
+            .for_each(|receipt| {
+                let result = decode_receipt(&rlp::encode(receipt))
+                    .unwrap();
+                assert!(result.is_successful() == receipt.status);
+                assert!(result.cumulative_gas_used == receipt.cumulative_gas_used.as_u64());
+                assert!(result.logs_bloom == receipt.logs_bloom.as_bytes());
+                assert!(result.logs.len() == receipt.logs.len());
+                result.logs
+                    .iter()
+                    .zip(receipt.logs.iter())
+                    .for_each(|(decoded_log, log)| {
+                        assert!(decoded_log.address == log.address.0);
+                        assert!(decoded_log.topics.iter().eq(log.topics.iter().map(|topic| &topic.0)));
+                        assert!(decoded_log.data == log.data);
+                    });
+            });
+    }
+}
@@ -0,0 +1,201 @@
+use serde_json::Value as Json;
+use wasm_bindgen::prelude::*;
+use crate::errors::AppError;
+use crate::decode_proof::{
+    get_proof_nodes,
+    get_proof_file_from_bytes,
+    get_decoded_proof_string,
+};
+use crate::verify_proof::verify_receipt_proof;
+use crate::get_receipts_trie::get_receipts_trie_from_encoded_receipts;
+use crate::utils::{
+    convert_hex_to_h256,
+    convert_hex_to_bytes,
+    convert_h256_to_prefixed_hex,
+    convert_bytes_to_prefixed_hex,
+};
+use crate::types::{
+    Bytes,
+    Result,
+};
+use receipt_proof_verifier::{
+    DecodedLog,
+    DecodedReceipt,
+    decode_receipt,
+};
+
+fn get_decoded_log_json(log: &DecodedLog) -> Json {
+    json!({
+        "address": convert_bytes_to_prefixed_hex(&log.address),
+        "topics": log.topics
+            .iter()
+            .map(|topic| convert_bytes_to_prefixed_hex(topic))
+            .collect::<Vec<String>>(),
+        "data": convert_bytes_to_prefixed_hex(&log.data),
+    })
+}
+
+fn get_decoded_receipt_json(receipt_data: &Bytes, receipt: &DecodedReceipt) -> Json {
+    json!({
+        "receipt": convert_bytes_to_prefixed_hex(receipt_data),
+        "tx_type": receipt.tx_type,
+        "status": receipt.is_successful(),
+        "cumulative_gas_used": receipt.cumulative_gas_used,
+        "logs_bloom": convert_bytes_to_prefixed_hex(&receipt.logs_bloom),
+        "logs": receipt.logs
+            .iter()
+            .map(get_decoded_log_json)
+            .collect::<Vec<Json>>(),
+    })
+}
+
+pub fn get_verified_receipt_json(
+    proof: &str,
+    receipts_root: &str,
+    tx_index: Option<usize>,
+) -> Result<String> {
+    let proof_file = get_proof_file_from_bytes(proof.as_bytes().to_vec())?;
+    let tx_index = match tx_index.or(proof_file.tx_index) {
+        Some(tx_index) => tx_index,
+        None => return Err(AppError::Custom(
+            "✘ Need a tx index to verify a proof without one!".to_string()
+        ))
+    };
+    let receipt_data = get_proof_nodes(&proof_file.proof)
+        .and_then(|proof_nodes|
+            verify_receipt_proof(
+                &proof_nodes,
+                &convert_hex_to_h256(receipts_root.to_string())?,
+                tx_index,
+            )
+        )?;
+    decode_receipt(&receipt_data)
+        .map_err(AppError::from)
+        .map(|receipt| get_decoded_receipt_json(&receipt_data, &receipt).to_string())
+}
+
+pub fn get_decoded_proof(proof: &str) -> Result<String> {
+    get_proof_file_from_bytes(proof.as_bytes().to_vec())
+        .and_then(|proof_file| get_decoded_proof_string(&proof_file.proof))
+}
+
+pub fn get_receipts_root_from_hex_receipts(hex_receipts: &[String]) -> Result<String> {
+    hex_receipts
+        .iter()
+        .map(|hex_receipt| convert_hex_to_bytes(hex_receipt.clone()))
+        .collect::<Result<Vec<Bytes>>>()
+        .and_then(|encoded_receipts| get_receipts_trie_from_encoded_receipts(&encoded_receipts))
+        .and_then(|trie| convert_h256_to_prefixed_hex(trie.root))
+}
+
+fn convert_app_error_to_js_value(e: AppError) -> JsValue {
+    JsValue::from_str(&e.to_string())
+}
+
+/// Verifies a proof (in any format `prove` outputs) against a receipts root,
+/// returning the proven receipt & its decoded logs as a JSON string.
+#[wasm_bindgen(js_name = verifyReceiptProof)]
+pub fn verify_receipt_proof_js(
+    proof: &str,
+    receipts_root: &str,
+    tx_index: Option<u32>,
+) -> std::result::Result<String, JsValue> {
+    get_verified_receipt_json(proof, receipts_root, tx_index.map(|index| index as usize))
+        .map_err(convert_app_error_to_js_value)
+}
+
+/// Decodes the trie nodes of a proof into a human-readable string.
+#[wasm_bindgen(js_name = decodeProof)]
+pub fn decode_proof_js(proof: &str) -> std::result::Result<String, JsValue> {
+    get_decoded_proof(proof)
+        .map_err(convert_app_error_to_js_value)
+}
+
+/// Computes the receipts root of a block from its hex-encoded receipts, in tx order.
+#[wasm_bindgen(js_name = getReceiptsRoot)]
+pub fn get_receipts_root_js(hex_receipts: Vec<String>) -> std::result::Result<String, JsValue> {
+    get_receipts_root_from_hex_receipts(&hex_receipts)
+        .map_err(convert_app_error_to_js_value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::get_hex_proof_from_branch::get_hex_proof_from_branch;
+    use crate::get_branch_from_trie::get_branch_from_trie;
+    use crate::test_utils::{
+        PROOF_1_INDEX,
+        RECEIPTS_ROOT_1,
+        get_sample_proof_1,
+    };
+
+    fn get_sample_receipt_hex() -> String {
+        let json: Json = serde_json::from_str(
+            &get_verified_receipt_json(&get_sample_proof_1(), RECEIPTS_ROOT_1, Some(PROOF_1_INDEX))
+                .unwrap()
+        ).unwrap();
+        json["receipt"].as_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn should_verify_proof_and_decode_receipt() {
+        let result = get_verified_receipt_json(
+            &get_sample_proof_1(),
+            RECEIPTS_ROOT_1,
+            Some(PROOF_1_INDEX),
+        ).unwrap();
+        let json: Json = serde_json::from_str(&result).unwrap();
+        assert!(json["status"] == true);
+        assert!(json["tx_type"].is_null());
+        assert!(!json["logs"].as_array().unwrap().is_empty());
+        assert!(json["logs"][0]["address"] == "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2");
+    }
+
+    #[test]
+    fn should_verify_json_proof_with_tx_index() {
+        let proof_json = json!({
+            "proof": get_sample_proof_1(),
+            "tx_index": PROOF_1_INDEX,
+        });
+        let result = get_verified_receipt_json(&proof_json.to_string(), RECEIPTS_ROOT_1, None);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn should_err_on_proof_without_tx_index() {
+        match get_verified_receipt_json(&get_sample_proof_1(), RECEIPTS_ROOT_1, None) {
+            Err(AppError::Custom(e)) => assert!(e.contains("Need a tx index")),
+            _ => panic!("Proof without tx index should not verify!"),
+        }
+    }
+
+    #[test]
+    fn should_decode_proof() {
+        let result = get_decoded_proof(&get_sample_proof_1())
+            .unwrap();
+        assert!(result.starts_with("Node 0: branch 0x"));
+        assert!(result.contains("Node 2: leaf"));
+    }
+
+    #[test]
+    fn should_get_receipts_root_that_proofs_verify_against() {
+        let tx_index = 3;
+        let receipt_hex = get_sample_receipt_hex();
+        let hex_receipts = vec![receipt_hex.clone(); 5];
+        let receipts_root = get_receipts_root_from_hex_receipts(&hex_receipts)
+            .unwrap();
+        let encoded_receipts = hex_receipts
+            .iter()
+            .map(|hex_receipt| convert_hex_to_bytes(hex_receipt.clone()).unwrap())
+            .collect::<Vec<Bytes>>();
+        let trie = get_receipts_trie_from_encoded_receipts(&encoded_receipts)
+            .unwrap();
+        let proof = get_branch_from_trie(trie, tx_index)
+            .and_then(|branch| get_hex_proof_from_branch(&branch))
+            .unwrap();
+        let result = get_verified_receipt_json(&proof, &receipts_root, Some(tx_index))
+            .unwrap();
+        let json: Json = serde_json::from_str(&result).unwrap();
+        assert!(json["receipt"] == receipt_hex);
+    }
+}
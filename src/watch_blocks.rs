@@ -0,0 +1,435 @@
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::collections::VecDeque;
+use ethereum_types::{
+    H256,
+    Bloom,
+    Address,
+};
+use crate::errors::AppError;
+use crate::rpc_cache::RpcCache;
+use crate::get_block::get_block_by_number;
+use crate::get_log::{
+    bloom_may_contain,
+    check_logs_bloom_of_block,
+};
+use crate::get_output_from_state::get_log_entry_data;
+use crate::get_receipts::get_receipts_from_tx_hashes;
+use crate::block_proof_context::BlockProofContext;
+use crate::types::{
+    Log,
+    Block,
+    Result,
+    ReceiptProof,
+    FinalityRequirement,
+};
+
+pub const DEFAULT_POLL_INTERVAL_IN_SECS: u64 = 12;
+const MAX_RECENT_BLOCK_HASHES: usize = 256;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LogFilter {
+    pub addresses: Vec<Address>,
+    pub topics: Vec<H256>,
+}
+
+impl LogFilter {
+    pub fn is_empty(&self) -> bool {
+        self.addresses.is_empty() && self.topics.is_empty()
+    }
+
+    pub fn matches_log(&self, log: &Log) -> bool {
+        (self.addresses.is_empty() || self.addresses.contains(&log.address)) &&
+        (self.topics.is_empty() || log.topics.iter().any(|topic| self.topics.contains(topic)))
+    }
+
+    pub fn may_match_bloom(&self, bloom: &Bloom) -> bool {
+        (
+            self.addresses.is_empty() ||
+            self.addresses.iter().any(|address| bloom_may_contain(bloom, Some(address), &[]))
+        ) && (
+            self.topics.is_empty() ||
+            self.topics.iter().any(|topic| bloom_may_contain(bloom, None, &[*topic]))
+        )
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct LogProof {
+    #[serde(flatten)]
+    pub receipt_proof: ReceiptProof,
+    pub log_index: usize,
+    pub log_entry_data: String,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum WatchEvent {
+    LogProof(LogProof),
+    Reorg { block_number: u64, block_hash: H256 },
+}
+
+impl WatchEvent {
+    pub fn to_json_line(&self) -> Result<String> {
+        match self {
+            WatchEvent::LogProof(log_proof) => Ok(serde_json::to_string(log_proof)?),
+            WatchEvent::Reorg { block_number, block_hash } => Ok(
+                json!({
+                    "reorg": {
+                        "block_number": block_number,
+                        "block_hash": format!("{:?}", block_hash),
+                    }
+                }).to_string()
+            ),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum WatchOutput {
+    Stdout,
+    Dir(PathBuf),
+}
+
+fn get_proof_file_prefix(block_number: u64, block_hash: &H256) -> String {
+    format!("{}-{:?}-", block_number, block_hash)
+}
+
+fn write_event_to_dir(dir: &PathBuf, event: &WatchEvent) -> Result<()> {
+    match event {
+        WatchEvent::LogProof(log_proof) => {
+            let receipt_proof = &log_proof.receipt_proof;
+            let path = dir.join(
+                format!(
+                    "{}{}-{}.json",
+                    get_proof_file_prefix(receipt_proof.block_number, &receipt_proof.block_hash),
+                    receipt_proof.tx_index,
+                    log_proof.log_index,
+                )
+            );
+            Ok(fs::write(path, event.to_json_line()?)?)
+        },
+        WatchEvent::Reorg { block_number, block_hash } => {
+            let prefix = get_proof_file_prefix(*block_number, block_hash);
+            fs::read_dir(dir)?
+                .map(|entry| Ok(entry?.path()))
+                .collect::<Result<Vec<PathBuf>>>()?
+                .iter()
+                .filter(|path| match path.file_name() {
+                    Some(name) => name.to_string_lossy().starts_with(&prefix),
+                    None => false,
+                })
+                .try_for_each(|path| Ok(fs::remove_file(path)?))
+        },
+    }
+}
+
+pub fn write_watch_events(output: &WatchOutput, events: &[WatchEvent]) -> Result<()> {
+    match output {
+        WatchOutput::Stdout => {
+            let stdout = std::io::stdout();
+            let mut handle = stdout.lock();
+            events
+                .iter()
+                .try_for_each(|event| Ok(writeln!(handle, "{}", event.to_json_line()?)?))
+                .and_then(|_| Ok(handle.flush()?))
+        },
+        WatchOutput::Dir(dir) => {
+            fs::create_dir_all(dir)?;
+            events
+                .iter()
+                .try_for_each(|event| write_event_to_dir(dir, event))
+        },
+    }
+}
+
+pub fn get_log_proofs_from_block(
+    endpoint: &str,
+    block: &Block,
+    filter: &LogFilter,
+    maybe_cache: Option<&RpcCache>,
+) -> Result<Vec<LogProof>> {
+    if !filter.may_match_bloom(&block.logs_bloom) {
+        info!("✔ Skipping block {}: its logs bloom has no matches!", block.number);
+        return Ok(vec![])
+    }
+    let receipts = get_receipts_from_tx_hashes(endpoint, &block.transactions, maybe_cache)?;
+    if let Err(e) = check_logs_bloom_of_block(block, &receipts) {
+        warn!("{} Blocks skipped via their logs bloom may have been missed!", e);
+    }
+    let matching_log_indices: Vec<(usize, usize)> = receipts
+        .iter()
+        .enumerate()
+        .flat_map(|(tx_index, receipt)|
+            receipt.logs
+                .iter()
+                .enumerate()
+                .filter(|(_, log)| filter.matches_log(log))
+                .map(move |(log_index, _)| (tx_index, log_index))
+        )
+        .collect();
+    info!("✔ Found {} matching logs in block {}", matching_log_indices.len(), block.number);
+    if matching_log_indices.is_empty() {
+        return Ok(vec![])
+    }
+    let block_proof_context = BlockProofContext::new(block.clone(), receipts)?;
+    matching_log_indices
+        .iter()
+        .map(|(tx_index, log_index)|
+            block_proof_context
+                .get_receipt_proof_for_index(*tx_index)
+                .and_then(|receipt_proof|
+                    get_log_entry_data(&block_proof_context.receipts[*tx_index], *log_index)
+                        .map(|log_entry_data|
+                            LogProof { receipt_proof, log_index: *log_index, log_entry_data }
+                        )
+                )
+        )
+        .collect()
+}
+
+pub struct BlockWatcher {
+    endpoint: String,
+    filter: LogFilter,
+    maybe_cache: Option<RpcCache>,
+    finality_requirement: FinalityRequirement,
+    recent_block_hashes: VecDeque<(u64, H256)>,
+    pub next_block_number: u64,
+}
+
+impl BlockWatcher {
+    pub fn new(
+        endpoint: String,
+        filter: LogFilter,
+        finality_requirement: FinalityRequirement,
+        maybe_cache: Option<RpcCache>,
+        maybe_from_block: Option<u64>,
+    ) -> Result<BlockWatcher> {
+        let mut watcher = BlockWatcher {
+            filter,
+            endpoint,
+            maybe_cache,
+            finality_requirement,
+            next_block_number: 0,
+            recent_block_hashes: VecDeque::new(),
+        };
+        watcher.next_block_number = match maybe_from_block {
+            Some(from_block) => from_block,
+            None => watcher.get_final_block_number()?,
+        };
+        info!("✔ Watching for logs from block {}...", watcher.next_block_number);
+        Ok(watcher)
+    }
+
+    fn get_final_block_number(&self) -> Result<u64> {
+        match &self.finality_requirement {
+            FinalityRequirement::Confirmations(min_confirmations) =>
+                get_block_by_number(&self.endpoint, "latest")
+                    .map(|block| block.number.as_u64().saturating_sub(*min_confirmations)),
+            FinalityRequirement::BlockTag(tag) =>
+                get_block_by_number(&self.endpoint, tag)
+                    .map(|block| block.number.as_u64()),
+        }
+    }
+
+    fn get_recorded_block_hash(&self, block_number: u64) -> Option<H256> {
+        self.recent_block_hashes
+            .iter()
+            .find(|(number, _)| *number == block_number)
+            .map(|(_, hash)| *hash)
+    }
+
+    fn record_block_hash(&mut self, block_number: u64, block_hash: H256) {
+        self.recent_block_hashes.push_back((block_number, block_hash));
+        if self.recent_block_hashes.len() > MAX_RECENT_BLOCK_HASHES {
+            self.recent_block_hashes.pop_front();
+        }
+    }
+
+    fn rewind_one_block(&mut self, reorged_block_hash: H256) -> WatchEvent {
+        self.recent_block_hashes.pop_back();
+        self.next_block_number -= 1;
+        warn!(
+            "✘ Block {:?} at height {} was reorged out, re-processing its height!",
+            reorged_block_hash,
+            self.next_block_number,
+        );
+        WatchEvent::Reorg { block_number: self.next_block_number, block_hash: reorged_block_hash }
+    }
+
+    pub fn process_next_block(&mut self) -> Result<Vec<WatchEvent>> {
+        let block = get_block_by_number(&self.endpoint, &self.next_block_number.to_string())?;
+        let maybe_parent_hash = self.next_block_number
+            .checked_sub(1)
+            .and_then(|parent_number| self.get_recorded_block_hash(parent_number));
+        match maybe_parent_hash {
+            Some(parent_hash) if parent_hash != block.parent_hash =>
+                Ok(vec![self.rewind_one_block(parent_hash)]),
+            _ => get_log_proofs_from_block(
+                &self.endpoint,
+                &block,
+                &self.filter,
+                self.maybe_cache.as_ref(),
+            )
+                .map(|log_proofs| {
+                    self.record_block_hash(self.next_block_number, block.hash);
+                    self.next_block_number += 1;
+                    log_proofs.into_iter().map(WatchEvent::LogProof).collect()
+                }),
+        }
+    }
+
+    pub fn poll_with<F>(&mut self, mut handle_events: F) -> Result<()>
+        where F: FnMut(Vec<WatchEvent>) -> Result<()>
+    {
+        let final_block_number = self.get_final_block_number()?;
+        while self.next_block_number <= final_block_number {
+            self.process_next_block().and_then(&mut handle_events)?;
+        }
+        Ok(())
+    }
+
+    pub fn poll(&mut self) -> Result<Vec<WatchEvent>> {
+        let mut events = vec![];
+        self.poll_with(|mut block_events| {
+            events.append(&mut block_events);
+            Ok(())
+        })?;
+        Ok(events)
+    }
+}
+
+pub fn get_log_filter(
+    maybe_addresses: Option<String>,
+    maybe_topics: Option<String>,
+) -> Result<LogFilter> {
+    let split = |maybe_list: Option<String>| -> Vec<String> {
+        maybe_list
+            .map(|list| list.split(',').map(|item| item.trim().to_string()).collect())
+            .unwrap_or_default()
+    };
+    let filter = LogFilter {
+        addresses: split(maybe_addresses)
+            .iter()
+            .map(|address| match address.trim_start_matches("0x").len() == 40 {
+                true => Ok(Address::from_slice(&hex::decode(address.trim_start_matches("0x"))?)),
+                false => Err(AppError::Custom(format!("✘ `{}` is not an address!", address))),
+            })
+            .collect::<Result<Vec<Address>>>()?,
+        topics: split(maybe_topics)
+            .iter()
+            .map(|topic| match topic.trim_start_matches("0x").len() == 64 {
+                true => Ok(H256::from_slice(&hex::decode(topic.trim_start_matches("0x"))?)),
+                false => Err(AppError::Custom(format!("✘ `{}` is not a topic hash!", topic))),
+            })
+            .collect::<Result<Vec<H256>>>()?,
+    };
+    match filter.is_empty() {
+        true => Err(AppError::Custom(
+            "✘ Need a `--contract` or `--topic` to filter logs by!".to_string()
+        )),
+        false => Ok(filter),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::get_log::get_logs_bloom_from_logs;
+    use crate::test_utils::{
+        get_temp_cache_dir,
+        get_expected_log,
+        get_sample_mock_node,
+        get_expected_block_1,
+    };
+
+    const WETH_ADDRESS: &str = "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2";
+
+    fn get_weth_watcher(endpoint: String) -> BlockWatcher {
+        let block = get_expected_block_1();
+        BlockWatcher::new(
+            endpoint,
+            get_log_filter(Some(WETH_ADDRESS.to_string()), None).unwrap(),
+            FinalityRequirement::Confirmations(0),
+            None,
+            Some(block.number.as_u64()),
+        ).unwrap()
+    }
+
+    #[test]
+    fn should_match_logs_by_address_and_topic() {
+        let log = get_expected_log();
+        let other_hash = H256::from_slice(&[0x11; 32]);
+        let matching_filter = LogFilter { addresses: vec![log.address], topics: vec![log.topics[0]] };
+        let wrong_topic_filter = LogFilter { addresses: vec![log.address], topics: vec![other_hash] };
+        assert!(matching_filter.matches_log(&log));
+        assert!(!wrong_topic_filter.matches_log(&log));
+    }
+
+    #[test]
+    fn should_check_filter_against_logs_bloom() {
+        let log = get_expected_log();
+        let bloom = get_logs_bloom_from_logs(&vec![log.clone()]).unwrap();
+        let filter = LogFilter { addresses: vec![log.address], topics: vec![] };
+        let other_filter = LogFilter { addresses: vec![Address::from_slice(&[0x11; 20])], topics: vec![] };
+        assert!(filter.may_match_bloom(&bloom));
+        assert!(!filter.may_match_bloom(&Bloom::default()));
+        assert!(!other_filter.may_match_bloom(&bloom));
+    }
+
+    #[test]
+    fn should_err_on_empty_or_invalid_log_filter() {
+        match get_log_filter(None, None) {
+            Err(AppError::Custom(e)) => assert!(e.contains("Need a `--contract` or `--topic`")),
+            _ => panic!("Empty filter should not be accepted!"),
+        }
+        assert!(get_log_filter(Some("0x1234".to_string()), None).is_err());
+    }
+
+    #[test]
+    fn should_poll_and_generate_proofs_for_matching_logs() {
+        let mock_node = get_sample_mock_node();
+        let mut watcher = get_weth_watcher(mock_node.get_endpoint());
+        let block = get_expected_block_1();
+        let events = watcher.poll().unwrap();
+        assert!(events.len() == 10);
+        events
+            .iter()
+            .for_each(|event| match event {
+                WatchEvent::LogProof(log_proof) => {
+                    assert!(log_proof.receipt_proof.block_hash == block.hash);
+                    assert!(!log_proof.log_entry_data.is_empty());
+                },
+                _ => panic!("Watcher should only emit log proofs!"),
+            });
+        assert!(watcher.next_block_number == block.number.as_u64() + 1);
+        assert!(watcher.poll().unwrap().is_empty());
+    }
+
+    #[test]
+    fn should_rewind_and_emit_reorg_when_parent_hash_changes() {
+        let mock_node = get_sample_mock_node();
+        let mut watcher = get_weth_watcher(mock_node.get_endpoint());
+        let block_number = watcher.next_block_number;
+        let stale_hash = H256::from_slice(&[0x11; 32]);
+        watcher.record_block_hash(block_number - 1, stale_hash);
+        let result = watcher.process_next_block().unwrap();
+        assert!(result == vec![WatchEvent::Reorg { block_number: block_number - 1, block_hash: stale_hash }]);
+        assert!(watcher.next_block_number == block_number - 1);
+    }
+
+    #[test]
+    fn should_write_events_to_dir_and_remove_them_on_reorg() {
+        let mock_node = get_sample_mock_node();
+        let mut watcher = get_weth_watcher(mock_node.get_endpoint());
+        let dir = PathBuf::from(get_temp_cache_dir("watch-out-dir"));
+        let output = WatchOutput::Dir(dir.clone());
+        let block = get_expected_block_1();
+        let num_files = || fs::read_dir(&dir).unwrap().count();
+        write_watch_events(&output, &watcher.poll().unwrap()).unwrap();
+        assert!(num_files() == 10);
+        let reorg = WatchEvent::Reorg { block_number: block.number.as_u64(), block_hash: block.hash };
+        write_watch_events(&output, &[reorg]).unwrap();
+        assert!(num_files() == 0);
+    }
+}
@@ -0,0 +1,14 @@
+use alloc::vec::Vec;
+use crate::types::Byte;
+use crate::nibble_utils::Nibbles;
+
+pub const ZERO_BYTE: u8 = 0u8;
+pub const HASH_LENGTH: usize = 32;
+pub const ADDRESS_LENGTH: usize = 20;
+pub const NUM_BITS_IN_NIBBLE: usize = 4;
+pub const NUM_NIBBLES_IN_BYTE: usize = 2;
+pub const HIGH_NIBBLE_MASK: Byte = 15u8; // NOTE: 15u8 == [0,0,0,0,1,1,1,1]
+pub static LEAF_NODE_STRING: &str = "leaf";
+pub static BRANCH_NODE_STRING: &str = "branch";
+pub static EXTENSION_NODE_STRING: &str = "extension";
+pub const EMPTY_NIBBLES: Nibbles = Nibbles { data: Vec::new(), offset: 0 };
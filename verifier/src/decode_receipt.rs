@@ -0,0 +1,153 @@
+use rlp::Rlp;
+use alloc::format;
+use alloc::vec::Vec;
+use crate::errors::VerifierError;
+use crate::utils::{
+    convert_bytes_to_hash,
+    convert_bytes_to_address,
+};
+use crate::types::{
+    Hash,
+    Bytes,
+    Result,
+    Address,
+};
+
+const MAX_TX_TYPE: u8 = 0x7f;
+const STATUS_SUCCESS: [u8; 1] = [1];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DecodedLog {
+    pub address: Address,
+    pub topics: Vec<Hash>,
+    pub data: Bytes,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DecodedReceipt {
+    pub tx_type: Option<u8>,
+    pub status_or_state_root: Bytes,
+    pub cumulative_gas_used: u64,
+    pub logs_bloom: Bytes,
+    pub logs: Vec<DecodedLog>,
+}
+
+impl DecodedReceipt {
+    pub fn is_successful(&self) -> bool {
+        self.status_or_state_root == STATUS_SUCCESS
+    }
+
+    pub fn get_log(&self, log_index: usize) -> Result<&DecodedLog> {
+        self.logs
+            .get(log_index)
+            .ok_or_else(|| VerifierError::Custom(
+                format!("✘ Receipt has no log at index {}!", log_index)
+            ))
+    }
+}
+
+fn decode_log(rlp: Rlp) -> Result<DecodedLog> {
+    let address: Bytes = rlp.val_at(0)?;
+    let topics: Vec<Bytes> = rlp.list_at(1)?;
+    Ok(
+        DecodedLog {
+            address: convert_bytes_to_address(&address)?,
+            topics: topics
+                .iter()
+                .map(|topic| convert_bytes_to_hash(topic))
+                .collect::<Result<Vec<Hash>>>()?,
+            data: rlp.val_at(2)?,
+        }
+    )
+}
+
+fn split_tx_type(receipt_data: &[u8]) -> (Option<u8>, &[u8]) {
+    match receipt_data.split_first() {
+        Some((first_byte, payload)) if *first_byte <= MAX_TX_TYPE => (Some(*first_byte), payload),
+        _ => (None, receipt_data),
+    }
+}
+
+pub fn decode_receipt(receipt_data: &[u8]) -> Result<DecodedReceipt> {
+    let (tx_type, payload) = split_tx_type(receipt_data);
+    let rlp = Rlp::new(payload);
+    Ok(
+        DecodedReceipt {
+            tx_type,
+            status_or_state_root: rlp.val_at(0)?,
+            cumulative_gas_used: rlp.val_at(1)?,
+            logs_bloom: rlp.val_at(2)?,
+            logs: rlp
+                .at(3)?
+                .iter()
+                .map(decode_log)
+                .collect::<Result<Vec<DecodedLog>>>()?,
+        }
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use rlp::RlpStream;
+
+    fn get_sample_log() -> DecodedLog {
+        DecodedLog {
+            address: [0xc0; 20],
+            topics: vec![[0x11; 32], [0x22; 32]],
+            data: vec![0xde, 0xad, 0xbe, 0xef],
+        }
+    }
+
+    fn encode_sample_receipt(status: bool) -> Bytes {
+        let log = get_sample_log();
+        let mut rlp_stream = RlpStream::new_list(4);
+        match status {
+            true => rlp_stream.append(&status),
+            false => rlp_stream.append_empty_data(),
+        };
+        rlp_stream
+            .append(&21_000u64)
+            .append(&vec![0u8; 256])
+            .begin_list(1)
+            .begin_list(3)
+            .append(&log.address.to_vec())
+            .begin_list(log.topics.len());
+        log.topics.iter().for_each(|topic| { rlp_stream.append(&topic.to_vec()); });
+        rlp_stream.append(&log.data);
+        rlp_stream.out()
+    }
+
+    #[test]
+    fn should_decode_receipt_and_its_logs() {
+        let result = decode_receipt(&encode_sample_receipt(true))
+            .unwrap();
+        assert!(result.tx_type.is_none());
+        assert!(result.is_successful());
+        assert!(result.cumulative_gas_used == 21_000);
+        assert!(result.logs_bloom.len() == 256);
+        assert!(*result.get_log(0).unwrap() == get_sample_log());
+    }
+
+    #[test]
+    fn should_decode_typed_receipt() {
+        let mut receipt_data = vec![0x02];
+        receipt_data.append(&mut encode_sample_receipt(false));
+        let result = decode_receipt(&receipt_data)
+            .unwrap();
+        assert!(result.tx_type == Some(0x02));
+        assert!(!result.is_successful());
+        assert!(result.logs.len() == 1);
+    }
+
+    #[test]
+    fn should_err_on_missing_log() {
+        let result = decode_receipt(&encode_sample_receipt(true))
+            .unwrap();
+        match result.get_log(1) {
+            Err(VerifierError::Custom(e)) => assert!(e.contains("no log at index 1")),
+            _ => panic!("Receipt should only have one log!"),
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use core::fmt;
+use alloc::string::{
+    String,
+    ToString,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VerifierError {
+    Custom(String),
+}
+
+impl fmt::Display for VerifierError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            VerifierError::Custom(ref msg) => f.write_str(msg),
+        }
+    }
+}
+
+impl From<rlp::DecoderError> for VerifierError {
+    fn from(e: rlp::DecoderError) -> VerifierError {
+        VerifierError::Custom(e.to_string())
+    }
+}
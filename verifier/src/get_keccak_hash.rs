@@ -0,0 +1,19 @@
+use tiny_keccak::keccak256;
+use crate::types::Hash;
+
+pub fn keccak_hash_bytes(bytes: &[u8]) -> Hash {
+    keccak256(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::convert_bytes_to_hex;
+
+    #[test]
+    fn should_get_keccak_hash_correctly() {
+        let expected_hash = "0x28a564315acde65743f71672cda538275f2118d98b485a30f2af6679bfc510c8";
+        let result = keccak_hash_bytes(&[1, 3, 3, 7]);
+        assert!(convert_bytes_to_hex(&result) == expected_hash);
+    }
+}
@@ -0,0 +1,28 @@
+#![no_std]
+
+extern crate alloc;
+
+pub mod types;
+pub mod utils;
+pub mod errors;
+pub mod constants;
+pub mod trie_nodes;
+pub mod path_codec;
+pub mod nibble_utils;
+pub mod verify_proof;
+pub mod decode_receipt;
+pub mod get_keccak_hash;
+
+pub use crate::errors::VerifierError;
+pub use crate::types::{
+    Hash,
+    Bytes,
+    Result,
+    Address,
+};
+pub use crate::verify_proof::verify_receipt_proof;
+pub use crate::decode_receipt::{
+    DecodedLog,
+    DecodedReceipt,
+    decode_receipt,
+};
@@ -0,0 +1,142 @@
+use core::fmt;
+use alloc::format;
+use crate::errors::VerifierError;
+use crate::types::{
+    Byte,
+    Bytes,
+    Result,
+};
+use crate::constants::{
+    EMPTY_NIBBLES,
+    HIGH_NIBBLE_MASK,
+    NUM_BITS_IN_NIBBLE,
+    NUM_NIBBLES_IN_BYTE,
+};
+
+#[derive(Clone, Eq)]
+pub struct Nibbles {
+    pub data: Bytes,
+    pub offset: usize,
+}
+
+impl PartialEq for Nibbles {
+    fn eq(&self, other: &Self) -> bool {
+        self.data == other.data &&
+        self.offset == other.offset
+    }
+}
+
+impl fmt::Debug for Nibbles {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self == &EMPTY_NIBBLES {
+            true => write!(f, "Nibble array is empty!")?,
+            false => for i in 0..get_length_in_nibbles(self) {
+                write!(
+                    f,
+                    "0x{:01x} ",
+                    get_nibble_at_index(self, i).map_err(|_| fmt::Error)?
+                )?;
+            }
+        };
+        Ok(())
+    }
+}
+
+impl Nibbles {
+    pub fn len(&self) -> usize {
+        get_length_in_nibbles(self)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+pub fn set_nibble_offset_to_zero(nibbles: Nibbles) -> Nibbles {
+    Nibbles { data: nibbles.data, offset: 0 }
+}
+
+pub fn set_nibble_offset_to_one(nibbles: Nibbles) -> Nibbles {
+    Nibbles { data: nibbles.data, offset: 1 }
+}
+
+pub fn get_nibbles_from_bytes(nibbles: Bytes) -> Nibbles {
+    Nibbles { data: nibbles, offset: 0 }
+}
+
+pub fn get_nibbles_from_offset_bytes(nibbles: Bytes) -> Nibbles {
+    Nibbles { data: nibbles, offset: 1 }
+}
+
+pub fn get_length_in_nibbles(nibbles: &Nibbles) -> usize {
+    (nibbles.data.len() * NUM_NIBBLES_IN_BYTE).saturating_sub(nibbles.offset)
+}
+
+fn get_byte_containing_nibble_at_nibble_index(nibbles: &Nibbles, nibble_index: usize) -> Byte {
+    nibbles.data[(nibbles.offset + nibble_index) / NUM_NIBBLES_IN_BYTE]
+}
+
+pub fn get_nibble_at_index(nibbles: &Nibbles, nibble_index: usize) -> Result<Byte> {
+    match nibble_index >= get_length_in_nibbles(nibbles) {
+        true => Err(VerifierError::Custom(
+            format!(
+                "✘ Index {} is out-of-bounds in nibble vector!",
+                nibble_index
+            )
+        )),
+        false => {
+            let byte = get_byte_containing_nibble_at_nibble_index(nibbles, nibble_index);
+            match (nibbles.offset + nibble_index) % NUM_NIBBLES_IN_BYTE {
+                0 => Ok(byte >> NUM_BITS_IN_NIBBLE),
+                _ => Ok(byte & HIGH_NIBBLE_MASK),
+            }
+        }
+    }
+}
+
+pub fn get_nibble_values(nibbles: &Nibbles) -> Result<Bytes> {
+    (0..get_length_in_nibbles(nibbles))
+        .map(|i| get_nibble_at_index(nibbles, i))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    fn get_sample_offset_nibbles() -> Nibbles {
+        get_nibbles_from_offset_bytes(vec![0x01, 0x23, 0x45])
+    }
+
+    #[test]
+    fn should_get_length_in_nibbles() {
+        let nibbles = get_nibbles_from_bytes(vec![0x12, 0x34]);
+        assert!(get_length_in_nibbles(&nibbles) == 4);
+        assert!(get_length_in_nibbles(&get_sample_offset_nibbles()) == 5);
+        assert!(EMPTY_NIBBLES.is_empty());
+    }
+
+    #[test]
+    fn should_get_nibble_values_from_offset_nibbles() {
+        let result = get_nibble_values(&get_sample_offset_nibbles())
+            .unwrap();
+        assert!(result == vec![0x01, 0x02, 0x03, 0x04, 0x05]);
+    }
+
+    #[test]
+    fn should_get_nibble_values_from_nibbles() {
+        let result = get_nibble_values(&get_nibbles_from_bytes(vec![0xab, 0xcd]))
+            .unwrap();
+        assert!(result == vec![0x0a, 0x0b, 0x0c, 0x0d]);
+    }
+
+    #[test]
+    fn should_err_on_out_of_bounds_nibble_index() {
+        let nibbles = get_sample_offset_nibbles();
+        match get_nibble_at_index(&nibbles, nibbles.len()) {
+            Err(VerifierError::Custom(e)) => assert!(e.contains("out-of-bounds")),
+            _ => panic!("Out of bounds index should not be accepted!"),
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use alloc::string::ToString;
+use crate::errors::VerifierError;
+use crate::types::{
+    Bytes,
+    Result,
+};
+use crate::nibble_utils::{
+    Nibbles,
+    get_nibble_at_index,
+    get_nibbles_from_bytes,
+    get_length_in_nibbles,
+    get_nibbles_from_offset_bytes,
+};
+use crate::constants::{
+    EMPTY_NIBBLES,
+    HIGH_NIBBLE_MASK,
+    LEAF_NODE_STRING,
+    EXTENSION_NODE_STRING,
+};
+
+pub const ODD_LENGTH_LEAF_PREFIX_NIBBLE: u8 = 3u8;       // [00000011]
+pub const EVEN_LENGTH_LEAF_PREFIX_BYTE: u8 = 32u8;       // [00100000]
+pub const EVEN_LENGTH_LEAF_PREFIX_NIBBLE: u8 = 2u8;      // [00000010]
+pub const EVEN_LENGTH_EXTENSION_PREFIX_BYTE: u8 = 0u8;   // [00000000]
+pub const ODD_LENGTH_EXTENSION_PREFIX_NIBBLE: u8 = 1u8;  // [00000001]
+pub const EVEN_LENGTH_EXTENSION_PREFIX_NIBBLE: u8 = 0u8; // [00000000]
+
+fn decode_even_length_nibbles(nibbles: Nibbles) -> Nibbles {
+    match get_length_in_nibbles(&nibbles) > 2 {
+        true => get_nibbles_from_bytes(nibbles.data[1..].to_vec()),
+        false => EMPTY_NIBBLES,
+    }
+}
+
+fn decode_odd_length_nibbles(nibbles: Nibbles) -> Nibbles {
+    let mut data = nibbles.data;
+    data[0] &= HIGH_NIBBLE_MASK;
+    get_nibbles_from_offset_bytes(data)
+}
+
+pub fn decode_path_to_nibbles_and_node_type(
+    path: Bytes
+) -> Result<(Nibbles, &'static str)> {
+    let nibbles = get_nibbles_from_bytes(path);
+    match get_nibble_at_index(&nibbles, 0)? {
+        EVEN_LENGTH_LEAF_PREFIX_NIBBLE => Ok((
+            decode_even_length_nibbles(nibbles),
+            LEAF_NODE_STRING
+        )),
+        EVEN_LENGTH_EXTENSION_PREFIX_NIBBLE => Ok((
+            decode_even_length_nibbles(nibbles),
+            EXTENSION_NODE_STRING
+        )),
+        ODD_LENGTH_LEAF_PREFIX_NIBBLE => Ok((
+            decode_odd_length_nibbles(nibbles),
+            LEAF_NODE_STRING
+        )),
+        ODD_LENGTH_EXTENSION_PREFIX_NIBBLE => Ok((
+            decode_odd_length_nibbles(nibbles),
+            EXTENSION_NODE_STRING
+        )),
+        _ => Err(VerifierError::Custom(
+            "✘ Malformed path - cannot determine node type!".to_string()
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use crate::nibble_utils::get_nibble_values;
+
+    #[test]
+    fn should_decode_even_length_leaf_path() {
+        let (nibbles, node_type) = decode_path_to_nibbles_and_node_type(vec![0x20, 0x12, 0x34])
+            .unwrap();
+        assert!(node_type == LEAF_NODE_STRING);
+        assert!(get_nibble_values(&nibbles).unwrap() == vec![0x01, 0x02, 0x03, 0x04]);
+    }
+
+    #[test]
+    fn should_decode_odd_length_extension_path() {
+        let (nibbles, node_type) = decode_path_to_nibbles_and_node_type(vec![0x1a, 0xbc])
+            .unwrap();
+        assert!(node_type == EXTENSION_NODE_STRING);
+        assert!(get_nibble_values(&nibbles).unwrap() == vec![0x0a, 0x0b, 0x0c]);
+    }
+
+    #[test]
+    fn should_decode_empty_even_length_path() {
+        let (nibbles, node_type) = decode_path_to_nibbles_and_node_type(vec![0x20])
+            .unwrap();
+        assert!(node_type == LEAF_NODE_STRING);
+        assert!(nibbles == EMPTY_NIBBLES);
+    }
+
+    #[test]
+    fn should_err_on_malformed_path() {
+        match decode_path_to_nibbles_and_node_type(vec![0x42]) {
+            Err(VerifierError::Custom(e)) => assert!(e.contains("Malformed path")),
+            _ => panic!("Malformed path should not decode!"),
+        }
+    }
+}
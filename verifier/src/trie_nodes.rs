@@ -0,0 +1,125 @@
+use rlp::Rlp;
+use alloc::vec::Vec;
+use alloc::boxed::Box;
+use alloc::string::ToString;
+use crate::errors::VerifierError;
+use crate::nibble_utils::Nibbles;
+use crate::constants::LEAF_NODE_STRING;
+use crate::path_codec::decode_path_to_nibbles_and_node_type;
+use crate::types::{
+    Bytes,
+    Result,
+    ChildNodes,
+};
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum DecodedNode {
+    Leaf {
+        path_nibbles: Nibbles,
+        value: Bytes,
+    },
+    Extension {
+        path_nibbles: Nibbles,
+        value: Bytes,
+    },
+    Branch {
+        branches: Box<ChildNodes>,
+        value: Option<Bytes>,
+    },
+}
+
+fn get_empty_child_nodes() -> ChildNodes {
+    [
+        None, None, None, None,
+        None, None, None, None,
+        None, None, None, None,
+        None, None, None, None,
+    ]
+}
+
+fn get_non_empty_bytes(bytes: &[u8]) -> Option<Bytes> {
+    match bytes.is_empty() {
+        true => None,
+        false => Some(bytes.to_vec()),
+    }
+}
+
+pub fn rlp_decode_node(rlp_data: &[u8]) -> Result<DecodedNode> {
+    let list: Vec<Bytes> = Rlp::new(rlp_data).as_list()?;
+    match list.len() {
+        2 => {
+            let (path_nibbles, node_type) = decode_path_to_nibbles_and_node_type(list[0].clone())?;
+            let value = list[1].clone();
+            match node_type == LEAF_NODE_STRING {
+                true => Ok(DecodedNode::Leaf { path_nibbles, value }),
+                false => Ok(DecodedNode::Extension { path_nibbles, value }),
+            }
+        },
+        17 => {
+            let mut branches = get_empty_child_nodes();
+            for (i, branch) in branches.iter_mut().enumerate() {
+                *branch = get_non_empty_bytes(&list[i]);
+            }
+            Ok(
+                DecodedNode::Branch {
+                    branches: Box::new(branches),
+                    value: get_non_empty_bytes(&list[16]),
+                }
+            )
+        },
+        _ => Err(VerifierError::Custom(
+            "✘ Cannot decode node from rlp data!".to_string()
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use rlp::RlpStream;
+    use crate::nibble_utils::get_nibble_values;
+
+    #[test]
+    fn should_decode_leaf_node() {
+        let mut rlp_stream = RlpStream::new_list(2);
+        rlp_stream.append(&vec![0x20u8, 0x80]).append(&vec![0xdeu8, 0xad]);
+        match rlp_decode_node(&rlp_stream.out()).unwrap() {
+            DecodedNode::Leaf { path_nibbles, value } => {
+                assert!(get_nibble_values(&path_nibbles).unwrap() == vec![0x08, 0x00]);
+                assert!(value == vec![0xde, 0xad]);
+            },
+            _ => panic!("Node should decode to a leaf!"),
+        }
+    }
+
+    #[test]
+    fn should_decode_branch_node() {
+        let mut rlp_stream = RlpStream::new_list(17);
+        for i in 0..16 {
+            match i == 3 {
+                true => rlp_stream.append(&vec![0x11u8; 32]),
+                false => rlp_stream.append_empty_data(),
+            };
+        }
+        rlp_stream.append_empty_data();
+        match rlp_decode_node(&rlp_stream.out()).unwrap() {
+            DecodedNode::Branch { branches, value } => {
+                assert!(value.is_none());
+                assert!(branches[3] == Some(vec![0x11u8; 32]));
+                assert!(branches.iter().filter(|branch| branch.is_some()).count() == 1);
+            },
+            _ => panic!("Node should decode to a branch!"),
+        }
+    }
+
+    #[test]
+    fn should_err_on_wrong_number_of_items() {
+        let mut rlp_stream = RlpStream::new_list(3);
+        rlp_stream.append_empty_data().append_empty_data().append_empty_data();
+        match rlp_decode_node(&rlp_stream.out()) {
+            Err(VerifierError::Custom(e)) => assert!(e.contains("Cannot decode node")),
+            _ => panic!("Three item list should not decode to a node!"),
+        }
+    }
+}
@@ -0,0 +1,10 @@
+use core::result;
+use alloc::vec::Vec;
+use crate::errors::VerifierError;
+
+pub type Byte = u8;
+pub type Bytes = Vec<Byte>;
+pub type Hash = [Byte; 32];
+pub type Address = [Byte; 20];
+pub type ChildNodes = [Option<Bytes>; 16];
+pub type Result<T> = result::Result<T, VerifierError>;
@@ -0,0 +1,75 @@
+use core::fmt::Write;
+use alloc::format;
+use alloc::string::String;
+use crate::errors::VerifierError;
+use crate::types::{
+    Hash,
+    Result,
+    Address,
+};
+use crate::constants::{
+    HASH_LENGTH,
+    ADDRESS_LENGTH,
+};
+
+pub fn convert_bytes_to_hex(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .fold(String::from("0x"), |mut hex, byte| {
+            let _ = write!(hex, "{:02x}", byte);
+            hex
+        })
+}
+
+pub fn convert_bytes_to_hash(bytes: &[u8]) -> Result<Hash> {
+    match bytes.len() == HASH_LENGTH {
+        true => {
+            let mut hash = [0u8; HASH_LENGTH];
+            hash.copy_from_slice(bytes);
+            Ok(hash)
+        },
+        false => Err(VerifierError::Custom(
+            format!("✘ Expected {} bytes for a hash, got {}!", HASH_LENGTH, bytes.len())
+        ))
+    }
+}
+
+pub fn convert_bytes_to_address(bytes: &[u8]) -> Result<Address> {
+    match bytes.len() == ADDRESS_LENGTH {
+        true => {
+            let mut address = [0u8; ADDRESS_LENGTH];
+            address.copy_from_slice(bytes);
+            Ok(address)
+        },
+        false => Err(VerifierError::Custom(
+            format!("✘ Expected {} bytes for an address, got {}!", ADDRESS_LENGTH, bytes.len())
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_convert_bytes_to_hex() {
+        let result = convert_bytes_to_hex(&[0x0f, 0xab, 0x00]);
+        assert!(result == "0x0fab00");
+    }
+
+    #[test]
+    fn should_convert_bytes_to_hash() {
+        let bytes = [0x11u8; HASH_LENGTH];
+        let result = convert_bytes_to_hash(&bytes)
+            .unwrap();
+        assert!(result == bytes);
+    }
+
+    #[test]
+    fn should_err_converting_wrong_length_bytes_to_address() {
+        match convert_bytes_to_address(&[0x11u8; HASH_LENGTH]) {
+            Err(VerifierError::Custom(e)) => assert!(e.contains("Expected 20 bytes")),
+            _ => panic!("Hash should not convert to an address!"),
+        }
+    }
+}
@@ -0,0 +1,180 @@
+use alloc::format;
+use alloc::string::ToString;
+use crate::errors::VerifierError;
+use crate::get_keccak_hash::keccak_hash_bytes;
+use crate::nibble_utils::{
+    get_nibble_values,
+    get_nibbles_from_bytes,
+};
+use crate::trie_nodes::{
+    DecodedNode,
+    rlp_decode_node,
+};
+use crate::utils::{
+    convert_bytes_to_hex,
+    convert_bytes_to_hash,
+};
+use crate::types::{
+    Hash,
+    Bytes,
+    Result,
+};
+
+pub fn get_receipt_key(tx_index: usize) -> Result<Bytes> {
+    get_nibble_values(&get_nibbles_from_bytes(rlp::encode(&tx_index)))
+}
+
+fn check_node_hash(encoded_node: &[u8], expected_hash: &Hash) -> Result<()> {
+    let hash = keccak_hash_bytes(encoded_node);
+    match &hash == expected_hash {
+        true => Ok(()),
+        false => Err(VerifierError::Custom(
+            format!(
+                "✘ Proof node hashes to {}, expected {}!",
+                convert_bytes_to_hex(&hash),
+                convert_bytes_to_hex(expected_hash),
+            )
+        ))
+    }
+}
+
+fn get_child_hash(reference: &[u8]) -> Result<Hash> {
+    convert_bytes_to_hash(reference)
+        .map_err(|_| VerifierError::Custom(
+            "✘ Proof references an inline node, which is not supported!".to_string()
+        ))
+}
+
+fn get_value_at_end_of_proof(remaining_nodes: &[Bytes], value: Bytes) -> Result<Bytes> {
+    match remaining_nodes.is_empty() {
+        true => Ok(value),
+        false => Err(VerifierError::Custom(
+            format!(
+                "✘ Proof has {} unused node(s) after the receipt!",
+                remaining_nodes.len(),
+            )
+        ))
+    }
+}
+
+fn get_key_not_in_proof_err() -> VerifierError {
+    VerifierError::Custom("✘ Proof does not contain the transaction index's key!".to_string())
+}
+
+fn verify_node(node: DecodedNode, remaining_nodes: &[Bytes], key: &[u8]) -> Result<Bytes> {
+    match node {
+        DecodedNode::Leaf { path_nibbles, value } =>
+            match get_nibble_values(&path_nibbles)? == key {
+                true => get_value_at_end_of_proof(remaining_nodes, value),
+                false => Err(get_key_not_in_proof_err()),
+            },
+        DecodedNode::Extension { path_nibbles, value } => {
+            let path = get_nibble_values(&path_nibbles)?;
+            match key.starts_with(&path) {
+                true => verify_proof_nodes(
+                    remaining_nodes,
+                    &get_child_hash(&value)?,
+                    &key[path.len()..],
+                ),
+                false => Err(get_key_not_in_proof_err()),
+            }
+        },
+        DecodedNode::Branch { branches, value } => match key.split_first() {
+            None => match value {
+                Some(value) => get_value_at_end_of_proof(remaining_nodes, value),
+                None => Err(get_key_not_in_proof_err()),
+            },
+            Some((nibble, remaining_key)) => match &branches[*nibble as usize] {
+                Some(reference) => verify_proof_nodes(
+                    remaining_nodes,
+                    &get_child_hash(reference)?,
+                    remaining_key,
+                ),
+                None => Err(get_key_not_in_proof_err()),
+            }
+        },
+    }
+}
+
+fn verify_proof_nodes(
+    proof_nodes: &[Bytes],
+    expected_hash: &Hash,
+    key: &[u8],
+) -> Result<Bytes> {
+    match proof_nodes.split_first() {
+        None => Err(VerifierError::Custom(
+            "✘ Proof ends before reaching the receipt!".to_string()
+        )),
+        Some((encoded_node, remaining_nodes)) =>
+            check_node_hash(encoded_node, expected_hash)
+                .and_then(|_| rlp_decode_node(encoded_node))
+                .and_then(|node| verify_node(node, remaining_nodes, key))
+    }
+}
+
+pub fn verify_receipt_proof(
+    proof_nodes: &[Bytes],
+    receipts_root: &Hash,
+    tx_index: usize,
+) -> Result<Bytes> {
+    get_receipt_key(tx_index)
+        .and_then(|key| verify_proof_nodes(proof_nodes, receipts_root, &key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use rlp::RlpStream;
+
+    fn get_sample_receipt() -> Bytes {
+        vec![0xc0, 0xff, 0xee]
+    }
+
+    fn get_single_leaf_proof(tx_index: usize) -> (Vec<Bytes>, Hash) {
+        let key = rlp::encode(&tx_index);
+        let mut encoded_path = vec![0x20u8];
+        encoded_path.extend_from_slice(&key);
+        let mut rlp_stream = RlpStream::new_list(2);
+        rlp_stream.append(&encoded_path).append(&get_sample_receipt());
+        let leaf = rlp_stream.out();
+        let root = keccak_hash_bytes(&leaf);
+        (vec![leaf], root)
+    }
+
+    #[test]
+    fn should_verify_single_leaf_proof() {
+        let (proof_nodes, root) = get_single_leaf_proof(5);
+        let result = verify_receipt_proof(&proof_nodes, &root, 5)
+            .unwrap();
+        assert!(result == get_sample_receipt());
+    }
+
+    #[test]
+    fn should_err_on_wrong_receipts_root() {
+        let (proof_nodes, _) = get_single_leaf_proof(5);
+        match verify_receipt_proof(&proof_nodes, &[0u8; 32], 5) {
+            Err(VerifierError::Custom(e)) => assert!(e.contains("hashes to")),
+            _ => panic!("Proof should not verify against wrong root!"),
+        }
+    }
+
+    #[test]
+    fn should_err_on_wrong_tx_index() {
+        let (proof_nodes, root) = get_single_leaf_proof(5);
+        match verify_receipt_proof(&proof_nodes, &root, 6) {
+            Err(VerifierError::Custom(e)) => assert!(e.contains("does not contain")),
+            _ => panic!("Proof should not verify for wrong index!"),
+        }
+    }
+
+    #[test]
+    fn should_err_on_empty_proof() {
+        let (_, root) = get_single_leaf_proof(5);
+        match verify_receipt_proof(&[], &root, 5) {
+            Err(VerifierError::Custom(e)) => assert!(e.contains("ends before")),
+            _ => panic!("Empty proof should not verify!"),
+        }
+    }
+}